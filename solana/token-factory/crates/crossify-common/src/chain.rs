@@ -0,0 +1,24 @@
+// Chain module for Crossify
+// This file contains the Wormhole chain ids and message types shared with the EVM contracts
+
+// Chain IDs in Wormhole ecosystem
+pub const CHAIN_ID_SOLANA: u16 = 1;
+pub const CHAIN_ID_ETHEREUM: u16 = 2;
+pub const CHAIN_ID_BSC: u16 = 4;
+pub const CHAIN_ID_BASE: u16 = 30; // Example value, may need to be updated
+
+// Message types
+pub const MSG_TYPE_TOKEN_CREATION: u8 = 1;
+pub const MSG_TYPE_PRICE_UPDATE: u8 = 2;
+pub const MSG_TYPE_LIQUIDITY_UPDATE: u8 = 3;
+pub const MSG_TYPE_SUPPLY_UPDATE: u8 = 4;
+pub const MSG_TYPE_TOKEN_TRANSFER: u8 = 5;
+pub const MSG_TYPE_BATCH_SYNC: u8 = 6;
+pub const MSG_TYPE_REMOTE_BUY: u8 = 7;
+pub const MSG_TYPE_REMOTE_SELL: u8 = 8;
+pub const MSG_TYPE_QUOTE_TRANSFER: u8 = 9;
+pub const MSG_TYPE_REFUND: u8 = 10;
+
+pub fn is_evm_chain(chain_id: u16) -> bool {
+    matches!(chain_id, CHAIN_ID_ETHEREUM | CHAIN_ID_BSC | CHAIN_ID_BASE)
+}
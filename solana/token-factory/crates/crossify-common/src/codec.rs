@@ -0,0 +1,345 @@
+// Codec module for Crossify
+// This file contains the framing of Wormhole messages, the ABI transcoding of messages sent by
+// EVM chains, batch sync messages and the Core Bridge PostedVAA layout
+
+use std::fmt;
+
+use crate::chain::*;
+
+// Every message is framed as type byte, u32 body length, body, so a reader can tell a short or
+// padded message from a well-formed one before decoding the body. The bytes of each message type
+// are pinned by integration/fixtures/wormhole_payloads.json, shared with the EVM contracts
+pub const MESSAGE_HEADER_LEN: usize = 5;
+
+// First byte of an ABI-encoded message, followed by the message type and one 32-byte word per
+// field. Legacy Borsh messages start with their message type, which is always below it
+pub const ABI_WIRE_VERSION: u8 = 0x81;
+
+// Solidity ABI word size
+const ABI_WORD_LEN: usize = 32;
+
+// Longest batch a remote chain may send; bounds the allocation made while splitting it
+pub const MAX_BATCH_SYNC_MESSAGES: usize = 64;
+
+// PostedVAA account layout: "vaa" magic, version, consistency level, vaa time, signature set,
+// submission time, nonce, sequence, emitter chain, emitter address, then a u32-prefixed payload
+const POSTED_VAA_MAGIC: &[u8; 3] = b"vaa";
+const POSTED_VAA_VERSION: u8 = 1;
+const POSTED_VAA_HEADER_LEN: usize = 91;
+
+/// Why a message failed to decode. `what` names the structure being read, for logs
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CodecError {
+    EmptyPayload,
+    TruncatedPayload {
+        what: &'static str,
+        len: usize,
+        expected: usize,
+    },
+    TrailingBytes {
+        what: &'static str,
+        len: usize,
+        expected: usize,
+    },
+    UnknownMessageType {
+        message_type: u8,
+    },
+    UnknownVersion {
+        what: &'static str,
+        version: u8,
+        expected: u8,
+    },
+    TooManyMessages {
+        count: usize,
+        max: usize,
+    },
+    // A field that holds a value outside its type, or a malformed header
+    Malformed {
+        what: &'static str,
+        message_type: u8,
+    },
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodecError::EmptyPayload => write!(f, "message is empty"),
+            CodecError::TruncatedPayload { what, len, expected } => {
+                write!(f, "{} len={} expected_len={} is truncated", what, len, expected)
+            }
+            CodecError::TrailingBytes { what, len, expected } => {
+                write!(f, "{} len={} expected_len={} has trailing bytes", what, len, expected)
+            }
+            CodecError::UnknownMessageType { message_type } => {
+                write!(f, "message_type={} has no ABI layout", message_type)
+            }
+            CodecError::UnknownVersion {
+                what,
+                version,
+                expected,
+            } => {
+                write!(f, "{} version={} expected={}", what, version, expected)
+            }
+            CodecError::TooManyMessages { count, max } => write!(f, "batch_sync count={} max={}", count, max),
+            CodecError::Malformed { what, message_type } => {
+                write!(f, "{} message_type={} failed to deserialize", what, message_type)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CodecError {}
+
+// Checks that exactly `expected` bytes are present
+fn require_len(what: &'static str, len: usize, expected: usize) -> Result<(), CodecError> {
+    if len < expected {
+        return Err(CodecError::TruncatedPayload { what, len, expected });
+    }
+    if len > expected {
+        return Err(CodecError::TrailingBytes { what, len, expected });
+    }
+    Ok(())
+}
+
+fn require_at_least(what: &'static str, len: usize, expected: usize) -> Result<(), CodecError> {
+    if len < expected {
+        return Err(CodecError::TruncatedPayload { what, len, expected });
+    }
+    Ok(())
+}
+
+// Frames a Borsh-encoded body as a message of `message_type`
+pub fn frame_message(message_type: u8, body: &[u8]) -> Vec<u8> {
+    let mut message = Vec::with_capacity(MESSAGE_HEADER_LEN + body.len());
+    message.push(message_type);
+    message.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    message.extend_from_slice(body);
+    message
+}
+
+// Splits a Wormhole message into its type and body; the body must be exactly as long as the
+// header declares. ABI-encoded messages are accepted too and returned with their body re-encoded
+// as Borsh, so parsers handle both formats while remote chains migrate
+pub fn deserialize_message(data: &[u8]) -> Result<(u8, Vec<u8>), CodecError> {
+    if data.is_empty() {
+        return Err(CodecError::EmptyPayload);
+    }
+    if data[0] == ABI_WIRE_VERSION {
+        return deserialize_abi_message(data);
+    }
+    require_at_least("message", data.len(), MESSAGE_HEADER_LEN)?;
+
+    let message_type = data[0];
+    let body_len = u32::from_le_bytes(data[1..MESSAGE_HEADER_LEN].try_into().unwrap()) as usize;
+    require_len("message body", data.len() - MESSAGE_HEADER_LEN, body_len)?;
+    Ok((message_type, data[MESSAGE_HEADER_LEN..].to_vec()))
+}
+
+// Field kinds of the ABI-encoded message types; each takes one word
+#[derive(Clone, Copy)]
+enum AbiField {
+    Bytes32, // Pubkey or UniversalAddress
+    U8,
+    U16,
+    U64,
+    I64,
+}
+
+// Field layout of each message type that may arrive ABI-encoded, in payload struct order.
+// Token creation and batch sync messages carry dynamic data and stay Borsh-only
+fn abi_layout(message_type: u8) -> Option<&'static [AbiField]> {
+    use AbiField::*;
+    match message_type {
+        MSG_TYPE_PRICE_UPDATE => Some(&[Bytes32, U64, U64, U64, U64, I64]),
+        MSG_TYPE_LIQUIDITY_UPDATE => Some(&[Bytes32, U64, U64, U64, U64, I64]),
+        MSG_TYPE_SUPPLY_UPDATE => Some(&[Bytes32, U64, U64, U64, I64]),
+        MSG_TYPE_TOKEN_TRANSFER => Some(&[Bytes32, U64, U64, Bytes32, U16, Bytes32, I64]),
+        MSG_TYPE_REMOTE_BUY => Some(&[Bytes32, U64, U64, U64, Bytes32, U16, Bytes32, I64]),
+        MSG_TYPE_REMOTE_SELL => Some(&[Bytes32, U64, U64, U64, Bytes32, I64]),
+        MSG_TYPE_QUOTE_TRANSFER => Some(&[Bytes32, U64, Bytes32, U64, U16, Bytes32, I64]),
+        MSG_TYPE_REFUND => Some(&[Bytes32, U64, U64, U8, U8, U64, Bytes32, I64]),
+        _ => None,
+    }
+}
+
+// Splits an ABI-encoded message into its type and its body transcoded to Borsh. Every word must
+// hold a value that fits its field, so one message has exactly one accepted encoding
+fn deserialize_abi_message(data: &[u8]) -> Result<(u8, Vec<u8>), CodecError> {
+    require_at_least("abi message", data.len(), 2)?;
+    let message_type = data[1];
+    let layout = abi_layout(message_type).ok_or(CodecError::UnknownMessageType { message_type })?;
+    let words = &data[2..];
+    let expected_len = layout.len() * ABI_WORD_LEN;
+    require_len("abi message body", words.len(), expected_len)?;
+
+    let mut body = Vec::with_capacity(expected_len);
+    for (field, word) in layout.iter().zip(words.chunks_exact(ABI_WORD_LEN)) {
+        // Integers are big-endian and right-aligned; the padding must be zero, or all ones
+        // for a negative i64
+        let (width, padding) = match field {
+            AbiField::Bytes32 => {
+                body.extend_from_slice(word);
+                continue;
+            }
+            AbiField::U8 => (1, 0x00),
+            AbiField::U16 => (2, 0x00),
+            AbiField::U64 => (8, 0x00),
+            AbiField::I64 => (8, if word[ABI_WORD_LEN - 8] & 0x80 != 0 { 0xff } else { 0x00 }),
+        };
+        let (high, value) = word.split_at(ABI_WORD_LEN - width);
+        if high.iter().any(|byte| *byte != padding) {
+            return Err(CodecError::Malformed {
+                what: "abi word",
+                message_type,
+            });
+        }
+        body.extend(value.iter().rev());
+    }
+    Ok((message_type, body))
+}
+
+// Serializes a batch of complete messages as u16 count, then u32-length-prefixed entries
+pub fn serialize_batch_sync_message(messages: &[Vec<u8>]) -> Vec<u8> {
+    let mut body = Vec::new();
+    body.extend_from_slice(&(messages.len() as u16).to_le_bytes());
+    for entry in messages.iter() {
+        body.extend_from_slice(&(entry.len() as u32).to_le_bytes());
+        body.extend_from_slice(entry);
+    }
+    frame_message(MSG_TYPE_BATCH_SYNC, &body)
+}
+
+// Splits a batch sync body (after the message header) into its messages
+pub fn parse_batch_sync_message(payload: &[u8]) -> Result<Vec<Vec<u8>>, CodecError> {
+    require_at_least("batch_sync", payload.len(), 2)?;
+    let count = u16::from_le_bytes([payload[0], payload[1]]) as usize;
+    if count > MAX_BATCH_SYNC_MESSAGES {
+        return Err(CodecError::TooManyMessages {
+            count,
+            max: MAX_BATCH_SYNC_MESSAGES,
+        });
+    }
+    let mut offset = 2;
+    let mut messages = Vec::with_capacity(count);
+    for _ in 0..count {
+        require_at_least("batch_sync entry header", payload.len(), offset + 4)?;
+        let len = u32::from_le_bytes(payload[offset..offset + 4].try_into().unwrap()) as usize;
+        offset += 4;
+        require_at_least("batch_sync entry", payload.len(), offset + len)?;
+        messages.push(payload[offset..offset + len].to_vec());
+        offset += len;
+    }
+    require_len("batch_sync", payload.len(), offset)?;
+    Ok(messages)
+}
+
+/// Message fields of a Core Bridge PostedVAA account
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PostedVaa {
+    pub consistency_level: u8,
+    pub timestamp: u32,
+    pub nonce: u32,
+    pub sequence: u64,
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub payload: Vec<u8>,
+}
+
+// Parses the data of a Core Bridge PostedVAA account
+pub fn parse_posted_vaa(data: &[u8]) -> Result<PostedVaa, CodecError> {
+    require_at_least("posted_vaa", data.len(), POSTED_VAA_HEADER_LEN + 4)?;
+    if &data[..3] != POSTED_VAA_MAGIC {
+        return Err(CodecError::Malformed {
+            what: "posted_vaa magic",
+            message_type: 0,
+        });
+    }
+    if data[3] != POSTED_VAA_VERSION {
+        return Err(CodecError::UnknownVersion {
+            what: "posted_vaa",
+            version: data[3],
+            expected: POSTED_VAA_VERSION,
+        });
+    }
+    let read_u32 = |offset: usize| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+
+    let mut emitter_address = [0u8; 32];
+    emitter_address.copy_from_slice(&data[59..91]);
+    let payload_len = read_u32(POSTED_VAA_HEADER_LEN) as usize;
+    let payload_start = POSTED_VAA_HEADER_LEN + 4;
+    require_at_least("posted_vaa payload", data.len(), payload_start + payload_len)?;
+
+    Ok(PostedVaa {
+        consistency_level: data[4],
+        timestamp: read_u32(5),
+        nonce: read_u32(45),
+        sequence: u64::from_le_bytes(data[49..57].try_into().unwrap()),
+        emitter_chain: u16::from_le_bytes([data[57], data[58]]),
+        emitter_address,
+        payload: data[payload_start..payload_start + payload_len].to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abi_supply_update(words: [[u8; 32]; 5]) -> Vec<u8> {
+        let mut data = vec![ABI_WIRE_VERSION, MSG_TYPE_SUPPLY_UPDATE];
+        for word in words {
+            data.extend_from_slice(&word);
+        }
+        data
+    }
+
+    fn uint_word(value: u64) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        word[24..].copy_from_slice(&value.to_be_bytes());
+        word
+    }
+
+    #[test]
+    fn abi_messages_transcode_to_borsh() {
+        let mut timestamp = [0xffu8; 32];
+        timestamp[24..].copy_from_slice(&(-5i64).to_be_bytes());
+        let data = abi_supply_update([[7u8; 32], uint_word(3), uint_word(1_000), uint_word(10), timestamp]);
+
+        let (message_type, body) = deserialize_message(&data).unwrap();
+        assert_eq!(message_type, MSG_TYPE_SUPPLY_UPDATE);
+        let mut expected = vec![7u8; 32];
+        for value in [3u64, 1_000, 10] {
+            expected.extend_from_slice(&value.to_le_bytes());
+        }
+        expected.extend_from_slice(&(-5i64).to_le_bytes());
+        assert_eq!(body, expected);
+    }
+
+    #[test]
+    fn abi_words_with_dirty_padding_are_rejected() {
+        let mut token_id = uint_word(3);
+        token_id[0] = 1;
+        let data = abi_supply_update([[7u8; 32], token_id, uint_word(1_000), uint_word(10), uint_word(0)]);
+        assert_eq!(
+            deserialize_message(&data),
+            Err(CodecError::Malformed {
+                what: "abi word",
+                message_type: MSG_TYPE_SUPPLY_UPDATE
+            })
+        );
+    }
+
+    #[test]
+    fn batches_round_trip_and_reject_trailing_bytes() {
+        let messages = vec![frame_message(MSG_TYPE_PRICE_UPDATE, &[1, 2, 3]), Vec::new()];
+        let (message_type, body) = deserialize_message(&serialize_batch_sync_message(&messages)).unwrap();
+        assert_eq!(message_type, MSG_TYPE_BATCH_SYNC);
+        assert_eq!(parse_batch_sync_message(&body).unwrap(), messages);
+
+        let mut padded = body.clone();
+        padded.push(0);
+        assert!(matches!(
+            parse_batch_sync_message(&padded),
+            Err(CodecError::TrailingBytes { .. })
+        ));
+    }
+}
@@ -0,0 +1,137 @@
+// Curve module for Crossify
+// This file contains the bonding curve pricing that quotes every buy and sell
+
+use std::fmt;
+
+/// Pricing parameters of a bonding curve; prices are per whole token, in quote units
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CurveParams {
+    pub curve_type: u8, // 0: Linear, 1: Exponential, 2: Bancor
+    pub base_price: u64,
+    pub slope: u64,
+    pub reserve_ratio: u16, // For Bancor formula, represented as parts per 1000
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveError {
+    MathOverflow,
+    InvalidCurveType { curve_type: u8 },
+}
+
+impl fmt::Display for CurveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CurveError::MathOverflow => write!(f, "curve quote overflows"),
+            CurveError::InvalidCurveType { curve_type } => write!(f, "curve_type={} is not a known curve", curve_type),
+        }
+    }
+}
+
+impl std::error::Error for CurveError {}
+
+// Scale of the exponential curve's slope: growth per whole token in parts per million of the base price
+pub const EXPONENTIAL_SLOPE_SCALE: u128 = 1_000_000;
+
+// Price of `amount` base units starting at `supply` base units.
+// A trade pays the area under the curve between the two supplies, so splitting a trade up never
+// changes its total and selling what was just bought returns exactly what it cost.
+pub fn quote(curve: &CurveParams, decimals: u8, supply: u64, amount: u64) -> Result<u64, CurveError> {
+    let end = supply.checked_add(amount).ok_or(CurveError::MathOverflow)?;
+    let cost = curve_area(curve, decimals, end)?
+        .checked_sub(curve_area(curve, decimals, supply)?)
+        .ok_or(CurveError::MathOverflow)?;
+    u64::try_from(cost).map_err(|_| CurveError::MathOverflow)
+}
+
+// Quote paid to take the curve from zero to `supply` base units. Curve parameters price whole
+// tokens, so this sums the price of every whole token below `supply` and charges the partial
+// token on top pro rata. The result never decreases as `supply` grows.
+pub fn curve_area(curve: &CurveParams, decimals: u8, supply: u64) -> Result<u128, CurveError> {
+    let unit = decimals_unit(decimals);
+    let whole = supply as u128 / unit;
+    let partial = supply as u128 % unit;
+    let below = whole_token_area(curve, whole)?;
+    if partial == 0 {
+        return Ok(below);
+    }
+
+    let next_price = whole_token_area(curve, whole + 1)?
+        .checked_sub(below)
+        .ok_or(CurveError::MathOverflow)?;
+    let partial_cost = next_price.checked_mul(partial).ok_or(CurveError::MathOverflow)? / unit;
+    below.checked_add(partial_cost).ok_or(CurveError::MathOverflow)
+}
+
+// Largest buy, in base units, that `quote_amount` pays for at `supply`. The area under the curve
+// never falls as supply grows, so a binary search over the amount finds it
+pub fn tokens_for_quote(curve: &CurveParams, decimals: u8, supply: u64, quote_amount: u64) -> Result<u64, CurveError> {
+    let budget = curve_area(curve, decimals, supply)?.saturating_add(quote_amount as u128);
+    let mut low: u64 = 0;
+    let mut high: u64 = u64::MAX - supply;
+    while low < high {
+        let mid = low + (high - low) / 2 + 1;
+        // An area too large to represent is beyond any budget
+        if matches!(curve_area(curve, decimals, supply + mid), Ok(area) if area <= budget) {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    Ok(low)
+}
+
+// Sum of the prices of whole tokens 0..`count`, in closed form per curve type
+fn whole_token_area(curve: &CurveParams, count: u128) -> Result<u128, CurveError> {
+    let base_price = curve.base_price as u128;
+    let slope = curve.slope as u128;
+    let area = match curve.curve_type {
+        // P(k) = base_price + slope * k
+        0 => linear_area(base_price, slope, 1, count),
+        // P(k) = base_price * (1 + slope * k / 1_000_000), a linearised exponential
+        1 => base_price
+            .checked_mul(slope)
+            .and_then(|growth| linear_area(base_price, growth, EXPONENTIAL_SLOPE_SCALE, count)),
+        // P(k) = base_price * (k / 1000)^(1 - reserve_ratio / 1000) in whole steps: flat for any
+        // reserve ratio, and base_price per thousand tokens (at least base_price) at zero
+        2 if curve.reserve_ratio > 0 => base_price.checked_mul(count),
+        2 => {
+            let steps = count / 1000;
+            let thousands = triangular(steps)
+                .and_then(|t| t.checked_mul(1000))
+                .and_then(|t| t.checked_add(steps * (count % 1000)));
+            thousands
+                .and_then(|t| t.checked_add(count.min(1000)))
+                .and_then(|t| t.checked_mul(base_price))
+        }
+        curve_type => return Err(CurveError::InvalidCurveType { curve_type }),
+    };
+    area.ok_or(CurveError::MathOverflow)
+}
+
+// Sum over k in 0..`count` of base_price + slope * k / scale, with the division applied once to
+// the total
+fn linear_area(base_price: u128, slope: u128, scale: u128, count: u128) -> Option<u128> {
+    let flat = base_price.checked_mul(count)?;
+    let rising = slope.checked_mul(triangular(count)?)? / scale;
+    flat.checked_add(rising)
+}
+
+// 0 + 1 + ... + (n - 1)
+fn triangular(n: u128) -> Option<u128> {
+    if n % 2 == 0 {
+        (n / 2).checked_mul(n.saturating_sub(1))
+    } else {
+        n.checked_mul((n - 1) / 2)
+    }
+}
+
+// Base units per whole token, saturating for decimals beyond u128 range
+pub fn decimals_unit(decimals: u8) -> u128 {
+    10_u128.checked_pow(decimals as u32).unwrap_or(u128::MAX)
+}
+
+// `bps` basis points of `amount`, rounded down
+pub fn bps_of(amount: u64, bps: u16) -> Option<u64> {
+    let part = (amount as u128).checked_mul(bps as u128)? / 10_000;
+    u64::try_from(part).ok()
+}
@@ -0,0 +1,14 @@
+// Shared library for Crossify
+// This file contains the chain ids, wire codec and curve math used by the token factory program
+// and by off-chain tools, which must agree with it byte for byte
+
+// `u64::is_multiple_of` is newer than the SBF toolchain the program is built with
+#![allow(clippy::manual_is_multiple_of)]
+
+pub mod chain;
+pub mod codec;
+pub mod curve;
+
+pub use chain::*;
+pub use codec::*;
+pub use curve::*;
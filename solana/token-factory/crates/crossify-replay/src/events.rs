@@ -0,0 +1,244 @@
+// Events module for crossify-replay
+// This file contains the decoding of token factory events from transaction logs
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::Engine;
+use token_factory::{
+    RefundedEvent, RemoteBuyExecutedEvent, RemoteSellExecutedEvent, SecondaryTokensBoughtEvent,
+    SecondaryTokensSoldEvent, TokenCreatedEvent, TokensBoughtEvent, TokensBurnedEvent, TokensSoldEvent,
+};
+
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+const LOG_TRUNCATED: &str = "Log truncated";
+
+/// The events that move TokenStats, reduced to what the replay needs
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReplayEvent {
+    Created {
+        mint: Pubkey,
+    },
+    // `volume` is what the trade added to total_volume, `sell_tax` what it added to
+    // total_creator_fees; `curve_supply` is the supply after the trade
+    Traded {
+        mint: Pubkey,
+        volume: u64,
+        sell_tax: u64,
+        curve_supply: u64,
+    },
+    // Secondary sells count their proceeds in the primary asset, which the event does not carry
+    SecondarySold {
+        mint: Pubkey,
+        curve_supply: u64,
+    },
+    Burned {
+        mint: Pubkey,
+        total_burned: u64,
+    },
+    Refunded {
+        mint: Pubkey,
+        amount: u64,
+    },
+}
+
+impl ReplayEvent {
+    pub fn mint(&self) -> Pubkey {
+        match self {
+            ReplayEvent::Created { mint }
+            | ReplayEvent::Traded { mint, .. }
+            | ReplayEvent::SecondarySold { mint, .. }
+            | ReplayEvent::Burned { mint, .. }
+            | ReplayEvent::Refunded { mint, .. } => *mint,
+        }
+    }
+}
+
+/// Events emitted by `program_id` in one transaction's log, in order
+pub struct ParsedLog {
+    pub events: Vec<ReplayEvent>,
+    pub truncated: bool, // The node cut the log short; later events are missing
+}
+
+// Walks the invocation stack in the log, so only `Program data:` lines written while
+// `program_id` is the running program are taken, not those of programs it calls or is called by
+pub fn parse_log(program_id: &Pubkey, logs: &[String]) -> ParsedLog {
+    let program_id = program_id.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+    let mut truncated = false;
+    for line in logs {
+        if let Some(rest) = line.strip_prefix("Program ") {
+            let mut words = rest.split_whitespace();
+            let (Some(id), Some(action)) = (words.next(), words.next()) else {
+                continue;
+            };
+            match action {
+                "invoke" => {
+                    stack.push(id);
+                    continue;
+                }
+                "success" | "failed:" => {
+                    stack.pop();
+                    continue;
+                }
+                _ => {}
+            }
+        }
+        if line.starts_with(LOG_TRUNCATED) {
+            truncated = true;
+            break;
+        }
+        if stack.last() != Some(&program_id.as_str()) {
+            continue;
+        }
+        if let Some(data) = line.strip_prefix(PROGRAM_DATA_PREFIX) {
+            if let Ok(bytes) = base64::engine::general_purpose::STANDARD.decode(data) {
+                events.extend(decode_event(&bytes));
+            }
+        }
+    }
+    ParsedLog { events, truncated }
+}
+
+fn decode<T: Discriminator + AnchorDeserialize>(data: &[u8]) -> Option<T> {
+    if data.len() < 8 || data[..8] != T::DISCRIMINATOR {
+        return None;
+    }
+    T::deserialize(&mut &data[8..]).ok()
+}
+
+// Decodes one emitted event; events that do not move TokenStats are skipped
+pub fn decode_event(data: &[u8]) -> Option<ReplayEvent> {
+    if let Some(event) = decode::<TokenCreatedEvent>(data) {
+        return Some(ReplayEvent::Created { mint: event.mint });
+    }
+    if let Some(event) = decode::<TokensBoughtEvent>(data) {
+        return Some(ReplayEvent::Traded {
+            mint: event.mint,
+            volume: event.cost,
+            sell_tax: 0,
+            curve_supply: event.curve_supply,
+        });
+    }
+    if let Some(event) = decode::<TokensSoldEvent>(data) {
+        return Some(ReplayEvent::Traded {
+            mint: event.mint,
+            volume: event.proceeds.saturating_add(event.sell_tax),
+            sell_tax: event.sell_tax,
+            curve_supply: event.curve_supply,
+        });
+    }
+    if let Some(event) = decode::<SecondaryTokensBoughtEvent>(data) {
+        return Some(ReplayEvent::Traded {
+            mint: event.mint,
+            volume: event.primary_cost,
+            sell_tax: 0,
+            curve_supply: event.curve_supply,
+        });
+    }
+    if let Some(event) = decode::<SecondaryTokensSoldEvent>(data) {
+        return Some(ReplayEvent::SecondarySold {
+            mint: event.mint,
+            curve_supply: event.curve_supply,
+        });
+    }
+    if let Some(event) = decode::<RemoteBuyExecutedEvent>(data) {
+        return Some(ReplayEvent::Traded {
+            mint: event.mint,
+            volume: event.quote_amount,
+            sell_tax: 0,
+            curve_supply: event.curve_supply,
+        });
+    }
+    if let Some(event) = decode::<RemoteSellExecutedEvent>(data) {
+        return Some(ReplayEvent::Traded {
+            mint: event.mint,
+            volume: event.proceeds.saturating_add(event.sell_tax),
+            sell_tax: event.sell_tax,
+            curve_supply: event.curve_supply,
+        });
+    }
+    if let Some(event) = decode::<TokensBurnedEvent>(data) {
+        return Some(ReplayEvent::Burned {
+            mint: event.mint,
+            total_burned: event.total_burned,
+        });
+    }
+    if let Some(event) = decode::<RefundedEvent>(data) {
+        return Some(ReplayEvent::Refunded {
+            mint: event.mint,
+            amount: event.amount,
+        });
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::AnchorSerialize;
+
+    fn program_data<T: Discriminator + AnchorSerialize>(event: &T) -> String {
+        let mut data = T::DISCRIMINATOR.to_vec();
+        data.extend(event.try_to_vec().unwrap());
+        format!(
+            "{}{}",
+            PROGRAM_DATA_PREFIX,
+            base64::engine::general_purpose::STANDARD.encode(data)
+        )
+    }
+
+    fn bought(mint: Pubkey, curve_supply: u64) -> TokensBoughtEvent {
+        TokensBoughtEvent {
+            token_id: 0,
+            mint,
+            buyer: Pubkey::new_unique(),
+            amount: 5,
+            cost: 70,
+            curve_supply,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn only_events_written_by_the_program_itself_are_taken() {
+        let program_id = token_factory::ID;
+        let other = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let logs = vec![
+            format!("Program {} invoke [1]", program_id),
+            program_data(&bought(mint, 5)),
+            format!("Program {} invoke [2]", other),
+            program_data(&bought(mint, 99)),
+            format!("Program {} success", other),
+            "Program log: unrelated".to_string(),
+            program_data(&bought(mint, 10)),
+            format!("Program {} success", program_id),
+        ];
+
+        let parsed = parse_log(&program_id, &logs);
+        assert!(!parsed.truncated);
+        let supplies: Vec<u64> = parsed
+            .events
+            .iter()
+            .map(|event| match event {
+                ReplayEvent::Traded { curve_supply, .. } => *curve_supply,
+                event => panic!("unexpected event {:?}", event),
+            })
+            .collect();
+        assert_eq!(supplies, [5, 10]);
+    }
+
+    #[test]
+    fn truncated_logs_are_flagged() {
+        let program_id = token_factory::ID;
+        let logs = vec![
+            format!("Program {} invoke [1]", program_id),
+            program_data(&bought(Pubkey::new_unique(), 5)),
+            "Log truncated".to_string(),
+        ];
+        let parsed = parse_log(&program_id, &logs);
+        assert!(parsed.truncated);
+        assert_eq!(parsed.events.len(), 1);
+    }
+}
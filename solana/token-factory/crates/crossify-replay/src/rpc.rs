@@ -0,0 +1,120 @@
+// RPC module for crossify-replay
+// This file contains the JSON-RPC calls the replay makes against a Solana node
+
+use base64::Engine;
+use serde_json::{json, Value};
+
+use crate::Result;
+
+// getSignaturesForAddress returns at most this many signatures per page
+const SIGNATURE_PAGE_LIMIT: u64 = 1000;
+
+const COMMITMENT: &str = "finalized";
+
+/// A program transaction that succeeded, with its log
+pub struct LoggedTransaction {
+    pub signature: String,
+    pub slot: u64,
+    pub logs: Vec<String>,
+}
+
+pub struct RpcClient {
+    url: String,
+    agent: ureq::Agent,
+    next_id: u64,
+}
+
+impl RpcClient {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            agent: ureq::Agent::new(),
+            next_id: 1,
+        }
+    }
+
+    fn call(&mut self, method: &str, params: Value) -> Result<Value> {
+        let request = json!({ "jsonrpc": "2.0", "id": self.next_id, "method": method, "params": params });
+        self.next_id += 1;
+        let body = self
+            .agent
+            .post(&self.url)
+            .set("Content-Type", "application/json")
+            .send_string(&request.to_string())?;
+        let mut response: Value = serde_json::from_str(&body.into_string()?)?;
+        if let Some(error) = response.get("error") {
+            return Err(format!("{} failed: {}", method, error).into());
+        }
+        Ok(response["result"].take())
+    }
+
+    pub fn slot(&mut self) -> Result<u64> {
+        let slot = self.call("getSlot", json!([{ "commitment": COMMITMENT }]))?;
+        slot.as_u64().ok_or_else(|| "getSlot returned no slot".into())
+    }
+
+    // Signatures of successful transactions invoking `program_id` within [from_slot, to_slot],
+    // oldest first. The node pages newest first, so paging stops once a page reaches `from_slot`
+    pub fn program_signatures(&mut self, program_id: &str, from_slot: u64, to_slot: u64) -> Result<Vec<(String, u64)>> {
+        let mut signatures = Vec::new();
+        let mut before: Option<String> = None;
+        loop {
+            let mut config = json!({ "limit": SIGNATURE_PAGE_LIMIT, "commitment": COMMITMENT });
+            if let Some(before) = &before {
+                config["before"] = json!(before);
+            }
+            let page = self.call("getSignaturesForAddress", json!([program_id, config]))?;
+            let page = page.as_array().ok_or("getSignaturesForAddress returned no list")?;
+            let mut reached_start = page.len() < SIGNATURE_PAGE_LIMIT as usize;
+            for entry in page {
+                let signature = entry["signature"]
+                    .as_str()
+                    .ok_or("signature entry without a signature")?;
+                let slot = entry["slot"].as_u64().ok_or("signature entry without a slot")?;
+                before = Some(signature.to_string());
+                if slot < from_slot {
+                    reached_start = true;
+                    break;
+                }
+                if slot <= to_slot && entry["err"].is_null() {
+                    signatures.push((signature.to_string(), slot));
+                }
+            }
+            if reached_start {
+                break;
+            }
+        }
+        signatures.reverse();
+        Ok(signatures)
+    }
+
+    pub fn transaction(&mut self, signature: &str, slot: u64) -> Result<LoggedTransaction> {
+        let config = json!({ "encoding": "json", "commitment": COMMITMENT, "maxSupportedTransactionVersion": 0 });
+        let transaction = self.call("getTransaction", json!([signature, config]))?;
+        let logs = transaction["meta"]["logMessages"]
+            .as_array()
+            .ok_or_else(|| format!("transaction {} has no log messages", signature))?
+            .iter()
+            .filter_map(|line| line.as_str().map(str::to_string))
+            .collect();
+        Ok(LoggedTransaction {
+            signature: signature.to_string(),
+            slot,
+            logs,
+        })
+    }
+
+    // Account data at the latest finalized slot, or None if the account does not exist
+    pub fn account_data(&mut self, address: &str) -> Result<Option<Vec<u8>>> {
+        let account = self.call(
+            "getAccountInfo",
+            json!([address, { "encoding": "base64", "commitment": COMMITMENT }]),
+        )?;
+        let value = &account["value"];
+        if value.is_null() {
+            return Ok(None);
+        }
+        let data = value["data"][0].as_str().ok_or("account data is not base64")?;
+        Ok(Some(base64::engine::general_purpose::STANDARD.decode(data)?))
+    }
+}
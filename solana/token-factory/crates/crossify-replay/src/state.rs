@@ -0,0 +1,162 @@
+// State module for crossify-replay
+// This file contains the TokenStats the replayed events imply, and the diff against on-chain accounts
+
+use std::collections::BTreeMap;
+
+use anchor_lang::prelude::Pubkey;
+use token_factory::TokenStats;
+
+use crate::events::ReplayEvent;
+
+/// TokenStats fields the replay can vouch for. A field is None when the events in range do not
+/// determine it: cumulative counters need the token's creation in range, and curve_supply needs
+/// at least one trade
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExpectedStats {
+    pub created_in_range: bool,
+    pub curve_supply: Option<u64>,
+    pub total_volume: Option<u64>,
+    pub total_creator_fees: Option<u64>,
+    pub total_burned: Option<u64>,
+    // A secondary sell adds proceeds the event does not carry, so the volume is unknown from then on
+    pub volume_unknown: bool,
+}
+
+impl ExpectedStats {
+    pub fn apply(&mut self, event: &ReplayEvent) {
+        match *event {
+            ReplayEvent::Created { .. } => {
+                *self = ExpectedStats {
+                    created_in_range: true,
+                    curve_supply: Some(0),
+                    total_volume: Some(0),
+                    total_creator_fees: Some(0),
+                    total_burned: Some(0),
+                    volume_unknown: false,
+                };
+            }
+            ReplayEvent::Traded {
+                volume,
+                sell_tax,
+                curve_supply,
+                ..
+            } => {
+                self.curve_supply = Some(curve_supply);
+                self.total_volume = self.total_volume.map(|total| total.saturating_add(volume));
+                self.total_creator_fees = self.total_creator_fees.map(|total| total.saturating_add(sell_tax));
+            }
+            ReplayEvent::SecondarySold { curve_supply, .. } => {
+                self.curve_supply = Some(curve_supply);
+                self.total_volume = None;
+                self.volume_unknown = true;
+            }
+            ReplayEvent::Burned { total_burned, .. } => {
+                self.total_burned = Some(total_burned);
+            }
+            ReplayEvent::Refunded { amount, .. } => {
+                self.curve_supply = self.curve_supply.map(|supply| supply.saturating_sub(amount));
+            }
+        }
+    }
+}
+
+// Replays events in order into the expected stats of each mint they touch
+pub fn replay<'a>(events: impl IntoIterator<Item = &'a ReplayEvent>) -> BTreeMap<Pubkey, ExpectedStats> {
+    let mut expected: BTreeMap<Pubkey, ExpectedStats> = BTreeMap::new();
+    for event in events {
+        expected.entry(event.mint()).or_default().apply(event);
+    }
+    expected
+}
+
+/// A field whose on-chain value differs from the replayed one
+#[derive(Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    pub field: &'static str,
+    pub expected: u64,
+    pub on_chain: u64,
+}
+
+pub fn diff(expected: &ExpectedStats, on_chain: &TokenStats) -> Vec<Mismatch> {
+    [
+        ("curve_supply", expected.curve_supply, on_chain.curve_supply),
+        ("total_volume", expected.total_volume, on_chain.total_volume),
+        (
+            "total_creator_fees",
+            expected.total_creator_fees,
+            on_chain.total_creator_fees,
+        ),
+        ("total_burned", expected.total_burned, on_chain.total_burned),
+    ]
+    .into_iter()
+    .filter_map(|(field, expected, on_chain)| {
+        let expected = expected?;
+        (expected != on_chain).then_some(Mismatch {
+            field,
+            expected,
+            on_chain,
+        })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn traded(mint: Pubkey, volume: u64, sell_tax: u64, curve_supply: u64) -> ReplayEvent {
+        ReplayEvent::Traded {
+            mint,
+            volume,
+            sell_tax,
+            curve_supply,
+        }
+    }
+
+    #[test]
+    fn counters_are_only_vouched_for_from_creation() {
+        let created = Pubkey::new_unique();
+        let older = Pubkey::new_unique();
+        let events = [
+            ReplayEvent::Created { mint: created },
+            traded(created, 100, 0, 10),
+            traded(older, 50, 0, 7),
+            traded(created, 40, 4, 6),
+            ReplayEvent::Refunded {
+                mint: created,
+                amount: 1,
+            },
+            ReplayEvent::Burned {
+                mint: older,
+                total_burned: 3,
+            },
+        ];
+        let expected = replay(&events);
+
+        let created_stats = &expected[&created];
+        assert_eq!(created_stats.curve_supply, Some(5));
+        assert_eq!(created_stats.total_volume, Some(140));
+        assert_eq!(created_stats.total_creator_fees, Some(4));
+        assert_eq!(created_stats.total_burned, Some(0));
+
+        let older_stats = &expected[&older];
+        assert_eq!(older_stats.curve_supply, Some(7));
+        assert_eq!(older_stats.total_volume, None);
+        assert_eq!(older_stats.total_burned, Some(3));
+    }
+
+    #[test]
+    fn secondary_sells_leave_the_volume_unverified() {
+        let mint = Pubkey::new_unique();
+        let events = [
+            ReplayEvent::Created { mint },
+            traded(mint, 100, 0, 10),
+            ReplayEvent::SecondarySold { mint, curve_supply: 8 },
+            traded(mint, 20, 0, 9),
+        ];
+        let stats = &replay(&events)[&mint];
+        assert_eq!(stats.curve_supply, Some(9));
+        assert_eq!(stats.total_volume, None);
+        assert!(stats.volume_unknown);
+    }
+}
@@ -0,0 +1,124 @@
+// WebAssembly bindings for Crossify
+// This file exposes the crossify-common curve math and wire codec to JavaScript, so the frontend
+// quotes trades and frames messages exactly as the program does. Build with
+// `wasm-pack build crates/crossify-wasm`; payload bodies are Borsh, encoded with the IDL types
+
+use wasm_bindgen::prelude::*;
+
+use crossify_common::CurveParams;
+
+/// A bonding curve as configured on a token, priced at the token's decimals
+#[wasm_bindgen]
+pub struct Curve {
+    params: CurveParams,
+    decimals: u8,
+}
+
+#[wasm_bindgen]
+impl Curve {
+    #[wasm_bindgen(constructor)]
+    pub fn new(curve_type: u8, base_price: u64, slope: u64, reserve_ratio: u16, decimals: u8) -> Curve {
+        Curve {
+            params: CurveParams {
+                curve_type,
+                base_price,
+                slope,
+                reserve_ratio,
+            },
+            decimals,
+        }
+    }
+
+    /// Price of `amount` base units bought at `supply`, or returned by selling down from `supply + amount`
+    pub fn quote(&self, supply: u64, amount: u64) -> Result<u64, JsError> {
+        crossify_common::quote(&self.params, self.decimals, supply, amount).map_err(js_error)
+    }
+
+    /// Quote paid to take the curve from zero to `supply` base units
+    #[wasm_bindgen(js_name = curveArea)]
+    pub fn curve_area(&self, supply: u64) -> Result<u128, JsError> {
+        crossify_common::curve_area(&self.params, self.decimals, supply).map_err(js_error)
+    }
+
+    /// Largest buy, in base units, that `quote_amount` pays for at `supply`
+    #[wasm_bindgen(js_name = tokensForQuote)]
+    pub fn tokens_for_quote(&self, supply: u64, quote_amount: u64) -> Result<u64, JsError> {
+        crossify_common::tokens_for_quote(&self.params, self.decimals, supply, quote_amount).map_err(js_error)
+    }
+}
+
+/// Frames a Borsh-encoded payload body as a Wormhole message of `message_type`
+#[wasm_bindgen(js_name = frameMessage)]
+pub fn frame_message(message_type: u8, body: &[u8]) -> Vec<u8> {
+    crossify_common::frame_message(message_type, body)
+}
+
+/// A Wormhole message split into its type and Borsh body
+#[wasm_bindgen]
+pub struct DecodedMessage {
+    message_type: u8,
+    body: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl DecodedMessage {
+    #[wasm_bindgen(getter, js_name = messageType)]
+    pub fn message_type(&self) -> u8 {
+        self.message_type
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn body(&self) -> Vec<u8> {
+        self.body.clone()
+    }
+}
+
+/// Splits a Borsh or ABI-encoded Wormhole message; ABI bodies come back transcoded to Borsh
+#[wasm_bindgen(js_name = deserializeMessage)]
+pub fn deserialize_message(data: &[u8]) -> Result<DecodedMessage, JsError> {
+    let (message_type, body) = crossify_common::deserialize_message(data).map_err(js_error)?;
+    Ok(DecodedMessage { message_type, body })
+}
+
+/// Complete messages carried by one batch sync message
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct BatchSync {
+    messages: Vec<Vec<u8>>,
+}
+
+#[wasm_bindgen]
+impl BatchSync {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> BatchSync {
+        BatchSync::default()
+    }
+
+    /// Splits a batch sync body, as returned by `deserializeMessage`, into its messages
+    pub fn parse(body: &[u8]) -> Result<BatchSync, JsError> {
+        let messages = crossify_common::parse_batch_sync_message(body).map_err(js_error)?;
+        Ok(BatchSync { messages })
+    }
+
+    pub fn push(&mut self, message: &[u8]) {
+        self.messages.push(message.to_vec());
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn message(&self, index: usize) -> Option<Vec<u8>> {
+        self.messages.get(index).cloned()
+    }
+
+    /// The framed batch sync message
+    pub fn serialize(&self) -> Vec<u8> {
+        crossify_common::serialize_batch_sync_message(&self.messages)
+    }
+}
+
+fn js_error(err: impl std::fmt::Display) -> JsError {
+    JsError::new(&err.to_string())
+}
@@ -0,0 +1,106 @@
+// Random buy, remote buy and sell sequences against the curve pricing the program charges.
+// After every step the reserve collected so far must still pay out the whole curve supply.
+//
+//   cd solana/token-factory/programs/token-factory/fuzz
+//   cargo +nightly fuzz run curve_solvency
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use token_factory::{curve_area, quote_bonding_curve, tokens_for_quote, BondingCurve};
+
+#[derive(Arbitrary, Debug)]
+enum Step {
+    Buy { amount: u64 },
+    RemoteBuy { quote_amount: u64 },
+    Sell { amount: u64 },
+}
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    curve_type: u8,
+    base_price: u64,
+    slope: u64,
+    reserve_ratio: u16,
+    decimals: u8,
+    steps: Vec<Step>,
+}
+
+fuzz_target!(|input: Input| run(input));
+
+fn run(input: Input) {
+    let curve = BondingCurve {
+        configured: true,
+        enabled: true,
+        curve_type: input.curve_type % 3,
+        // configure_bonding_curve rejects a zero base price
+        base_price: input.base_price.max(1),
+        slope: input.slope,
+        reserve_ratio: input.reserve_ratio % 1001,
+        ..Default::default()
+    };
+    let decimals = input.decimals % 10;
+    let mut supply: u64 = 0;
+    let mut reserve: u128 = 0;
+    
+    for step in input.steps {
+        match step {
+            Step::Buy { amount } => {
+                // A quote that overflows is a rejected buy
+                let Ok(cost) = quote_bonding_curve(&curve, decimals, supply, amount) else {
+                    continue;
+                };
+                reserve += cost as u128;
+                supply += amount;
+            }
+            Step::RemoteBuy { quote_amount } => {
+                let Ok(amount) = tokens_for_quote(&curve, decimals, supply, quote_amount) else {
+                    continue;
+                };
+                let Ok(cost) = quote_bonding_curve(&curve, decimals, supply, amount) else {
+                    continue;
+                };
+                assert!(
+                    cost <= quote_amount,
+                    "remote buy of {} costs {} > {}",
+                    amount,
+                    cost,
+                    quote_amount
+                );
+                // The buy is the largest the quote pays for
+                let more = amount
+                    .checked_add(1)
+                    .and_then(|more| quote_bonding_curve(&curve, decimals, supply, more).ok());
+                if let Some(more) = more {
+                    assert!(more > quote_amount, "remote buy stopped short at {}", amount);
+                }
+                reserve += cost as u128;
+                supply += amount;
+            }
+            Step::Sell { amount } => {
+                let amount = (amount as u128 % (supply as u128 + 1)) as u64;
+                // Proceeds beyond u64 are a rejected sell; the holder splits it up
+                let Ok(proceeds) = quote_bonding_curve(&curve, decimals, supply - amount, amount) else {
+                    continue;
+                };
+                assert!(
+                    proceeds as u128 <= reserve,
+                    "sell pays {} from a reserve of {}",
+                    proceeds,
+                    reserve
+                );
+                reserve -= proceeds as u128;
+                supply -= amount;
+            }
+        }
+
+        let owed = curve_area(&curve, decimals, supply).expect("bought supply has a representable area");
+        assert!(
+            reserve >= owed,
+            "reserve {} short of {} owed at supply {}",
+            reserve,
+            owed,
+            supply
+        );
+    }
+}
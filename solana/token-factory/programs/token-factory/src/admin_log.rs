@@ -0,0 +1,179 @@
+// Admin log module for Crossify Token Factory
+// This file contains the on-chain ring buffer of recent privileged changes, kept alongside events
+// so the history can be read back without access to old transaction logs
+
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+use crate::{TokenFactory, TokenFactoryError, ACCOUNT_RESERVED_LEN, ACCOUNT_VERSION};
+
+// Actions a log keeps before the oldest is overwritten
+pub const ADMIN_LOG_LEN: usize = 32;
+
+// AdminLogEntry action codes
+#[constant]
+pub const ADMIN_ACTION_TREASURY: u16 = 1;
+#[constant]
+pub const ADMIN_ACTION_OTC_FEE: u16 = 2;
+#[constant]
+pub const ADMIN_ACTION_AUTHORITY: u16 = 3;
+#[constant]
+pub const ADMIN_ACTION_BRIDGE_FEE: u16 = 4;
+#[constant]
+pub const ADMIN_ACTION_VERIFIER: u16 = 5;
+#[constant]
+pub const ADMIN_ACTION_PRICE_ATTESTOR: u16 = 6;
+#[constant]
+pub const ADMIN_ACTION_SYMBOL_REGISTRY: u16 = 7;
+#[constant]
+pub const ADMIN_ACTION_CREATION_GATE: u16 = 8;
+#[constant]
+pub const ADMIN_ACTION_FEATURES: u16 = 9;
+#[constant]
+pub const ADMIN_ACTION_SYNC_SCHEDULE: u16 = 10;
+#[constant]
+pub const ADMIN_ACTION_CHAIN_FEES: u16 = 11;
+#[constant]
+pub const ADMIN_ACTION_CHAIN_FINALITY: u16 = 12;
+#[constant]
+pub const ADMIN_ACTION_EMITTER_POLICY: u16 = 13;
+#[constant]
+pub const ADMIN_ACTION_LAUNCH_CERTIFICATES: u16 = 14;
+#[constant]
+pub const ADMIN_ACTION_GOVERNANCE_CURVE: u16 = 15;
+#[constant]
+pub const ADMIN_ACTION_GOVERNANCE_TREASURY_SPEND: u16 = 16;
+#[constant]
+pub const ADMIN_ACTION_QUOTE_MINT: u16 = 17;
+#[constant]
+pub const ADMIN_ACTION_PRICE_GUARD: u16 = 18;
+#[constant]
+pub const ADMIN_ACTION_PRICE_RESET: u16 = 19;
+
+#[derive(Accounts)]
+pub struct OpenAdminLog<'info> {
+    #[account(seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + size_of::<AdminLog>(),
+        seeds = [b"admin_log", token_factory.key().as_ref()],
+        bump,
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> OpenAdminLog<'info> {
+    // Actions are recorded from the next one on; earlier changes are only in event logs
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        // Verify authority
+        require_with_context!(
+            self.token_factory.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=open_admin_log signer={} expected_authority={}",
+            self.authority.key(),
+            self.token_factory.authority
+        );
+        
+        let mut admin_log = self.admin_log.load_init()?;
+        admin_log.factory = self.token_factory.key();
+        admin_log.bump = bump;
+        admin_log.account_version = ACCOUNT_VERSION;
+        
+        emit!(AdminLogOpenedEvent {
+            admin_log: self.admin_log.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+/// Last ADMIN_LOG_LEN privileged changes to the factory and to governed tokens.
+/// Zero-copy so admin instructions can append without deserializing the whole ring.
+#[account(zero_copy)]
+pub struct AdminLog {
+    pub factory: Pubkey,
+    pub total_actions: u64, // Actions recorded since the log was opened
+    pub next_index: u32,    // Slot the next action is written to
+    pub len: u32,           // Filled slots, up to ADMIN_LOG_LEN
+    pub bump: u8,
+    pub account_version: u8,
+    pub _padding: [u8; 6],
+    pub entries: [AdminLogEntry; ADMIN_LOG_LEN],
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+impl AdminLog {
+    pub fn record(&mut self, entry: AdminLogEntry) {
+        self.entries[self.next_index as usize] = entry;
+        self.next_index = (self.next_index + 1) % ADMIN_LOG_LEN as u32;
+        self.len = (self.len + 1).min(ADMIN_LOG_LEN as u32);
+        self.total_actions = self.total_actions.saturating_add(1);
+    }
+}
+
+#[zero_copy]
+pub struct AdminLogEntry {
+    pub actor: Pubkey,        // Signer, or the executed proposal for governance actions
+    pub subject: Pubkey,      // Mint or emitter the action applies to, default for factory settings
+    pub old_value: [u8; 32],  // Encoded with admin_value or as raw key bytes
+    pub new_value: [u8; 32],
+    pub slot: u64,
+    pub timestamp: i64,
+    pub action: u16,          // ADMIN_ACTION_*
+    pub chain_id: u16,        // Wormhole chain of chain registry actions, 0 otherwise
+    pub _padding: [u8; 4],
+}
+
+// Packs up to four integers little-endian into an entry value, in the order the instruction takes them
+pub fn admin_value(words: &[u64]) -> [u8; 32] {
+    let mut value = [0u8; 32];
+    for (chunk, word) in value.chunks_mut(8).zip(words.iter()) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    value
+}
+
+// Appends an action to the factory's admin log. `admin_log` must already be checked against its
+// derived address; a log that was never opened is skipped
+pub fn record_admin_action(
+    admin_log: &AccountInfo,
+    actor: Pubkey,
+    action: u16,
+    chain_id: u16,
+    subject: Pubkey,
+    old_value: [u8; 32],
+    new_value: [u8; 32],
+) -> Result<()> {
+    if admin_log.data_is_empty() {
+        return Ok(());
+    }
+    let clock = Clock::get()?;
+    let loader: AccountLoader<AdminLog> = AccountLoader::try_from(admin_log)?;
+    loader.load_mut()?.record(AdminLogEntry {
+        actor,
+        subject,
+        old_value,
+        new_value,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+        action,
+        chain_id,
+        _padding: [0; 4],
+    });
+    Ok(())
+}
+
+#[event]
+pub struct AdminLogOpenedEvent {
+    pub admin_log: Pubkey,
+    pub timestamp: i64,
+}
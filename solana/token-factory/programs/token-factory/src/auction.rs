@@ -0,0 +1,644 @@
+// Auction module for Crossify Token Factory
+// This file contains the descending-price initial sale whose clearing price seeds the bonding curve
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use std::mem::size_of;
+
+use crate::{
+    decimals_unit, require_feature_enabled, BondingCurve, TokenData, TokenFactory, TokenFactoryError,
+    ACCOUNT_RESERVED_LEN, ACCOUNT_VERSION, FEATURE_PRESALES,
+};
+
+#[derive(Accounts)]
+pub struct StartDutchAuction<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + size_of::<DutchAuction>(),
+        seeds = [b"auction", mint.key().as_ref()],
+        bump,
+    )]
+    pub auction: Account<'info, DutchAuction>,
+    
+    #[account(address = token_data.bonding_curve.quote_mint @ TokenFactoryError::ReserveNotInitialized)]
+    pub quote_mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"auction_vault", auction.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = auction,
+    )]
+    pub auction_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    
+    // Read for the factory's feature switches
+    #[account(address = token_data.factory, seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+}
+
+impl<'info> StartDutchAuction<'info> {
+    pub fn process(
+        &mut self,
+        bump: u8,
+        tranche_amount: u64,
+        start_price: u64,
+        floor_price: u64,
+        start_ts: i64,
+        duration: i64,
+    ) -> Result<()> {
+        require_feature_enabled(&self.token_factory, FEATURE_PRESALES, "start_dutch_auction")?;
+        let token_data = &mut self.token_data;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=start_dutch_auction token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            self.authority.key(),
+            token_data.authority
+        );
+        require_with_context!(
+            token_data.bonding_curve.configured,
+            TokenFactoryError::CurveNotConfigured,
+            "instruction=start_dutch_auction token_id={}",
+            token_data.token_id
+        );
+        require_with_context!(
+            !token_data.bonding_curve.trading_started,
+            TokenFactoryError::TradingAlreadyStarted,
+            "instruction=start_dutch_auction token_id={}",
+            token_data.token_id
+        );
+        require_with_context!(
+            tranche_amount > 0 && floor_price > 0 && start_price > floor_price && duration > 0,
+            TokenFactoryError::InvalidAuction,
+            "instruction=start_dutch_auction token_id={} tranche_amount={} start_price={} floor_price={} duration={}",
+            token_data.token_id,
+            tranche_amount,
+            start_price,
+            floor_price,
+            duration
+        );
+        let end_ts = start_ts
+            .checked_add(duration)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        
+        // Curve trading stays closed until the auction settles
+        token_data.bonding_curve.auction_pending = true;
+        
+        let auction = &mut self.auction;
+        auction.mint = token_data.mint;
+        auction.token_id = token_data.token_id;
+        auction.decimals = token_data.decimals;
+        auction.auction_vault = self.auction_vault.key();
+        auction.tranche_amount = tranche_amount;
+        auction.start_price = start_price;
+        auction.floor_price = floor_price;
+        auction.start_ts = start_ts;
+        auction.end_ts = end_ts;
+        auction.sold = 0;
+        auction.clearing_price = 0;
+        auction.settled = false;
+        auction.bump = bump;
+        auction.account_version = ACCOUNT_VERSION;
+        
+        emit!(DutchAuctionStartedEvent {
+            token_id: auction.token_id,
+            mint: auction.mint,
+            tranche_amount,
+            start_price,
+            floor_price,
+            start_ts,
+            end_ts,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct PlaceAuctionBid<'info> {
+    #[account(
+        mut,
+        seeds = [b"auction", auction.mint.as_ref()],
+        bump = auction.bump,
+        has_one = auction_vault,
+    )]
+    pub auction: Account<'info, DutchAuction>,
+    
+    #[account(mut)]
+    pub auction_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        space = 8 + size_of::<AuctionBid>(),
+        seeds = [b"auction_bid", auction.key().as_ref(), bidder.key().as_ref()],
+        bump,
+    )]
+    pub bid: Account<'info, AuctionBid>,
+    
+    #[account(
+        mut,
+        token::mint = auction_vault.mint,
+        token::authority = bidder,
+    )]
+    pub bidder_quote_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    
+    // Read for the factory's feature switches
+    #[account(seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+}
+
+impl<'info> PlaceAuctionBid<'info> {
+    pub fn process(&mut self, amount: u64, max_price: u64) -> Result<()> {
+        require_feature_enabled(&self.token_factory, FEATURE_PRESALES, "place_auction_bid")?;
+        let now = Clock::get()?.unix_timestamp;
+        let auction = &self.auction;
+        
+        require_with_context!(
+            !auction.settled && now >= auction.start_ts && now < auction.end_ts,
+            TokenFactoryError::AuctionNotActive,
+            "instruction=place_auction_bid token_id={} now={} start_ts={} end_ts={} settled={}",
+            auction.token_id,
+            now,
+            auction.start_ts,
+            auction.end_ts,
+            auction.settled
+        );
+        let remaining = auction.tranche_amount - auction.sold;
+        require_with_context!(
+            amount > 0 && amount <= remaining,
+            TokenFactoryError::InvalidAmount,
+            "instruction=place_auction_bid token_id={} amount={} remaining={}",
+            auction.token_id,
+            amount,
+            remaining
+        );
+        
+        let price = auction_price(auction, now);
+        require_with_context!(
+            price <= max_price,
+            TokenFactoryError::SlippageExceeded,
+            "instruction=place_auction_bid token_id={} price={} max_price={}",
+            auction.token_id,
+            price,
+            max_price
+        );
+        
+        // Bidders pay the current price; any excess over the clearing price is refunded on claim
+        let cost = tranche_cost(price, amount, auction.decimals, true)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.bidder_quote_account.to_account_info(),
+                    to: self.auction_vault.to_account_info(),
+                    authority: self.bidder.to_account_info(),
+                },
+            ),
+            cost,
+        )?;
+        
+        let bid = &mut self.bid;
+        if bid.bidder == Pubkey::default() {
+            bid.auction = self.auction.key();
+            bid.bidder = self.bidder.key();
+            bid.account_version = ACCOUNT_VERSION;
+        }
+        bid.amount = bid
+            .amount
+            .checked_add(amount)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        bid.paid = bid
+            .paid
+            .checked_add(cost)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        
+        // The bid that sells out the tranche sets the clearing price
+        let auction = &mut self.auction;
+        auction.sold += amount;
+        if auction.sold == auction.tranche_amount {
+            auction.clearing_price = price;
+        }
+
+        emit!(AuctionBidPlacedEvent {
+            token_id: auction.token_id,
+            mint: auction.mint,
+            bidder: self.bidder.key(),
+            amount,
+            price,
+            sold: auction.sold,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SettleDutchAuction<'info> {
+    #[account(
+        mut,
+        constraint = token_data.mint == auction.mint @ TokenFactoryError::InvalidAuction,
+    )]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        mut,
+        seeds = [b"auction", token_data.mint.as_ref()],
+        bump = auction.bump,
+        has_one = auction_vault,
+    )]
+    pub auction: Account<'info, DutchAuction>,
+    
+    #[account(mut)]
+    pub auction_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"reserve", token_data.mint.as_ref()],
+        bump,
+    )]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> SettleDutchAuction<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let auction = &mut self.auction;
+        
+        require_with_context!(
+            !auction.settled,
+            TokenFactoryError::AuctionNotActive,
+            "instruction=settle_dutch_auction token_id={} already settled",
+            auction.token_id
+        );
+        require_with_context!(
+            now >= auction.end_ts || auction.sold == auction.tranche_amount,
+            TokenFactoryError::AuctionNotEnded,
+            "instruction=settle_dutch_auction token_id={} now={} end_ts={} sold={} tranche_amount={}",
+            auction.token_id,
+            now,
+            auction.end_ts,
+            auction.sold,
+            auction.tranche_amount
+        );
+        
+        let proceeds = auction
+            .settle(&mut self.token_data.bonding_curve)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        if proceeds > 0 {
+            let mint_key = auction.mint;
+            let bump = [auction.bump];
+            let signer_seeds: &[&[&[u8]]] = &[&[b"auction", mint_key.as_ref(), &bump]];
+            token::transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    token::Transfer {
+                        from: self.auction_vault.to_account_info(),
+                        to: self.reserve_vault.to_account_info(),
+                        authority: self.auction.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                proceeds,
+            )?;
+        }
+
+        let auction = &self.auction;
+        emit!(DutchAuctionSettledEvent {
+            token_id: auction.token_id,
+            mint: auction.mint,
+            sold: auction.sold,
+            clearing_price: auction.clearing_price,
+            proceeds,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ClaimAuctionTokens<'info> {
+    #[account(constraint = token_data.mint == auction.mint @ TokenFactoryError::InvalidAuction)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(mut, address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA holding the delegated mint authority; it holds no data
+    #[account(
+        seeds = [b"mint_authority", mint.key().as_ref()],
+        bump = token_data.mint_authority_bump,
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"auction", mint.key().as_ref()],
+        bump = auction.bump,
+        has_one = auction_vault,
+    )]
+    pub auction: Account<'info, DutchAuction>,
+    
+    #[account(mut)]
+    pub auction_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        has_one = auction,
+        has_one = bidder,
+        close = bidder,
+        seeds = [b"auction_bid", auction.key().as_ref(), bidder.key().as_ref()],
+        bump,
+    )]
+    pub bid: Account<'info, AuctionBid>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = bidder,
+    )]
+    pub bidder_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = auction_vault.mint,
+        token::authority = bidder,
+    )]
+    pub bidder_quote_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClaimAuctionTokens<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let auction = &self.auction;
+        let bid = &self.bid;
+        
+        require_with_context!(
+            auction.settled,
+            TokenFactoryError::AuctionNotEnded,
+            "instruction=claim_auction_tokens token_id={} bidder={}",
+            auction.token_id,
+            bid.bidder
+        );
+        
+        let owed = tranche_cost(auction.clearing_price, bid.amount, auction.decimals, true)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        let refund = bid.paid - owed;
+        
+        let mint_key = auction.mint;
+        let bump = [self.token_data.mint_authority_bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"mint_authority", mint_key.as_ref(), &bump]];
+        token::mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token::MintTo {
+                    mint: self.mint.to_account_info(),
+                    to: self.bidder_token_account.to_account_info(),
+                    authority: self.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            bid.amount,
+        )?;
+        
+        if refund > 0 {
+            let bump = [auction.bump];
+            let signer_seeds: &[&[&[u8]]] = &[&[b"auction", mint_key.as_ref(), &bump]];
+            token::transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    token::Transfer {
+                        from: self.auction_vault.to_account_info(),
+                        to: self.bidder_quote_account.to_account_info(),
+                        authority: self.auction.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                refund,
+            )?;
+        }
+
+        emit!(AuctionTokensClaimedEvent {
+            token_id: self.auction.token_id,
+            mint: mint_key,
+            bidder: self.bidder.key(),
+            amount: self.bid.amount,
+            refund,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+// Price falls linearly from `start_price` to `floor_price` over the auction window
+pub fn auction_price(auction: &DutchAuction, now: i64) -> u64 {
+    if now <= auction.start_ts {
+        return auction.start_price;
+    }
+    if now >= auction.end_ts {
+        return auction.floor_price;
+    }
+    let elapsed = (now - auction.start_ts) as u128;
+    let duration = (auction.end_ts - auction.start_ts) as u128;
+    let drop = (auction.start_price - auction.floor_price) as u128 * elapsed / duration;
+    auction.start_price - drop as u64
+}
+
+// Quote cost of `amount` base units at `price` per whole token. Bids and per-bidder settlement
+// round up while reserve proceeds round down, so the vault always covers every refund.
+fn tranche_cost(price: u64, amount: u64, decimals: u8, round_up: bool) -> Option<u64> {
+    let unit = decimals_unit(decimals);
+    let value = (price as u128).checked_mul(amount as u128)?;
+    let mut cost = value / unit;
+    if round_up && value % unit != 0 {
+        cost += 1;
+    }
+    u64::try_from(cost).ok()
+}
+
+#[account]
+pub struct DutchAuction {
+    pub mint: Pubkey,
+    pub token_id: u64,
+    pub decimals: u8,
+    pub auction_vault: Pubkey,
+    pub tranche_amount: u64,
+    pub start_price: u64, // Quote units per whole token
+    pub floor_price: u64,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub sold: u64,
+    pub clearing_price: u64, // Set when the tranche sells out, or to the floor at settlement
+    pub settled: bool,
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+impl DutchAuction {
+    // Closes the auction and seeds the curve at the clearing price, returning the proceeds that
+    // move to the reserve; the rest of the vault stays for bidder refunds. Auctioned tokens stay
+    // out of the curve supply, so they are never redeemable against the reserve: with a positive
+    // slope the curve prices that supply above the clearing price the reserve was paid
+    fn settle(&mut self, bonding_curve: &mut BondingCurve) -> Option<u64> {
+        // An undersubscribed auction clears at the floor
+        if self.sold < self.tranche_amount {
+            self.clearing_price = self.floor_price;
+        }
+        self.settled = true;
+        bonding_curve.base_price = self.clearing_price;
+        bonding_curve.auction_pending = false;
+        tranche_cost(self.clearing_price, self.sold, self.decimals, false)
+    }
+}
+
+#[account]
+pub struct AuctionBid {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub paid: u64,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+#[event]
+pub struct DutchAuctionStartedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub tranche_amount: u64,
+    pub start_price: u64,
+    pub floor_price: u64,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionBidPlacedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub price: u64,
+    pub sold: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DutchAuctionSettledEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub sold: u64,
+    pub clearing_price: u64,
+    pub proceeds: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionTokensClaimedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub refund: u64,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{quote_bonding_curve, required_reserve};
+    
+    const DECIMALS: u8 = 6;
+    const UNIT: u64 = 1_000_000;
+    
+    fn token_data(base_price: u64, slope: u64) -> TokenData {
+        let mut token_data = TokenData::deserialize(&mut &[0u8; 4096][..]).unwrap();
+        token_data.decimals = DECIMALS;
+        token_data.bonding_curve = BondingCurve {
+            configured: true,
+            enabled: true,
+            base_price,
+            slope,
+            auction_pending: true,
+            ..Default::default()
+        };
+        token_data
+    }
+
+    fn auction(sold: u64, clearing_price: u64) -> DutchAuction {
+        DutchAuction {
+            mint: Pubkey::new_unique(),
+            token_id: 1,
+            decimals: DECIMALS,
+            auction_vault: Pubkey::new_unique(),
+            tranche_amount: sold,
+            start_price: 2 * clearing_price,
+            floor_price: clearing_price / 2,
+            start_ts: 0,
+            end_ts: 100,
+            sold,
+            clearing_price,
+            settled: false,
+            bump: 0,
+            account_version: ACCOUNT_VERSION,
+            _reserved: [0; ACCOUNT_RESERVED_LEN],
+        }
+    }
+
+    #[test]
+    fn settlement_with_a_sloped_curve_leaves_the_reserve_covered() {
+        let mut token_data = token_data(1_000, 50);
+        let mut auction = auction(500_000 * UNIT, 2_000);
+        let mut curve_supply = 0;
+        let mut reserve = auction.settle(&mut token_data.bonding_curve).unwrap();
+        assert_eq!(token_data.bonding_curve.base_price, 2_000);
+        assert!(required_reserve(&token_data, curve_supply, 0).unwrap() <= reserve);
+        
+        // Curve buyers after the auction, then auction holders selling everything the curve will take
+        let bought = 200_000 * UNIT;
+        reserve += quote_bonding_curve(&token_data.bonding_curve, DECIMALS, curve_supply, bought).unwrap();
+        curve_supply += bought;
+        assert!(required_reserve(&token_data, curve_supply, 0).unwrap() <= reserve);
+        reserve -= quote_bonding_curve(&token_data.bonding_curve, DECIMALS, 0, curve_supply).unwrap();
+        curve_supply = 0;
+        assert!(required_reserve(&token_data, curve_supply, 0).unwrap() <= reserve);
+        assert_eq!(reserve, tranche_cost(2_000, 500_000 * UNIT, DECIMALS, false).unwrap());
+    }
+}
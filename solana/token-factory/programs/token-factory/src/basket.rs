@@ -0,0 +1,381 @@
+// Basket module for Crossify Token Factory
+// This file contains index tokens backed by fixed amounts of several factory tokens held in vaults
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::{decimals_unit, TokenData, TokenFactory, TokenFactoryError, ACCOUNT_RESERVED_LEN, ACCOUNT_VERSION};
+
+// Factory tokens a single basket can hold
+pub const MAX_BASKET_COMPONENTS: usize = 8;
+
+// Remaining accounts per component: token_data, vault when creating; source/destination and vault otherwise
+const BASKET_ACCOUNTS_PER_COMPONENT: usize = 2;
+
+#[derive(Accounts)]
+#[instruction(decimals: u8)]
+pub struct CreateBasket<'info> {
+    #[account(seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Basket::LEN,
+        seeds = [b"basket", basket_mint.key().as_ref()],
+        bump,
+    )]
+    pub basket: Account<'info, Basket>,
+    
+    // Only the basket PDA can mint, and only against deposited components
+    #[account(
+        init,
+        payer = creator,
+        mint::decimals = decimals,
+        mint::authority = basket,
+    )]
+    pub basket_mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> CreateBasket<'info> {
+    // Remaining accounts: per component its TokenData and a token account of its mint owned by
+    // the basket PDA, which becomes the component's vault
+    pub fn process(
+        &mut self,
+        bump: u8,
+        decimals: u8,
+        units: Vec<u64>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let basket_key = self.basket.key();
+        require_with_context!(
+            !units.is_empty()
+                && units.len() <= MAX_BASKET_COMPONENTS
+                && units.iter().all(|u| *u > 0)
+                && remaining_accounts.len() == units.len() * BASKET_ACCOUNTS_PER_COMPONENT,
+            TokenFactoryError::InvalidBasket,
+            "instruction=create_basket basket={} components={} accounts={} max_components={}",
+            basket_key,
+            units.len(),
+            remaining_accounts.len(),
+            MAX_BASKET_COMPONENTS
+        );
+        
+        let mut components: Vec<BasketComponent> = Vec::with_capacity(units.len());
+        for (accounts, units) in remaining_accounts
+            .chunks(BASKET_ACCOUNTS_PER_COMPONENT)
+            .zip(units)
+        {
+            let token_data: Account<TokenData> = Account::try_from(&accounts[0])?;
+            let vault: Account<TokenAccount> = Account::try_from(&accounts[1])?;
+            
+            // Only the basket PDA can move vault funds, and nobody can close the vault under it
+            require_with_context!(
+                token_data.factory == self.token_factory.key()
+                    && vault.mint == token_data.mint
+                    && vault.owner == basket_key
+                    && vault.delegate.is_none()
+                    && vault.close_authority.is_none()
+                    && !components.iter().any(|c| c.mint == token_data.mint),
+                TokenFactoryError::InvalidBasket,
+                "instruction=create_basket basket={} token_id={} vault={}",
+                basket_key,
+                token_data.token_id,
+                vault.key()
+            );
+            components.push(BasketComponent {
+                mint: token_data.mint,
+                vault: vault.key(),
+                units,
+            });
+        }
+
+        let basket = &mut self.basket;
+        basket.factory = self.token_factory.key();
+        basket.mint = self.basket_mint.key();
+        basket.creator = self.creator.key();
+        basket.decimals = decimals;
+        basket.components = components;
+        basket.created_at = Clock::get()?.unix_timestamp;
+        basket.bump = bump;
+        basket.account_version = ACCOUNT_VERSION;
+        
+        emit!(BasketCreatedEvent {
+            basket: basket_key,
+            basket_mint: basket.mint,
+            creator: basket.creator,
+            component_mints: basket.components.iter().map(|c| c.mint).collect(),
+            units: basket.components.iter().map(|c| c.units).collect(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct MintBasket<'info> {
+    #[account(
+        seeds = [b"basket", basket.mint.as_ref()],
+        bump = basket.bump,
+    )]
+    pub basket: Account<'info, Basket>,
+    
+    #[account(mut, address = basket.mint)]
+    pub basket_mint: Account<'info, Mint>,
+    
+    #[account(mut, token::mint = basket_mint)]
+    pub destination: Account<'info, TokenAccount>,
+    
+    pub depositor: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> MintBasket<'info> {
+    // Remaining accounts: per component, in basket order, the depositor's token account and the vault
+    pub fn process(&mut self, amount: u64, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let basket = &self.basket;
+        require_with_context!(
+            amount > 0,
+            TokenFactoryError::InvalidAmount,
+            "instruction=mint_basket basket={} amount={}",
+            basket.key(),
+            amount
+        );
+        basket.check_component_accounts(remaining_accounts, 1, "mint_basket")?;
+        
+        // Deposits round up, so every basket token stays fully backed
+        for (accounts, component) in remaining_accounts
+            .chunks(BASKET_ACCOUNTS_PER_COMPONENT)
+            .zip(basket.components.iter())
+        {
+            let deposit = basket.component_amount(component, amount, true)?;
+            token::transfer(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    token::Transfer {
+                        from: accounts[0].clone(),
+                        to: accounts[1].clone(),
+                        authority: self.depositor.to_account_info(),
+                    },
+                ),
+                deposit,
+            )?;
+        }
+
+        let bump = [basket.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"basket", basket.mint.as_ref(), &bump]];
+        token::mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token::MintTo {
+                    mint: self.basket_mint.to_account_info(),
+                    to: self.destination.to_account_info(),
+                    authority: basket.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+        
+        emit!(BasketMintedEvent {
+            basket: basket.key(),
+            owner: self.depositor.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct RedeemBasket<'info> {
+    #[account(
+        seeds = [b"basket", basket.mint.as_ref()],
+        bump = basket.bump,
+    )]
+    pub basket: Account<'info, Basket>,
+    
+    #[account(mut, address = basket.mint)]
+    pub basket_mint: Account<'info, Mint>,
+    
+    #[account(mut, token::mint = basket_mint, token::authority = owner)]
+    pub source: Account<'info, TokenAccount>,
+    
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> RedeemBasket<'info> {
+    // Remaining accounts: per component, in basket order, the vault and the owner's destination account
+    pub fn process(&mut self, amount: u64, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let basket = &self.basket;
+        require_with_context!(
+            amount > 0,
+            TokenFactoryError::InvalidAmount,
+            "instruction=redeem_basket basket={} amount={}",
+            basket.key(),
+            amount
+        );
+        basket.check_component_accounts(remaining_accounts, 0, "redeem_basket")?;
+        
+        token::burn(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Burn {
+                    mint: self.basket_mint.to_account_info(),
+                    from: self.source.to_account_info(),
+                    authority: self.owner.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        // Payouts round down, leaving any dust with the remaining holders
+        let bump = [basket.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"basket", basket.mint.as_ref(), &bump]];
+        for (accounts, component) in remaining_accounts
+            .chunks(BASKET_ACCOUNTS_PER_COMPONENT)
+            .zip(basket.components.iter())
+        {
+            let payout = basket.component_amount(component, amount, false)?;
+            if payout == 0 {
+                continue;
+            }
+            token::transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    token::Transfer {
+                        from: accounts[0].clone(),
+                        to: accounts[1].clone(),
+                        authority: basket.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                payout,
+            )?;
+        }
+
+        emit!(BasketRedeemedEvent {
+            basket: basket.key(),
+            owner: self.owner.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+/// Index token minted against fixed amounts of factory tokens held in per-component vaults
+#[account]
+pub struct Basket {
+    pub factory: Pubkey,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub decimals: u8,
+    pub components: Vec<BasketComponent>,
+    pub created_at: i64,
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+impl Basket {
+    pub const LEN: usize = 32 + 32 + 32 + 1 + 4 + BasketComponent::LEN * MAX_BASKET_COMPONENTS + 8 + 1 + 1 + ACCOUNT_RESERVED_LEN;
+    
+    // Base units of `component` backing `amount` base units of the basket
+    pub fn component_amount(&self, component: &BasketComponent, amount: u64, round_up: bool) -> Result<u64> {
+        let unit = decimals_unit(self.decimals);
+        let scaled = (component.units as u128)
+            .checked_mul(amount as u128)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        let mut component_amount = scaled / unit;
+        if round_up && scaled % unit != 0 {
+            component_amount += 1;
+        }
+        u64::try_from(component_amount).map_err(|_| TokenFactoryError::MathOverflow.into())
+    }
+
+    // Checks the remaining accounts pair up with the components and that the one at
+    // `vault_index` of each pair is that component's vault
+    fn check_component_accounts(
+        &self,
+        remaining_accounts: &[AccountInfo],
+        vault_index: usize,
+        instruction: &str,
+    ) -> Result<()> {
+        require_with_context!(
+            remaining_accounts.len() == self.components.len() * BASKET_ACCOUNTS_PER_COMPONENT,
+            TokenFactoryError::InvalidBasket,
+            "instruction={} basket={} accounts={} components={}",
+            instruction,
+            self.mint,
+            remaining_accounts.len(),
+            self.components.len()
+        );
+        for (accounts, component) in remaining_accounts
+            .chunks(BASKET_ACCOUNTS_PER_COMPONENT)
+            .zip(self.components.iter())
+        {
+            require_with_context!(
+                accounts[vault_index].key() == component.vault,
+                TokenFactoryError::InvalidBasket,
+                "instruction={} basket={} component_mint={} vault={} expected_vault={}",
+                instruction,
+                self.mint,
+                component.mint,
+                accounts[vault_index].key(),
+                component.vault
+            );
+        }
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BasketComponent {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub units: u64, // Base units of `mint` per whole basket token
+}
+
+impl BasketComponent {
+    pub const LEN: usize = 32 + 32 + 8;
+}
+
+#[event]
+pub struct BasketCreatedEvent {
+    pub basket: Pubkey,
+    pub basket_mint: Pubkey,
+    pub creator: Pubkey,
+    pub component_mints: Vec<Pubkey>,
+    pub units: Vec<u64>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BasketMintedEvent {
+    pub basket: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BasketRedeemedEvent {
+    pub basket: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,299 @@
+// Bootstrap module for Crossify Token Factory
+// This file contains initialize_v2, which sets up the factory, its roles and fees, and the chain
+// registry in one transaction
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use std::mem::size_of;
+
+use crate::{
+    wormhole_core_bridge, ChainConfig, ChainConfiguredEvent, ChainFinality, ChainFinalityConfiguredEvent,
+    DeliveryFeeParams, TokenFactory, TokenFactoryError, ACCOUNT_RESERVED_LEN, ACCOUNT_VERSION, ALL_FEATURES,
+    MAX_BRIDGE_FEE_LAMPORTS, MAX_CHAIN_ID, MAX_KEEPER_REWARD_LAMPORTS, MAX_OTC_FEE_BPS, TOKEN_FACTORY_VERSION,
+};
+
+// Chains one initialize_v2 call can register; more can be added later with configure_chain
+pub const MAX_BOOTSTRAP_CHAINS: usize = 8;
+
+// Remaining accounts per chain: chain_config, chain_finality (both writable, not yet created)
+const BOOTSTRAP_ACCOUNTS_PER_CHAIN: usize = 2;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ChainBootstrapParams {
+    pub chain_id: u16,
+    pub fee_params: DeliveryFeeParams,
+    pub min_source_confirmation: u32,
+    pub max_staleness: u32,
+}
+
+/// Everything the separate configure_* calls would otherwise set after `initialize`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct FactoryBootstrapParams {
+    pub authority: Option<Pubkey>, // Defaults to the signer, e.g. to hand off to a multisig at once
+    pub treasury: Option<Pubkey>,  // Defaults to the authority
+    pub otc_fee_bps: u16,
+    pub bridge_fee_lamports: u64,
+    pub sync_interval: i64,
+    pub keeper_reward_lamports: u64,
+    pub verifier: Pubkey,
+    pub price_attestor: Pubkey,
+    pub required_collection: Pubkey,
+    pub require_unique_symbols: bool,
+    pub symbol_claim_fee_lamports: u64,
+    pub disabled_features: u64,
+    pub chains: Vec<ChainBootstrapParams>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeV2<'info> {
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + size_of::<TokenFactory>(),
+        seeds = [b"token_factory"],
+        bump,
+    )]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    // Only the upgrade authority may create the singleton, so nobody can front-run the deployer
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::TokenFactory>,
+    
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(payer.key())
+            @ TokenFactoryError::InvalidAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    /// CHECK: Core Bridge this build targets; must be deployed on the cluster being initialized
+    #[account(
+        address = wormhole_core_bridge::ID,
+        constraint = core_bridge_program.executable @ TokenFactoryError::BridgeNotDeployed
+    )]
+    pub core_bridge_program: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeV2<'info> {
+    pub fn process(&mut self, params: FactoryBootstrapParams, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let factory_key = self.token_factory.key();
+        
+        // One factory per deployment, so token ids share a single namespace
+        require_with_context!(
+            self.token_factory.version == 0,
+            TokenFactoryError::FactoryAlreadyInitialized,
+            "instruction=initialize_v2 token_factory={} authority={}",
+            factory_key,
+            self.token_factory.authority
+        );
+        
+        // Same bounds the individual configure_* instructions enforce
+        require_with_context!(
+            params.otc_fee_bps <= MAX_OTC_FEE_BPS,
+            TokenFactoryError::FeeTooHigh,
+            "instruction=initialize_v2 otc_fee_bps={} max={}",
+            params.otc_fee_bps,
+            MAX_OTC_FEE_BPS
+        );
+        require_with_context!(
+            params.bridge_fee_lamports <= MAX_BRIDGE_FEE_LAMPORTS,
+            TokenFactoryError::FeeTooHigh,
+            "instruction=initialize_v2 bridge_fee_lamports={} max={}",
+            params.bridge_fee_lamports,
+            MAX_BRIDGE_FEE_LAMPORTS
+        );
+        require_with_context!(
+            params.sync_interval >= 0 && params.keeper_reward_lamports <= MAX_KEEPER_REWARD_LAMPORTS,
+            TokenFactoryError::FeeTooHigh,
+            "instruction=initialize_v2 sync_interval={} keeper_reward_lamports={} max={}",
+            params.sync_interval,
+            params.keeper_reward_lamports,
+            MAX_KEEPER_REWARD_LAMPORTS
+        );
+        require_with_context!(
+            params.disabled_features & !ALL_FEATURES == 0,
+            TokenFactoryError::InvalidFeatures,
+            "instruction=initialize_v2 disabled_features={:#x} known_features={:#x}",
+            params.disabled_features,
+            ALL_FEATURES
+        );
+        require_with_context!(
+            params.chains.len() <= MAX_BOOTSTRAP_CHAINS
+                && remaining_accounts.len() == params.chains.len() * BOOTSTRAP_ACCOUNTS_PER_CHAIN,
+            TokenFactoryError::InvalidBootstrapAccounts,
+            "instruction=initialize_v2 chains={} accounts={} max_chains={}",
+            params.chains.len(),
+            remaining_accounts.len(),
+            MAX_BOOTSTRAP_CHAINS
+        );
+        
+        let authority = params.authority.unwrap_or(self.payer.key());
+        let treasury = params.treasury.unwrap_or(authority);
+        let token_factory = &mut self.token_factory;
+        token_factory.authority = authority;
+        token_factory.token_count = 0;
+        token_factory.treasury = treasury;
+        token_factory.otc_fee_bps = params.otc_fee_bps;
+        token_factory.bridge_fee_lamports = params.bridge_fee_lamports;
+        token_factory.sync_interval = params.sync_interval;
+        token_factory.keeper_reward_lamports = params.keeper_reward_lamports;
+        token_factory.verifier = params.verifier;
+        token_factory.require_unique_symbols = params.require_unique_symbols;
+        token_factory.symbol_claim_fee_lamports = params.symbol_claim_fee_lamports;
+        token_factory.version = TOKEN_FACTORY_VERSION;
+        token_factory.lookup_table = Pubkey::default();
+        token_factory.price_attestor = params.price_attestor;
+        token_factory.required_collection = params.required_collection;
+        token_factory.disabled_features = params.disabled_features;
+        
+        let now = Clock::get()?.unix_timestamp;
+        let mut chain_ids = Vec::with_capacity(params.chains.len());
+        for (accounts, chain) in remaining_accounts
+            .chunks(BOOTSTRAP_ACCOUNTS_PER_CHAIN)
+            .zip(params.chains.iter())
+        {
+            self.register_chain(&factory_key, accounts, chain, now)?;
+            chain_ids.push(chain.chain_id);
+        }
+
+        emit!(FactoryBootstrappedEvent {
+            token_factory: factory_key,
+            authority,
+            treasury,
+            verifier: params.verifier,
+            price_attestor: params.price_attestor,
+            disabled_features: params.disabled_features,
+            chain_ids,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    // Creates and fills the chain_config and chain_finality PDAs for one chain
+    fn register_chain(
+        &self,
+        factory_key: &Pubkey,
+        accounts: &[AccountInfo<'info>],
+        chain: &ChainBootstrapParams,
+        now: i64,
+    ) -> Result<()> {
+        let chain_id = chain.chain_id;
+        require_with_context!(
+            chain_id > 0 && chain_id <= MAX_CHAIN_ID,
+            TokenFactoryError::UnsupportedChain,
+            "instruction=initialize_v2 chain_id={} max_chain_id={}",
+            chain_id,
+            MAX_CHAIN_ID
+        );
+        require_with_context!(
+            chain.max_staleness == 0 || chain.max_staleness > chain.min_source_confirmation,
+            TokenFactoryError::InvalidFinalityBounds,
+            "instruction=initialize_v2 chain_id={} min_source_confirmation={} max_staleness={}",
+            chain_id,
+            chain.min_source_confirmation,
+            chain.max_staleness
+        );
+        
+        // A repeated chain id fails here too, since its PDAs were already created
+        let chain_id_bytes = chain_id.to_le_bytes();
+        let config_bump = self.create_pda(
+            &accounts[0],
+            &[b"chain_config", factory_key.as_ref(), &chain_id_bytes],
+            8 + size_of::<ChainConfig>(),
+            chain_id,
+        )?;
+        let finality_bump = self.create_pda(
+            &accounts[1],
+            &[b"chain_finality", factory_key.as_ref(), &chain_id_bytes],
+            8 + size_of::<ChainFinality>(),
+            chain_id,
+        )?;
+        
+        let chain_config = ChainConfig {
+            factory: *factory_key,
+            chain_id,
+            fee_params: chain.fee_params,
+            updated_at: now,
+            bump: config_bump,
+            account_version: ACCOUNT_VERSION,
+            _reserved: [0; ACCOUNT_RESERVED_LEN],
+        };
+        chain_config.try_serialize(&mut &mut accounts[0].try_borrow_mut_data()?[..])?;
+        let chain_finality = ChainFinality {
+            factory: *factory_key,
+            chain_id,
+            min_source_confirmation: chain.min_source_confirmation,
+            max_staleness: chain.max_staleness,
+            bump: finality_bump,
+            account_version: ACCOUNT_VERSION,
+            _reserved: [0; ACCOUNT_RESERVED_LEN],
+        };
+        chain_finality.try_serialize(&mut &mut accounts[1].try_borrow_mut_data()?[..])?;
+        
+        emit!(ChainConfiguredEvent {
+            chain_id,
+            base_fee_lamports: chain.fee_params.base_fee_lamports,
+            gas_limit: chain.fee_params.gas_limit,
+            lamports_per_gas: chain.fee_params.lamports_per_gas,
+            lamports_per_byte: chain.fee_params.lamports_per_byte,
+            timestamp: now,
+        });
+        emit!(ChainFinalityConfiguredEvent {
+            chain_id,
+            min_source_confirmation: chain.min_source_confirmation,
+            max_staleness: chain.max_staleness,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    // Allocates a program-owned PDA paid for by the payer, returning its bump
+    fn create_pda(&self, account: &AccountInfo<'info>, seeds: &[&[u8]], space: usize, chain_id: u16) -> Result<u8> {
+        let (expected, bump) = Pubkey::find_program_address(seeds, &crate::ID);
+        require_with_context!(
+            account.key() == expected && account.data_is_empty() && account.lamports() == 0,
+            TokenFactoryError::InvalidBootstrapAccounts,
+            "instruction=initialize_v2 chain_id={} account={} expected={}",
+            chain_id,
+            account.key(),
+            expected
+        );
+        
+        let bump_seed = [bump];
+        let mut signer_seeds = seeds.to_vec();
+        signer_seeds.push(&bump_seed);
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: self.payer.to_account_info(),
+                    to: account.clone(),
+                },
+                &[&signer_seeds[..]],
+            ),
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            &crate::ID,
+        )?;
+        
+        Ok(bump)
+    }
+}
+
+#[event]
+pub struct FactoryBootstrappedEvent {
+    pub token_factory: Pubkey,
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub verifier: Pubkey,
+    pub price_attestor: Pubkey,
+    pub disabled_features: u64,
+    pub chain_ids: Vec<u16>,
+    pub timestamp: i64,
+}
@@ -0,0 +1,830 @@
+// Bridge module for Crossify Token Factory
+// This file contains outbound token transfers: burn here, publish a transfer message for the target chain
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as ix_sysvar;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use std::mem::size_of;
+
+use crate::wormhole::{
+    deserialize_wormhole_message, parse_posted_vaa, parse_token_transfer_message,
+    serialize_token_transfer_message, wormhole, TokenTransferPayload, UniversalAddress,
+};
+use crate::wormhole_core_bridge;
+use crate::{
+    emit_digest, require_feature_enabled, require_message_type_allowed, require_no_trade_bridge_mix, ChainSupply,
+    CrossChainMessageSentEvent, TokenData, TokenFactory, TokenFactoryError, TokenStats, ACCOUNT_RESERVED_LEN,
+    ACCOUNT_VERSION, DIGEST_ACTION_BRIDGED_IN, DIGEST_ACTION_BRIDGED_OUT, FEATURE_BRIDGING,
+};
+
+// Period over which outbound volume fades from the per-token daily cap
+pub const BRIDGE_WINDOW_SECONDS: i64 = 86_400;
+
+// Raising a daily bridge cap only takes effect after this delay; lowering it is immediate
+pub const BRIDGE_CAP_INCREASE_DELAY: i64 = 86_400;
+
+// How outbound transfers leave Solana, fixed when cross-chain is first enabled.
+// Tokens enabled before modes existed store 0 and bridge like BRIDGE_MODE_BURN.
+#[constant]
+pub const BRIDGE_MODE_BURN: u8 = 1; // Canonical multichain supply: burn here, mint there
+#[constant]
+pub const BRIDGE_MODE_LOCK: u8 = 2; // Wrapped model: lock in custody here, release on return
+
+#[derive(Accounts)]
+#[instruction(amount: u64, target_chain: u16)]
+pub struct BridgeOut<'info> {
+    #[account(address = token_data.factory, seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_stats", token_data.mint.as_ref()],
+        bump = token_stats.bump,
+    )]
+    pub token_stats: Account<'info, TokenStats>,
+    
+    #[account(mut, address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = sender,
+    )]
+    pub sender_token_account: Account<'info, TokenAccount>,
+    
+    // Required for lock-mode tokens
+    #[account(
+        mut,
+        seeds = [b"custody", mint.key().as_ref()],
+        bump,
+    )]
+    pub custody_vault: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = 8 + size_of::<ChainSupply>(),
+        seeds = [b"chain_supply", mint.key().as_ref(), &target_chain.to_le_bytes()],
+        bump,
+    )]
+    pub chain_supply: Account<'info, ChainSupply>,
+    
+    /// CHECK: Receives the protocol fee in lamports; checked against the factory treasury
+    #[account(mut, address = token_factory.treasury @ TokenFactoryError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub sender: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar, used to reject bridging bundled with a trade of this mint
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BridgeOut<'info> {
+    pub fn process(
+        &mut self,
+        amount: u64,
+        target_chain: u16,
+        recipient: UniversalAddress,
+        chain_supply_bump: u8,
+    ) -> Result<()> {
+        require_feature_enabled(&self.token_factory, FEATURE_BRIDGING, "bridge_out")?;
+        let token_data = &self.token_data;
+        let now = Clock::get()?.unix_timestamp;
+        
+        require_with_context!(
+            amount > 0,
+            TokenFactoryError::InvalidAmount,
+            "instruction=bridge_out token_id={} amount={}",
+            token_data.token_id,
+            amount
+        );
+        require_with_context!(
+            token_data.cross_chain_enabled,
+            TokenFactoryError::CrossChainNotEnabled,
+            "instruction=bridge_out token_id={} target_chain={}",
+            token_data.token_id,
+            target_chain
+        );
+        require_with_context!(
+            token_data.cross_chain_info.supported_chains.contains(target_chain),
+            TokenFactoryError::UnsupportedChain,
+            "instruction=bridge_out token_id={} target_chain={} supported_chains={:?}",
+            token_data.token_id,
+            target_chain,
+            token_data.cross_chain_info.supported_chains.chain_ids()
+        );
+        require_no_trade_bridge_mix(&self.instructions, &token_data.mint, true, "bridge_out")?;
+        require_with_context!(
+            recipient.is_valid_for_chain(target_chain),
+            TokenFactoryError::InvalidUniversalAddress,
+            "instruction=bridge_out token_id={} target_chain={} recipient={:?}",
+            token_data.token_id,
+            target_chain,
+            recipient.bytes
+        );
+        
+        let token_stats = &mut self.token_stats;
+        let bridged_in_window = bridged_volume_within_cap(token_data, token_stats, amount, now, "bridge_out")?;
+        
+        collect_bridge_fee(
+            &self.token_factory,
+            &self.sender,
+            &self.treasury.to_account_info(),
+            &self.system_program,
+        )?;
+        
+        if token_data.bridge_mode == BRIDGE_MODE_LOCK {
+            let custody_vault = self
+                .custody_vault
+                .as_ref()
+                .ok_or(TokenFactoryError::CustodyNotInitialized)?;
+            token::transfer(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    token::Transfer {
+                        from: self.sender_token_account.to_account_info(),
+                        to: custody_vault.to_account_info(),
+                        authority: self.sender.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+        } else {
+            token::burn(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    token::Burn {
+                        mint: self.mint.to_account_info(),
+                        from: self.sender_token_account.to_account_info(),
+                        authority: self.sender.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+        }
+        
+        token_stats.bridged_in_window = bridged_in_window;
+        token_stats.bridge_window_start_ts = now;
+        token_stats.total_bridged_out = token_stats
+            .total_bridged_out
+            .checked_add(amount)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        
+        let chain_supply = &mut self.chain_supply;
+        if chain_supply.mint == Pubkey::default() {
+            chain_supply.mint = token_data.mint;
+            chain_supply.chain_id = target_chain;
+            chain_supply.bump = chain_supply_bump;
+            chain_supply.account_version = ACCOUNT_VERSION;
+        }
+        chain_supply.bridged_out = chain_supply
+            .bridged_out
+            .checked_add(amount)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        
+        let payload = serialize_token_transfer_message(&TokenTransferPayload {
+            factory: token_data.factory,
+            token_id: token_data.token_id,
+            amount,
+            sender: self.sender.key(),
+            recipient_chain: target_chain,
+            recipient,
+            timestamp: now,
+        });
+        
+        // In a real implementation, this would call the Wormhole bridge to send the message
+        // For now, we just emit an event
+        emit!(CrossChainMessageSentEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            target_chain,
+            payload,
+            timestamp: now,
+        });
+        emit!(TokensBridgedOutEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            sender: self.sender.key(),
+            target_chain,
+            recipient,
+            amount,
+            bridged_in_window,
+            timestamp: now,
+        });
+        emit_digest(DIGEST_ACTION_BRIDGED_OUT, token_data.mint, self.sender.key(), amount, now);
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct RedeemTransfer<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_stats", token_data.mint.as_ref()],
+        bump = token_stats.bump,
+    )]
+    pub token_stats: Account<'info, TokenStats>,
+    
+    #[account(mut, address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA holding the delegated mint authority; it holds no data
+    #[account(
+        seeds = [b"mint_authority", mint.key().as_ref()],
+        bump = token_data.mint_authority_bump,
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: Verified VAA posted by the Core Bridge; parsed and checked in `process`
+    #[account(owner = wormhole_core_bridge::ID)]
+    pub posted_vaa: UncheckedAccount<'info>,
+    
+    /// CHECK: EmitterPolicy PDA of the VAA's emitter, possibly uninitialized; checked in `process`
+    pub emitter_policy: UncheckedAccount<'info>,
+    
+    // Exists once a VAA has been redeemed, so every transfer mints at most once
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + size_of::<ReceivedMessage>(),
+        seeds = [b"received", posted_vaa.key().as_ref()],
+        bump,
+    )]
+    pub received_message: Account<'info, ReceivedMessage>,
+    
+    /// CHECK: Wallet named in the transfer payload; checked in `process`
+    pub recipient: UncheckedAccount<'info>,
+    
+    // Created on the fly for recipients without one, like the Token Bridge's redeem_with_payer
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+    
+    // Holds transfers whose recipient account cannot receive them until they are claimed
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"transfer_escrow", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = mint_authority,
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+    
+    // Required for lock-mode tokens, whose inbound transfers are released from custody
+    #[account(
+        mut,
+        seeds = [b"custody", mint.key().as_ref()],
+        bump,
+    )]
+    pub custody_vault: Option<Account<'info, TokenAccount>>,
+    
+    // Per-chain accounting for the source chain; required for lock-mode tokens
+    #[account(mut)]
+    pub chain_supply: Option<Account<'info, ChainSupply>>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    
+    // Read for the factory's feature switches
+    #[account(address = token_data.factory, seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+}
+
+impl<'info> RedeemTransfer<'info> {
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        require_feature_enabled(&self.token_factory, FEATURE_BRIDGING, "redeem_transfer")?;
+        let token_data = &self.token_data;
+        let vaa = parse_posted_vaa(&self.posted_vaa.try_borrow_data()?)?;
+        
+        require_with_context!(
+            token_data.cross_chain_enabled,
+            TokenFactoryError::CrossChainNotEnabled,
+            "instruction=redeem_transfer token_id={} emitter_chain={}",
+            token_data.token_id,
+            vaa.emitter_chain
+        );
+        require_with_context!(
+            token_data.cross_chain_info.supported_chains.contains(vaa.emitter_chain)
+                && vaa.emitter_address == token_data.cross_chain_info.wormhole_emitter,
+            TokenFactoryError::UntrustedEmitter,
+            "instruction=redeem_transfer token_id={} emitter_chain={} emitter_address={:?}",
+            token_data.token_id,
+            vaa.emitter_chain,
+            vaa.emitter_address.bytes
+        );
+        
+        let (message_type, payload) = deserialize_wormhole_message(&vaa.payload)?;
+        require_message_type_allowed(
+            &self.emitter_policy,
+            &token_data.factory,
+            vaa.emitter_chain,
+            &vaa.emitter_address,
+            message_type,
+            "redeem_transfer",
+        )?;
+        require_with_context!(
+            message_type == wormhole::MSG_TYPE_TOKEN_TRANSFER,
+            TokenFactoryError::UnknownMessageType,
+            "instruction=redeem_transfer token_id={} message_type={}",
+            token_data.token_id,
+            message_type
+        );
+        let transfer = parse_token_transfer_message(&payload)?;
+        require_with_context!(
+            transfer.factory == token_data.factory
+                && transfer.token_id == token_data.token_id
+                && transfer.recipient_chain == wormhole::CHAIN_ID_SOLANA
+                && transfer.amount > 0,
+            TokenFactoryError::InvalidMessagePayload,
+            "instruction=redeem_transfer token_id={} payload_factory={} payload_token_id={} recipient_chain={} amount={}",
+            token_data.token_id,
+            transfer.factory,
+            transfer.token_id,
+            transfer.recipient_chain,
+            transfer.amount
+        );
+        require_with_context!(
+            transfer.recipient == UniversalAddress::from_pubkey(&self.recipient.key()),
+            TokenFactoryError::InvalidRecipient,
+            "instruction=redeem_transfer token_id={} recipient={}",
+            token_data.token_id,
+            self.recipient.key()
+        );
+        
+        // Custody can only release what was locked for transfers to the source chain
+        let is_lock_mode = token_data.bridge_mode == BRIDGE_MODE_LOCK;
+        if let Some(chain_supply) = self.chain_supply.as_ref() {
+            require_with_context!(
+                chain_supply.mint == token_data.mint && chain_supply.chain_id == vaa.emitter_chain,
+                TokenFactoryError::InvalidChainSupply,
+                "instruction=redeem_transfer token_id={} chain_supply={} emitter_chain={}",
+                token_data.token_id,
+                chain_supply.key(),
+                vaa.emitter_chain
+            );
+            require_with_context!(
+                !is_lock_mode || transfer.amount <= chain_supply.outstanding(),
+                TokenFactoryError::InvalidChainSupply,
+                "instruction=redeem_transfer token_id={} amount={} outstanding={}",
+                token_data.token_id,
+                transfer.amount,
+                chain_supply.outstanding()
+            );
+        } else {
+            require_with_context!(
+                !is_lock_mode,
+                TokenFactoryError::InvalidChainSupply,
+                "instruction=redeem_transfer token_id={} chain_supply account missing",
+                token_data.token_id
+            );
+        }
+
+        // A frozen recipient account would fail the whole redeem; escrow instead so the VAA
+        // is still consumed and the recipient can claim to another account later
+        let escrowed = self.recipient_token_account.is_frozen();
+        let destination = if escrowed {
+            self.escrow_vault.to_account_info()
+        } else {
+            self.recipient_token_account.to_account_info()
+        };
+        
+        let mint_key = token_data.mint;
+        let mint_bump = [token_data.mint_authority_bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"mint_authority", mint_key.as_ref(), &mint_bump]];
+        if is_lock_mode {
+            let custody_vault = self
+                .custody_vault
+                .as_ref()
+                .ok_or(TokenFactoryError::CustodyNotInitialized)?;
+            token::transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    token::Transfer {
+                        from: custody_vault.to_account_info(),
+                        to: destination,
+                        authority: self.mint_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                transfer.amount,
+            )?;
+        } else {
+            token::mint_to(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    token::MintTo {
+                        mint: self.mint.to_account_info(),
+                        to: destination,
+                        authority: self.mint_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                transfer.amount,
+            )?;
+        }
+        
+        self.token_stats.total_bridged_in = self
+            .token_stats
+            .total_bridged_in
+            .checked_add(transfer.amount)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        if let Some(chain_supply) = self.chain_supply.as_mut() {
+            chain_supply.bridged_in = chain_supply
+                .bridged_in
+                .checked_add(transfer.amount)
+                .ok_or(TokenFactoryError::MathOverflow)?;
+        }
+        
+        let received_message = &mut self.received_message;
+        received_message.posted_vaa = self.posted_vaa.key();
+        received_message.emitter_chain = vaa.emitter_chain;
+        received_message.sequence = vaa.sequence;
+        received_message.mint = mint_key;
+        received_message.recipient = self.recipient.key();
+        received_message.escrowed_amount = if escrowed { transfer.amount } else { 0 };
+        received_message.bump = bump;
+        received_message.account_version = ACCOUNT_VERSION;
+        
+        let now = Clock::get()?.unix_timestamp;
+        emit!(TokensBridgedInEvent {
+            token_id: self.token_data.token_id,
+            mint: mint_key,
+            source_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+            recipient: self.recipient.key(),
+            amount: transfer.amount,
+            escrowed,
+            timestamp: now,
+        });
+        emit_digest(DIGEST_ACTION_BRIDGED_IN, mint_key, self.recipient.key(), transfer.amount, now);
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeCustody<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    /// CHECK: Program PDA that signs for the custody and escrow vaults; it holds no data
+    #[account(seeds = [b"mint_authority", mint.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"custody", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = mint_authority,
+    )]
+    pub custody_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> InitializeCustody<'info> {
+    pub fn process(&mut self, mint_authority_bump: u8) -> Result<()> {
+        let token_data = &mut self.token_data;
+        require_with_context!(
+            token_data.bridge_mode == BRIDGE_MODE_LOCK,
+            TokenFactoryError::InvalidBridgeMode,
+            "instruction=initialize_custody token_id={} bridge_mode={}",
+            token_data.token_id,
+            token_data.bridge_mode
+        );
+        
+        // Lock-mode mints need not delegate their mint authority, but the PDA still signs for custody
+        token_data.mint_authority_bump = mint_authority_bump;
+        
+        emit!(CustodyInitializedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            custody_vault: self.custody_vault.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ClaimEscrowedTransfer<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA holding the delegated mint authority; owns the escrow vault
+    #[account(
+        seeds = [b"mint_authority", mint.key().as_ref()],
+        bump = token_data.mint_authority_bump,
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        has_one = mint,
+        has_one = recipient,
+    )]
+    pub received_message: Account<'info, ReceivedMessage>,
+    
+    #[account(
+        mut,
+        seeds = [b"transfer_escrow", mint.key().as_ref()],
+        bump,
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = mint)]
+    pub destination_token_account: Account<'info, TokenAccount>,
+    
+    pub recipient: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClaimEscrowedTransfer<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let amount = self.received_message.escrowed_amount;
+        require_with_context!(
+            amount > 0,
+            TokenFactoryError::NothingToClaim,
+            "instruction=claim_escrowed_transfer token_id={} recipient={} sequence={}",
+            self.token_data.token_id,
+            self.recipient.key(),
+            self.received_message.sequence
+        );
+        
+        let mint_key = self.token_data.mint;
+        let mint_bump = [self.token_data.mint_authority_bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"mint_authority", mint_key.as_ref(), &mint_bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.escrow_vault.to_account_info(),
+                    to: self.destination_token_account.to_account_info(),
+                    authority: self.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+        
+        self.received_message.escrowed_amount = 0;
+        
+        emit!(EscrowedTransferClaimedEvent {
+            token_id: self.token_data.token_id,
+            mint: mint_key,
+            recipient: self.recipient.key(),
+            destination: self.destination_token_account.key(),
+            sequence: self.received_message.sequence,
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+// Charges the factory's outbound message fee, on top of the Wormhole fee. Messages signed by
+// the factory authority (an admin key or governance Realm) are exempt.
+pub fn collect_bridge_fee<'info>(
+    token_factory: &Account<'info, TokenFactory>,
+    payer: &Signer<'info>,
+    treasury: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<u64> {
+    let fee = token_factory.bridge_fee_lamports;
+    if fee == 0 || payer.key() == token_factory.authority {
+        return Ok(0);
+    }
+
+    system_program::transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            system_program::Transfer {
+                from: payer.to_account_info(),
+                to: treasury.clone(),
+            },
+        ),
+        fee,
+    )?;
+    
+    emit!(BridgeFeeCollectedEvent {
+        payer: payer.key(),
+        treasury: treasury.key(),
+        fee,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(fee)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct BridgeLimits {
+    pub daily_cap: u64, // Max tokens bridged out per window, 0 = unlimited
+    pub pending_daily_cap: u64,
+    pub pending_cap_effective_ts: i64, // 0 when no increase is pending
+}
+
+impl BridgeLimits {
+    // Cap in force at `now`, applying a pending increase once its delay has passed
+    pub fn effective_daily_cap(&self, now: i64) -> u64 {
+        if self.pending_cap_effective_ts != 0 && now >= self.pending_cap_effective_ts {
+            self.pending_daily_cap
+        } else {
+            self.daily_cap
+        }
+    }
+}
+
+// Outbound volume still counted against the daily cap at `now`, from the volume counted at
+// `last_ts`. Each token counts fully when sent and fades out linearly over BRIDGE_WINDOW_SECONDS,
+// so a full cap sent just before a day boundary cannot be followed by another one just after it.
+// Rounded up, in favour of the cap.
+pub fn decayed_bridge_volume(volume: u64, last_ts: i64, now: i64) -> u64 {
+    let remaining = BRIDGE_WINDOW_SECONDS - now.saturating_sub(last_ts).clamp(0, BRIDGE_WINDOW_SECONDS);
+    let window = BRIDGE_WINDOW_SECONDS as u128;
+    ((volume as u128 * remaining as u128).div_ceil(window)) as u64
+}
+
+// Per-token circuit breaker on outbound volume, shared by every path that sends tokens off
+// Solana. Returns the window volume to store once the transfer has gone through.
+pub fn bridged_volume_within_cap(
+    token_data: &TokenData,
+    token_stats: &TokenStats,
+    amount: u64,
+    now: i64,
+    instruction: &str,
+) -> Result<u64> {
+    let daily_cap = token_data.bridge_limits.effective_daily_cap(now);
+    let bridged_in_window = decayed_bridge_volume(token_stats.bridged_in_window, token_stats.bridge_window_start_ts, now)
+        .checked_add(amount)
+        .ok_or(TokenFactoryError::MathOverflow)?;
+    require_with_context!(
+        daily_cap == 0 || bridged_in_window <= daily_cap,
+        TokenFactoryError::BridgeCapExceeded,
+        "instruction={} token_id={} amount={} bridged_in_window={} daily_cap={}",
+        instruction,
+        token_data.token_id,
+        amount,
+        token_stats.bridged_in_window,
+        daily_cap
+    );
+    Ok(bridged_in_window)
+}
+
+/// Replay guard for a redeemed VAA
+#[account]
+pub struct ReceivedMessage {
+    pub posted_vaa: Pubkey,
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub escrowed_amount: u64, // Minted to the transfer escrow, awaiting claim_escrowed_transfer
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+#[event]
+pub struct TokensBridgedInEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub source_chain: u16,
+    pub sequence: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub escrowed: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EscrowedTransferClaimedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub destination: Pubkey,
+    pub sequence: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokensBridgedOutEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub sender: Pubkey,
+    pub target_chain: u16,
+    pub recipient: UniversalAddress,
+    pub amount: u64,
+    pub bridged_in_window: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BridgeFeeCollectedEvent {
+    pub payer: Pubkey,
+    pub treasury: Pubkey,
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BridgeFeeConfiguredEvent {
+    pub bridge_fee_lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BridgeCapConfiguredEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub daily_cap: u64,
+    pub effective_ts: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CustodyInitializedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub custody_vault: Pubkey,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Bridges `amount` at `now` if the cap allows it, the way bridge_out does
+    fn bridge(volume: &mut u64, last_ts: &mut i64, amount: u64, now: i64, daily_cap: u64) -> bool {
+        let counted = decayed_bridge_volume(*volume, *last_ts, now) + amount;
+        if counted > daily_cap {
+            return false;
+        }
+        (*volume, *last_ts) = (counted, now);
+        true
+    }
+
+    #[test]
+    fn a_full_cap_cannot_be_sent_again_across_a_day_boundary() {
+        let (mut volume, mut last_ts) = (0, 0);
+        let day = BRIDGE_WINDOW_SECONDS;
+        assert!(bridge(&mut volume, &mut last_ts, 1_000, day - 60, 1_000));
+        
+        // Two minutes later, on the other side of the boundary, barely anything has faded
+        assert!(!bridge(&mut volume, &mut last_ts, 1_000, day + 60, 1_000));
+        assert!(!bridge(&mut volume, &mut last_ts, 2, day + 60, 1_000));
+        assert!(bridge(&mut volume, &mut last_ts, 1, day + 60, 1_000));
+        
+        // Half a day on, about half the cap is free again, and all of it after a full day
+        assert!(bridge(&mut volume, &mut last_ts, 498, day - 60 + day / 2, 1_000));
+        assert!(!bridge(&mut volume, &mut last_ts, 1, day - 60 + day / 2, 1_000));
+        assert!(bridge(&mut volume, &mut last_ts, 1_000, 3 * day, 1_000));
+    }
+
+    #[test]
+    fn volume_fades_linearly_and_rounds_up() {
+        assert_eq!(decayed_bridge_volume(1_000, 100, 100), 1_000);
+        assert_eq!(decayed_bridge_volume(1_000, 100, 100 + BRIDGE_WINDOW_SECONDS / 4), 750);
+        assert_eq!(decayed_bridge_volume(1_000, 100, 100 + BRIDGE_WINDOW_SECONDS - 1), 1);
+        assert_eq!(decayed_bridge_volume(1_000, 100, 100 + BRIDGE_WINDOW_SECONDS), 0);
+        // A clock behind the last update counts the volume in full
+        assert_eq!(decayed_bridge_volume(1_000, 100, 50), 1_000);
+    }
+}
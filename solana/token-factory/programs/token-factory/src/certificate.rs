@@ -0,0 +1,331 @@
+// Certificate module for Crossify Token Factory
+// This file contains launch certificates: compressed NFTs minted through Bubblegum to a token's
+// creator, recording what was launched and when
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use std::mem::size_of;
+
+use crate::admin_log::{admin_value, record_admin_action, ADMIN_ACTION_LAUNCH_CERTIFICATES};
+use crate::{
+    mpl_bubblegum, spl_account_compression, spl_noop, TokenData, TokenFactory, TokenFactoryError, ACCOUNT_RESERVED_LEN,
+    ACCOUNT_VERSION, MAX_METADATA_URI_LEN, MAX_NAME_LEN,
+};
+
+// Bubblegum's Anchor discriminator for mint_v1
+const BUBBLEGUM_MINT_V1: [u8; 8] = [145, 98, 192, 118, 184, 147, 118, 104];
+
+// Longest symbol Bubblegum metadata accepts
+pub const MAX_CERTIFICATE_SYMBOL_LEN: usize = 10;
+
+#[derive(Accounts)]
+pub struct ConfigureLaunchCertificates<'info> {
+    #[account(seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + size_of::<CertificateConfig>(),
+        seeds = [b"certificate_config", token_factory.key().as_ref()],
+        bump,
+    )]
+    pub certificate_config: Account<'info, CertificateConfig>,
+    
+    /// CHECK: Concurrent merkle tree owned by the compression program; Bubblegum checks that
+    /// certificate_config is its tree delegate on every mint
+    #[account(owner = spl_account_compression::ID)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// CHECK: Factory admin log, checked against its derived address; skipped until opened
+    #[account(mut, seeds = [b"admin_log", token_factory.key().as_ref()], bump)]
+    pub admin_log: UncheckedAccount<'info>,
+}
+
+impl<'info> ConfigureLaunchCertificates<'info> {
+    // The tree must have been created with, or delegated to, certificate_config beforehand
+    pub fn process(&mut self, bump: u8, enabled: bool) -> Result<()> {
+        // Verify authority
+        require_with_context!(
+            self.token_factory.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_launch_certificates signer={} expected_authority={}",
+            self.authority.key(),
+            self.token_factory.authority
+        );
+        
+        record_admin_action(
+            &self.admin_log,
+            self.authority.key(),
+            ADMIN_ACTION_LAUNCH_CERTIFICATES,
+            0,
+            self.merkle_tree.key(),
+            admin_value(&[self.certificate_config.enabled as u64]),
+            admin_value(&[enabled as u64]),
+        )?;
+        
+        let certificate_config = &mut self.certificate_config;
+        certificate_config.factory = self.token_factory.key();
+        certificate_config.merkle_tree = self.merkle_tree.key();
+        certificate_config.enabled = enabled;
+        certificate_config.bump = bump;
+        certificate_config.account_version = ACCOUNT_VERSION;
+        
+        emit!(LaunchCertificatesConfiguredEvent {
+            merkle_tree: certificate_config.merkle_tree,
+            enabled,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct MintLaunchCertificate<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        mut,
+        seeds = [b"certificate_config", token_data.factory.as_ref()],
+        bump = certificate_config.bump,
+    )]
+    pub certificate_config: Account<'info, CertificateConfig>,
+    
+    // One certificate per launch
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + size_of::<LaunchCertificate>(),
+        seeds = [b"launch_certificate", token_data.mint.as_ref()],
+        bump,
+    )]
+    pub launch_certificate: Account<'info, LaunchCertificate>,
+    
+    // The token's creator, who receives the certificate
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    // Bubblegum validates its own and the compression program's accounts below
+    
+    /// CHECK: Bubblegum tree config PDA of merkle_tree
+    #[account(mut)]
+    pub tree_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: The configured certificate tree
+    #[account(mut, address = certificate_config.merkle_tree)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL Noop program, which logs the new leaf for indexers
+    #[account(address = spl_noop::ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL Account Compression program
+    #[account(address = spl_account_compression::ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    
+    /// CHECK: Metaplex Bubblegum program
+    #[account(address = mpl_bubblegum::ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MintLaunchCertificate<'info> {
+    // Optional and creator-initiated, so create_token stays within its compute budget
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        let token_data = &self.token_data;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=mint_launch_certificate token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            self.authority.key(),
+            token_data.authority
+        );
+        require_with_context!(
+            self.certificate_config.enabled,
+            TokenFactoryError::LaunchCertificatesDisabled,
+            "instruction=mint_launch_certificate token_id={}",
+            token_data.token_id
+        );
+        require_with_context!(
+            token_data.name.len() <= MAX_NAME_LEN
+                && token_data.symbol.len() <= MAX_CERTIFICATE_SYMBOL_LEN
+                && token_data.metadata_uri.len() <= MAX_METADATA_URI_LEN,
+            TokenFactoryError::InvalidMetadata,
+            "instruction=mint_launch_certificate token_id={} name_len={} symbol_len={} metadata_uri_len={}",
+            token_data.token_id,
+            token_data.name.len(),
+            token_data.symbol.len(),
+            token_data.metadata_uri.len()
+        );
+        
+        let metadata = BubblegumMetadataArgs {
+            name: token_data.name.clone(),
+            symbol: token_data.symbol.clone(),
+            uri: token_data.metadata_uri.clone(),
+            seller_fee_basis_points: 0,
+            primary_sale_happened: true,
+            is_mutable: false,
+            edition_nonce: None,
+            token_standard: Some(0), // NonFungible
+            collection: None,
+            uses: None,
+            token_program_version: 0, // Original
+            creators: vec![BubblegumCreator {
+                address: token_data.authority,
+                verified: false,
+                share: 100,
+            }],
+        };
+        let mut data = BUBBLEGUM_MINT_V1.to_vec();
+        data.extend_from_slice(&metadata.try_to_vec().unwrap());
+        let mint_ix = Instruction {
+            program_id: mpl_bubblegum::ID,
+            accounts: vec![
+                AccountMeta::new(self.tree_authority.key(), false),
+                AccountMeta::new_readonly(self.authority.key(), false),
+                AccountMeta::new_readonly(self.authority.key(), false),
+                AccountMeta::new(self.merkle_tree.key(), false),
+                AccountMeta::new(self.authority.key(), true),
+                AccountMeta::new_readonly(self.certificate_config.key(), true),
+                AccountMeta::new_readonly(self.log_wrapper.key(), false),
+                AccountMeta::new_readonly(self.compression_program.key(), false),
+                AccountMeta::new_readonly(self.system_program.key(), false),
+            ],
+            data,
+        };
+        let factory_key = self.certificate_config.factory;
+        let config_bump = [self.certificate_config.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"certificate_config", factory_key.as_ref(), &config_bump]];
+        invoke_signed(
+            &mint_ix,
+            &[
+                self.tree_authority.to_account_info(),
+                self.authority.to_account_info(),
+                self.merkle_tree.to_account_info(),
+                self.certificate_config.to_account_info(),
+                self.log_wrapper.to_account_info(),
+                self.compression_program.to_account_info(),
+                self.system_program.to_account_info(),
+                self.bubblegum_program.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+        
+        let now = Clock::get()?.unix_timestamp;
+        let certificate_config = &mut self.certificate_config;
+        let launch_certificate = &mut self.launch_certificate;
+        launch_certificate.mint = token_data.mint;
+        launch_certificate.creator = token_data.authority;
+        launch_certificate.merkle_tree = certificate_config.merkle_tree;
+        launch_certificate.leaf_index = certificate_config.minted;
+        launch_certificate.created_slot = token_data.created_slot;
+        launch_certificate.minted_at = now;
+        launch_certificate.bump = bump;
+        launch_certificate.account_version = ACCOUNT_VERSION;
+        certificate_config.minted = certificate_config.minted.saturating_add(1);
+        
+        emit!(LaunchCertificateMintedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            creator: token_data.authority,
+            merkle_tree: launch_certificate.merkle_tree,
+            leaf_index: launch_certificate.leaf_index,
+            created_slot: launch_certificate.created_slot,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+/// Factory-wide launch certificate settings
+#[account]
+pub struct CertificateConfig {
+    pub factory: Pubkey,
+    pub merkle_tree: Pubkey, // Tree delegated to this PDA
+    pub enabled: bool,
+    pub minted: u64, // Certificates minted into merkle_tree, tracking its leaf index
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+/// Marks a launch as certified and points at its certificate leaf
+#[account]
+pub struct LaunchCertificate {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub leaf_index: u64, // Assumes the tree only ever receives certificates
+    pub created_slot: u64, // Slot the token was created in; 0 for tokens created before it was recorded
+    pub minted_at: i64,
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+// Bubblegum's MetadataArgs; enums are written as their Borsh variant index
+#[derive(AnchorSerialize)]
+struct BubblegumMetadataArgs {
+    name: String,
+    symbol: String,
+    uri: String,
+    seller_fee_basis_points: u16,
+    primary_sale_happened: bool,
+    is_mutable: bool,
+    edition_nonce: Option<u8>,
+    token_standard: Option<u8>,
+    collection: Option<BubblegumCollection>,
+    uses: Option<BubblegumUses>,
+    token_program_version: u8,
+    creators: Vec<BubblegumCreator>,
+}
+
+#[derive(AnchorSerialize)]
+struct BubblegumCollection {
+    verified: bool,
+    key: Pubkey,
+}
+
+#[derive(AnchorSerialize)]
+struct BubblegumUses {
+    use_method: u8,
+    remaining: u64,
+    total: u64,
+}
+
+#[derive(AnchorSerialize)]
+struct BubblegumCreator {
+    address: Pubkey,
+    verified: bool,
+    share: u8,
+}
+
+#[event]
+pub struct LaunchCertificatesConfiguredEvent {
+    pub merkle_tree: Pubkey,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LaunchCertificateMintedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub leaf_index: u64,
+    pub created_slot: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,635 @@
+// Chain registry module for Crossify Token Factory
+// This file contains admin-maintained per-chain parameters: delivery fees for outbound messages and
+// finality bounds and per-emitter message type allowlists for inbound ones
+
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+use crate::admin_log::{
+    admin_value, record_admin_action, ADMIN_ACTION_CHAIN_FEES, ADMIN_ACTION_CHAIN_FINALITY, ADMIN_ACTION_EMITTER_POLICY,
+    ADMIN_ACTION_PRICE_GUARD,
+};
+use crate::wormhole::UniversalAddress;
+use crate::{MirroredCurveState, TokenFactory, TokenFactoryError, ACCOUNT_RESERVED_LEN, ACCOUNT_VERSION};
+
+// Longest span a price guard holds one reference price for (one day)
+pub const MAX_PRICE_GUARD_WINDOW: u32 = 86_400;
+
+#[derive(Accounts)]
+#[instruction(chain_id: u16)]
+pub struct ConfigureChain<'info> {
+    #[account(seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + size_of::<ChainConfig>(),
+        seeds = [b"chain_config", token_factory.key().as_ref(), &chain_id.to_le_bytes()],
+        bump,
+    )]
+    pub chain_config: Account<'info, ChainConfig>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// CHECK: Factory admin log, checked against its derived address; skipped until opened
+    #[account(mut, seeds = [b"admin_log", token_factory.key().as_ref()], bump)]
+    pub admin_log: UncheckedAccount<'info>,
+}
+
+impl<'info> ConfigureChain<'info> {
+    pub fn process(&mut self, bump: u8, chain_id: u16, fee_params: DeliveryFeeParams) -> Result<()> {
+        // Verify authority
+        require_with_context!(
+            self.token_factory.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_chain chain_id={} signer={} expected_authority={}",
+            chain_id,
+            self.authority.key(),
+            self.token_factory.authority
+        );
+        
+        record_admin_action(
+            &self.admin_log,
+            self.authority.key(),
+            ADMIN_ACTION_CHAIN_FEES,
+            chain_id,
+            Pubkey::default(),
+            self.chain_config.fee_params.admin_value(),
+            fee_params.admin_value(),
+        )?;
+        
+        let chain_config = &mut self.chain_config;
+        chain_config.factory = self.token_factory.key();
+        chain_config.chain_id = chain_id;
+        chain_config.fee_params = fee_params;
+        chain_config.updated_at = Clock::get()?.unix_timestamp;
+        chain_config.bump = bump;
+        chain_config.account_version = ACCOUNT_VERSION;
+        
+        emit!(ChainConfiguredEvent {
+            chain_id,
+            base_fee_lamports: fee_params.base_fee_lamports,
+            gas_limit: fee_params.gas_limit,
+            lamports_per_gas: fee_params.lamports_per_gas,
+            lamports_per_byte: fee_params.lamports_per_byte,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id: u16)]
+pub struct ConfigureChainFinality<'info> {
+    #[account(seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + size_of::<ChainFinality>(),
+        seeds = [b"chain_finality", token_factory.key().as_ref(), &chain_id.to_le_bytes()],
+        bump,
+    )]
+    pub chain_finality: Account<'info, ChainFinality>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// CHECK: Factory admin log, checked against its derived address; skipped until opened
+    #[account(mut, seeds = [b"admin_log", token_factory.key().as_ref()], bump)]
+    pub admin_log: UncheckedAccount<'info>,
+}
+
+impl<'info> ConfigureChainFinality<'info> {
+    pub fn process(
+        &mut self,
+        bump: u8,
+        chain_id: u16,
+        min_source_confirmation: u32,
+        max_staleness: u32,
+    ) -> Result<()> {
+        // Verify authority
+        require_with_context!(
+            self.token_factory.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_chain_finality chain_id={} signer={} expected_authority={}",
+            chain_id,
+            self.authority.key(),
+            self.token_factory.authority
+        );
+        require_with_context!(
+            max_staleness == 0 || max_staleness > min_source_confirmation,
+            TokenFactoryError::InvalidFinalityBounds,
+            "instruction=configure_chain_finality chain_id={} min_source_confirmation={} max_staleness={}",
+            chain_id,
+            min_source_confirmation,
+            max_staleness
+        );
+        
+        record_admin_action(
+            &self.admin_log,
+            self.authority.key(),
+            ADMIN_ACTION_CHAIN_FINALITY,
+            chain_id,
+            Pubkey::default(),
+            admin_value(&[
+                self.chain_finality.min_source_confirmation as u64,
+                self.chain_finality.max_staleness as u64,
+            ]),
+            admin_value(&[min_source_confirmation as u64, max_staleness as u64]),
+        )?;
+        
+        let chain_finality = &mut self.chain_finality;
+        chain_finality.factory = self.token_factory.key();
+        chain_finality.chain_id = chain_id;
+        chain_finality.min_source_confirmation = min_source_confirmation;
+        chain_finality.max_staleness = max_staleness;
+        chain_finality.bump = bump;
+        chain_finality.account_version = ACCOUNT_VERSION;
+        
+        emit!(ChainFinalityConfiguredEvent {
+            chain_id,
+            min_source_confirmation,
+            max_staleness,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id: u16, emitter: UniversalAddress)]
+pub struct ConfigureEmitterPolicy<'info> {
+    #[account(seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + size_of::<EmitterPolicy>(),
+        seeds = [b"emitter_policy", token_factory.key().as_ref(), &chain_id.to_le_bytes(), &emitter.bytes],
+        bump,
+    )]
+    pub emitter_policy: Account<'info, EmitterPolicy>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// CHECK: Factory admin log, checked against its derived address; skipped until opened
+    #[account(mut, seeds = [b"admin_log", token_factory.key().as_ref()], bump)]
+    pub admin_log: UncheckedAccount<'info>,
+}
+
+impl<'info> ConfigureEmitterPolicy<'info> {
+    pub fn process(
+        &mut self,
+        bump: u8,
+        chain_id: u16,
+        emitter: UniversalAddress,
+        allowed_message_types: u64,
+    ) -> Result<()> {
+        // Verify authority
+        require_with_context!(
+            self.token_factory.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_emitter_policy chain_id={} signer={} expected_authority={}",
+            chain_id,
+            self.authority.key(),
+            self.token_factory.authority
+        );
+        
+        record_admin_action(
+            &self.admin_log,
+            self.authority.key(),
+            ADMIN_ACTION_EMITTER_POLICY,
+            chain_id,
+            Pubkey::new_from_array(emitter.bytes),
+            admin_value(&[self.emitter_policy.allowed_message_types]),
+            admin_value(&[allowed_message_types]),
+        )?;
+        
+        let emitter_policy = &mut self.emitter_policy;
+        emitter_policy.factory = self.token_factory.key();
+        emitter_policy.chain_id = chain_id;
+        emitter_policy.emitter = emitter;
+        emitter_policy.allowed_message_types = allowed_message_types;
+        emitter_policy.bump = bump;
+        emitter_policy.account_version = ACCOUNT_VERSION;
+        
+        emit!(EmitterPolicyConfiguredEvent {
+            chain_id,
+            emitter,
+            allowed_message_types,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id: u16)]
+pub struct ConfigurePriceGuard<'info> {
+    #[account(seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + size_of::<PriceGuard>(),
+        seeds = [b"price_guard", token_factory.key().as_ref(), &chain_id.to_le_bytes()],
+        bump,
+    )]
+    pub price_guard: Account<'info, PriceGuard>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// CHECK: Factory admin log, checked against its derived address; skipped until opened
+    #[account(mut, seeds = [b"admin_log", token_factory.key().as_ref()], bump)]
+    pub admin_log: UncheckedAccount<'info>,
+}
+
+impl<'info> ConfigurePriceGuard<'info> {
+    // A zero window switches the guard off for the chain
+    pub fn process(&mut self, bump: u8, chain_id: u16, max_deviation_bps: u16, window: u32) -> Result<()> {
+        // Verify authority
+        require_with_context!(
+            self.token_factory.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_price_guard chain_id={} signer={} expected_authority={}",
+            chain_id,
+            self.authority.key(),
+            self.token_factory.authority
+        );
+        require_with_context!(
+            max_deviation_bps > 0 && window <= MAX_PRICE_GUARD_WINDOW,
+            TokenFactoryError::InvalidPriceGuard,
+            "instruction=configure_price_guard chain_id={} max_deviation_bps={} window={} max_window={}",
+            chain_id,
+            max_deviation_bps,
+            window,
+            MAX_PRICE_GUARD_WINDOW
+        );
+        
+        record_admin_action(
+            &self.admin_log,
+            self.authority.key(),
+            ADMIN_ACTION_PRICE_GUARD,
+            chain_id,
+            Pubkey::default(),
+            admin_value(&[self.price_guard.max_deviation_bps as u64, self.price_guard.window as u64]),
+            admin_value(&[max_deviation_bps as u64, window as u64]),
+        )?;
+        
+        let price_guard = &mut self.price_guard;
+        price_guard.factory = self.token_factory.key();
+        price_guard.chain_id = chain_id;
+        price_guard.max_deviation_bps = max_deviation_bps;
+        price_guard.window = window;
+        price_guard.bump = bump;
+        price_guard.account_version = ACCOUNT_VERSION;
+        
+        emit!(PriceGuardConfiguredEvent {
+            chain_id,
+            max_deviation_bps,
+            window,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id: u16)]
+pub struct QuoteDeliveryFee<'info> {
+    #[account(seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    #[account(
+        seeds = [b"chain_config", token_factory.key().as_ref(), &chain_id.to_le_bytes()],
+        bump = chain_config.bump,
+    )]
+    pub chain_config: Account<'info, ChainConfig>,
+}
+
+impl<'info> QuoteDeliveryFee<'info> {
+    pub fn process(&self, chain_id: u16, payload_len: u32) -> Result<u64> {
+        let delivery_fee = self
+            .chain_config
+            .fee_params
+            .delivery_fee(payload_len)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        let total_fee = delivery_fee
+            .checked_add(self.token_factory.bridge_fee_lamports)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        
+        emit!(DeliveryFeeQuotedEvent {
+            chain_id,
+            payload_len,
+            delivery_fee,
+            protocol_fee: self.token_factory.bridge_fee_lamports,
+            total_fee,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(total_fee)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct DeliveryFeeParams {
+    pub base_fee_lamports: u64,
+    pub gas_limit: u64,         // Destination gas budgeted for delivery
+    pub lamports_per_gas: u64,  // Destination gas price converted to lamports
+    pub lamports_per_byte: u64, // Relayer surcharge per payload byte
+}
+
+impl DeliveryFeeParams {
+    // Relayer cost, in lamports, of delivering a `payload_len`-byte message
+    pub fn delivery_fee(&self, payload_len: u32) -> Option<u64> {
+        self.gas_limit
+            .checked_mul(self.lamports_per_gas)?
+            .checked_add(self.lamports_per_byte.checked_mul(payload_len as u64)?)?
+            .checked_add(self.base_fee_lamports)
+    }
+
+    // Admin log encoding, fields in declaration order
+    pub fn admin_value(&self) -> [u8; 32] {
+        admin_value(&[self.base_fee_lamports, self.gas_limit, self.lamports_per_gas, self.lamports_per_byte])
+    }
+}
+
+#[account]
+pub struct ChainConfig {
+    pub factory: Pubkey,
+    pub chain_id: u16,
+    pub fee_params: DeliveryFeeParams,
+    pub updated_at: i64,
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+/// How long inbound state updates from a chain must age before use, and when they expire
+#[account]
+pub struct ChainFinality {
+    pub factory: Pubkey,
+    pub chain_id: u16,
+    pub min_source_confirmation: u32, // Seconds after the source block before an update is acted on
+    pub max_staleness: u32,           // Seconds after the source block an update is still accepted; 0 = no limit
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+impl ChainFinality {
+    // Checks a source block time against the bounds, returning the update's age in seconds
+    pub fn check_update_age(&self, source_timestamp: i64, now: i64) -> Result<i64> {
+        let age = now.saturating_sub(source_timestamp);
+        require_with_context!(
+            age >= self.min_source_confirmation as i64,
+            TokenFactoryError::UpdateNotFinal,
+            "chain_id={} age={} min_source_confirmation={}",
+            self.chain_id,
+            age,
+            self.min_source_confirmation
+        );
+        require_with_context!(
+            self.max_staleness == 0 || age <= self.max_staleness as i64,
+            TokenFactoryError::StaleUpdate,
+            "chain_id={} age={} max_staleness={}",
+            self.chain_id,
+            age,
+            self.max_staleness
+        );
+        Ok(age)
+    }
+}
+
+/// Largest move a mirrored price may make within one window of source time
+#[account]
+pub struct PriceGuard {
+    pub factory: Pubkey,
+    pub chain_id: u16,
+    pub max_deviation_bps: u16, // Relative to the price the current window opened at
+    pub window: u32,            // Source-time seconds each reference price is held for; 0 = off
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+impl PriceGuard {
+    // Rejects `new_price` if it moves too far from the price its window opened at, so a run of
+    // small steps cannot compound past the bound. A window opens at the last mirrored price once
+    // the previous one has run out, or at `new_price` itself after a full window without updates.
+    // Returns the reference price and source time to record with the update
+    pub fn check(&self, mirrored_curve: &MirroredCurveState, new_price: u64, source_ts: i64) -> Result<(u64, i64)> {
+        let window = self.window as i64;
+        let mut reference = (mirrored_curve.reference_price, mirrored_curve.reference_timestamp);
+        if reference.0 == 0 || source_ts.saturating_sub(reference.1) >= window {
+            reference = (mirrored_curve.current_price, mirrored_curve.source_timestamp);
+        }
+        let (reference_price, reference_ts) = reference;
+        let elapsed = source_ts.saturating_sub(reference_ts);
+        if self.window == 0 || reference_price == 0 || elapsed >= window {
+            return Ok((new_price, source_ts));
+        }
+        let deviation_bps = new_price.abs_diff(reference_price) as u128 * 10_000 / reference_price as u128;
+        require_with_context!(
+            deviation_bps <= self.max_deviation_bps as u128,
+            TokenFactoryError::PriceDeviationTooLarge,
+            "chain_id={} reference_price={} new_price={} deviation_bps={} max_deviation_bps={} elapsed={}",
+            self.chain_id,
+            reference_price,
+            new_price,
+            deviation_bps,
+            self.max_deviation_bps,
+            elapsed
+        );
+        Ok(reference)
+    }
+}
+
+/// Message types one foreign emitter may deliver; emitters without a policy may send any type
+#[account]
+pub struct EmitterPolicy {
+    pub factory: Pubkey,
+    pub chain_id: u16,
+    pub emitter: UniversalAddress,
+    pub allowed_message_types: u64, // Bit n set = message type n allowed
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+// Rejects `message_type` if the emitter's policy exists and excludes it. The policy account is
+// checked against its derived address, so a caller cannot pass an empty one to skip it
+pub fn require_message_type_allowed(
+    emitter_policy: &AccountInfo,
+    factory: &Pubkey,
+    chain_id: u16,
+    emitter: &UniversalAddress,
+    message_type: u8,
+    instruction: &str,
+) -> Result<()> {
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"emitter_policy", factory.as_ref(), &chain_id.to_le_bytes(), &emitter.bytes],
+        &crate::ID,
+    );
+    require_with_context!(
+        emitter_policy.key() == expected,
+        TokenFactoryError::InvalidEmitterPolicy,
+        "instruction={} emitter_policy={} expected={}",
+        instruction,
+        emitter_policy.key(),
+        expected
+    );
+    if emitter_policy.data_is_empty() {
+        return Ok(());
+    }
+    let policy: Account<EmitterPolicy> = Account::try_from(emitter_policy)?;
+    require_with_context!(
+        message_type < 64 && policy.allowed_message_types & (1 << message_type) != 0,
+        TokenFactoryError::MessageTypeNotAllowed,
+        "instruction={} emitter_chain={} message_type={} allowed_message_types={:#x}",
+        instruction,
+        chain_id,
+        message_type,
+        policy.allowed_message_types
+    );
+    Ok(())
+}
+
+#[event]
+pub struct ChainConfiguredEvent {
+    pub chain_id: u16,
+    pub base_fee_lamports: u64,
+    pub gas_limit: u64,
+    pub lamports_per_gas: u64,
+    pub lamports_per_byte: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DeliveryFeeQuotedEvent {
+    pub chain_id: u16,
+    pub payload_len: u32,
+    pub delivery_fee: u64,
+    pub protocol_fee: u64,
+    pub total_fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ChainFinalityConfiguredEvent {
+    pub chain_id: u16,
+    pub min_source_confirmation: u32,
+    pub max_staleness: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PriceGuardConfiguredEvent {
+    pub chain_id: u16,
+    pub max_deviation_bps: u16,
+    pub window: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmitterPolicyConfiguredEvent {
+    pub chain_id: u16,
+    pub emitter: UniversalAddress,
+    pub allowed_message_types: u64,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guard(max_deviation_bps: u16, window: u32) -> PriceGuard {
+        PriceGuard {
+            factory: Pubkey::new_unique(),
+            chain_id: 2,
+            max_deviation_bps,
+            window,
+            bump: 0,
+            account_version: ACCOUNT_VERSION,
+            _reserved: [0; ACCOUNT_RESERVED_LEN],
+        }
+    }
+
+    // Applies `new_price` at `source_ts` if the guard allows it, the way the mirror instructions do
+    fn mirror(guard: &PriceGuard, curve: &mut MirroredCurveState, new_price: u64, source_ts: i64) -> bool {
+        match guard.check(curve, new_price, source_ts) {
+            Ok((reference_price, reference_timestamp)) => {
+                curve.current_price = new_price;
+                curve.source_timestamp = source_ts;
+                curve.reference_price = reference_price;
+                curve.reference_timestamp = reference_timestamp;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn curve(price: u64) -> MirroredCurveState {
+        MirroredCurveState {
+            mint: Pubkey::new_unique(),
+            chain_id: 2,
+            current_price: price,
+            current_supply: 0,
+            current_liquidity: 0,
+            last_sequence: 0,
+            source_timestamp: 0,
+            updated_at: 0,
+            reference_price: 0,
+            reference_timestamp: 0,
+            bump: 0,
+            account_version: ACCOUNT_VERSION,
+            _reserved: [0; 16],
+        }
+    }
+
+    #[test]
+    fn small_steps_do_not_compound_within_a_window() {
+        let guard = guard(1_000, 3_600);
+        let mut curve = curve(1_000_000);
+        for step in 1..=10 {
+            assert!(mirror(&guard, &mut curve, 1_000_000 + step * 10_000, step as i64 * 60));
+        }
+        // 10% above the window's opening price, with every step under 1%; the next one is out
+        assert!(!mirror(&guard, &mut curve, 1_110_000, 660));
+        assert!(!mirror(&guard, &mut curve, 1_105_000, 3_599));
+    }
+
+    #[test]
+    fn a_new_window_opens_at_the_last_price() {
+        let guard = guard(1_000, 3_600);
+        let mut curve = curve(1_000_000);
+        assert!(mirror(&guard, &mut curve, 1_100_000, 3_000));
+        // The first window ran out at 3_600; the next is measured from 1_100_000, mirrored at 3_000
+        assert!(mirror(&guard, &mut curve, 1_200_000, 3_700));
+        assert_eq!((curve.reference_price, curve.reference_timestamp), (1_100_000, 3_000));
+        assert!(!mirror(&guard, &mut curve, 1_250_000, 3_800));
+        // A full window without updates lets the price move freely and opens a window there
+        assert!(mirror(&guard, &mut curve, 5_000_000, 10_000));
+        assert_eq!((curve.reference_price, curve.reference_timestamp), (5_000_000, 10_000));
+    }
+}
@@ -0,0 +1,167 @@
+// Cleanup module for Crossify Token Factory
+// This file contains permissionless cranks closing expired or idle accounts and returning their rent
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    BuyerRecord, Delegation, DelegationRevokedEvent, SessionClosedEvent, TokenData, TokenFactoryError,
+    TradingSession,
+};
+
+// Seconds after a wallet's last sell before its emptied BuyerRecord can be closed
+#[constant]
+pub const STALE_BUYER_RECORD_AGE: i64 = 30 * 86_400;
+
+#[derive(Accounts)]
+pub struct CloseExpiredSession<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"session", session.owner.as_ref(), session.session_key.as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, TradingSession>,
+    
+    /// CHECK: Session owner, who paid its rent; only receives lamports
+    #[account(mut, address = session.owner)]
+    pub owner: UncheckedAccount<'info>,
+    
+    pub cranker: Signer<'info>,
+}
+
+impl<'info> CloseExpiredSession<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let session = &self.session;
+        let now = Clock::get()?.unix_timestamp;
+        require_with_context!(
+            now >= session.expires_at,
+            TokenFactoryError::AccountNotStale,
+            "instruction=close_expired_session session_key={} expires_at={} now={}",
+            session.session_key,
+            session.expires_at,
+            now
+        );
+        
+        emit!(SessionClosedEvent {
+            owner: session.owner,
+            session_key: session.session_key,
+            quote_spent: session.quote_spent,
+            tokens_sold: session.tokens_sold,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CloseExpiredDelegation<'info> {
+    #[account(constraint = token_data.mint == delegation.mint @ TokenFactoryError::InvalidDelegation)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        mut,
+        close = granted_by,
+        seeds = [b"delegation", delegation.mint.as_ref(), delegation.delegate.as_ref()],
+        bump = delegation.bump,
+    )]
+    pub delegation: Account<'info, Delegation>,
+    
+    /// CHECK: Authority that granted and paid for the delegation; only receives lamports
+    #[account(mut, address = delegation.granted_by)]
+    pub granted_by: UncheckedAccount<'info>,
+    
+    pub cranker: Signer<'info>,
+}
+
+impl<'info> CloseExpiredDelegation<'info> {
+    // Delegations also lapse once the token authority changes hands
+    pub fn process(&mut self) -> Result<()> {
+        let delegation = &self.delegation;
+        let token_data = &self.token_data;
+        let now = Clock::get()?.unix_timestamp;
+        require_with_context!(
+            now >= delegation.expires_at || delegation.granted_by != token_data.authority,
+            TokenFactoryError::AccountNotStale,
+            "instruction=close_expired_delegation token_id={} delegate={} expires_at={} now={}",
+            token_data.token_id,
+            delegation.delegate,
+            delegation.expires_at,
+            now
+        );
+        
+        emit!(DelegationRevokedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            delegate: delegation.delegate,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CloseStaleBuyerRecord<'info> {
+    #[account(constraint = token_data.mint == buyer_record.mint)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        mut,
+        close = wallet,
+        seeds = [b"buyer_record", buyer_record.mint.as_ref(), buyer_record.wallet.as_ref()],
+        bump,
+    )]
+    pub buyer_record: Account<'info, BuyerRecord>,
+    
+    /// CHECK: Wallet the record tracks; only receives lamports
+    #[account(mut, address = buyer_record.wallet)]
+    pub wallet: UncheckedAccount<'info>,
+    
+    pub cranker: Signer<'info>,
+}
+
+impl<'info> CloseStaleBuyerRecord<'info> {
+    // Only records with no curve position whose sell limits have long run out, so closing one
+    // changes neither holder counts nor what the wallet may sell next
+    pub fn process(&mut self) -> Result<()> {
+        let token_data = &self.token_data;
+        let buyer_record = &self.buyer_record;
+        let limits = &token_data.sell_limits;
+        let now = Clock::get()?.unix_timestamp;
+        let idle_for = STALE_BUYER_RECORD_AGE
+            .max(limits.cooldown_seconds)
+            .max(limits.window_seconds);
+        require_with_context!(
+            buyer_record.net_bought == 0
+                && buyer_record.last_sell_ts > 0
+                && now >= buyer_record.last_sell_ts.saturating_add(idle_for),
+            TokenFactoryError::AccountNotStale,
+            "instruction=close_stale_buyer_record token_id={} wallet={} net_bought={} last_sell_ts={} now={}",
+            token_data.token_id,
+            buyer_record.wallet,
+            buyer_record.net_bought,
+            buyer_record.last_sell_ts,
+            now
+        );
+        
+        emit!(BuyerRecordClosedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            wallet: buyer_record.wallet,
+            cranker: self.cranker.key(),
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+#[event]
+pub struct BuyerRecordClosedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub cranker: Pubkey,
+    pub timestamp: i64,
+}
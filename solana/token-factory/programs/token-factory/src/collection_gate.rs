@@ -0,0 +1,120 @@
+// Collection gate module for Crossify Token Factory
+// This file contains members-only creation, where creators prove they hold an NFT of the
+// factory's configured verified Metaplex collection
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::{mpl_token_metadata, TokenFactoryError};
+
+// Metaplex account key of a MetadataV1 account
+const METADATA_V1_KEY: u8 = 4;
+
+// Creator entries in Metaplex metadata: address, verified, share
+const METADATA_CREATOR_LEN: usize = 32 + 1 + 1;
+
+// Requires the creator to hold an NFT whose metadata names `required_collection` as its verified
+// collection; a default `required_collection` leaves creation open
+pub fn require_collection_member(
+    required_collection: &Pubkey,
+    nft_account: Option<&Account<TokenAccount>>,
+    nft_metadata: Option<&AccountInfo>,
+    creator: &Pubkey,
+) -> Result<()> {
+    if *required_collection == Pubkey::default() {
+        return Ok(());
+    }
+    let (nft_account, nft_metadata) = match (nft_account, nft_metadata) {
+        (Some(nft_account), Some(nft_metadata)) => (nft_account, nft_metadata),
+        _ => {
+            msg!(
+                "instruction=create_token required_collection={} collection NFT accounts missing",
+                required_collection
+            );
+            return Err(TokenFactoryError::NotCollectionMember.into());
+        }
+    };
+    
+    let (expected_metadata, _) = Pubkey::find_program_address(
+        &[b"metadata", mpl_token_metadata::ID.as_ref(), nft_account.mint.as_ref()],
+        &mpl_token_metadata::ID,
+    );
+    let collection = if nft_metadata.key() == expected_metadata && *nft_metadata.owner == mpl_token_metadata::ID {
+        parse_verified_collection(&nft_metadata.try_borrow_data()?)
+    } else {
+        None
+    };
+    require_with_context!(
+        nft_account.owner == *creator && nft_account.amount == 1 && collection == Some(*required_collection),
+        TokenFactoryError::NotCollectionMember,
+        "instruction=create_token creator={} nft_mint={} nft_owner={} amount={} required_collection={}",
+        creator,
+        nft_account.mint,
+        nft_account.owner,
+        nft_account.amount,
+        required_collection
+    );
+    
+    Ok(())
+}
+
+// Verified collection of a Metaplex MetadataV1 account, if it has one. Walks the Borsh layout up
+// to the collection field, since strings and creators make every earlier offset variable
+fn parse_verified_collection(data: &[u8]) -> Option<Pubkey> {
+    let mut reader = MetadataReader { data, offset: 0 };
+    if reader.take(1)?[0] != METADATA_V1_KEY {
+        return None;
+    }
+    reader.take(32 + 32)?; // update_authority, mint
+    for _ in 0..3 {
+        // name, symbol, uri
+        let len = reader.read_u32()? as usize;
+        reader.take(len)?;
+    }
+    reader.take(2)?; // seller_fee_basis_points
+    if reader.read_option()? {
+        let creators = reader.read_u32()? as usize;
+        reader.take(creators.checked_mul(METADATA_CREATOR_LEN)?)?;
+    }
+    reader.take(2)?; // primary_sale_happened, is_mutable
+    if reader.read_option()? {
+        reader.take(1)?; // edition_nonce
+    }
+    if reader.read_option()? {
+        reader.take(1)?; // token_standard
+    }
+    if !reader.read_option()? {
+        return None;
+    }
+    let verified = reader.take(1)?[0] == 1;
+    let key = Pubkey::new_from_array(reader.take(32)?.try_into().ok()?);
+    verified.then_some(key)
+}
+
+struct MetadataReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> MetadataReader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let end = self.offset.checked_add(len)?;
+        let bytes = self.data.get(self.offset..end)?;
+        self.offset = end;
+        Some(bytes)
+    }
+
+    fn read_u32(&mut self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.take(4)?.try_into().ok()?))
+    }
+
+    fn read_option(&mut self) -> Option<bool> {
+        Some(self.take(1)?[0] == 1)
+    }
+}
+
+#[event]
+pub struct CreationGateConfiguredEvent {
+    pub required_collection: Pubkey, // default once creation is open to everyone again
+    pub timestamp: i64,
+}
@@ -23,7 +23,7 @@ impl<'info> ReceiveWormholeMessage<'info> {
     pub fn process_message(
         &mut self,
         source_chain: u16,
-        source_address: Vec<u8>,
+        source_address: UniversalAddress,
         payload: Vec<u8>
     ) -> Result<()> {
         // Verify the source is trusted
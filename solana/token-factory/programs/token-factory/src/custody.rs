@@ -0,0 +1,115 @@
+// Custody module for Crossify Token Factory
+// This file contains per-chain accounting of bridged supply and the proof-of-reserve view
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::{TokenData, TokenFactoryError, ACCOUNT_RESERVED_LEN, BRIDGE_MODE_LOCK};
+
+#[derive(Accounts)]
+pub struct ProofOfReserve<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        seeds = [b"custody", token_data.mint.as_ref()],
+        bump,
+    )]
+    pub custody_vault: Account<'info, TokenAccount>,
+}
+
+impl<'info> ProofOfReserve<'info> {
+    // Remaining accounts: the token's ChainSupply accounts, one per chain to include
+    pub fn process(&self, remaining_accounts: &[AccountInfo<'info>]) -> Result<ReserveReport> {
+        let token_data = &self.token_data;
+        require_with_context!(
+            token_data.bridge_mode == BRIDGE_MODE_LOCK,
+            TokenFactoryError::InvalidBridgeMode,
+            "instruction=proof_of_reserve token_id={} bridge_mode={}",
+            token_data.token_id,
+            token_data.bridge_mode
+        );
+        
+        let mut chains = Vec::with_capacity(remaining_accounts.len());
+        let mut total_outstanding: u64 = 0;
+        for account in remaining_accounts {
+            let chain_supply: Account<ChainSupply> = Account::try_from(account)?;
+            require_with_context!(
+                chain_supply.mint == token_data.mint
+                    && !chains.iter().any(|c: &ChainOutstanding| c.chain_id == chain_supply.chain_id),
+                TokenFactoryError::InvalidBatch,
+                "instruction=proof_of_reserve token_id={} chain_supply={}",
+                token_data.token_id,
+                account.key()
+            );
+            let outstanding = chain_supply.outstanding();
+            total_outstanding = total_outstanding
+                .checked_add(outstanding)
+                .ok_or(TokenFactoryError::MathOverflow)?;
+            chains.push(ChainOutstanding {
+                chain_id: chain_supply.chain_id,
+                outstanding,
+            });
+        }
+
+        let report = ReserveReport {
+            locked: self.custody_vault.amount,
+            total_outstanding,
+            fully_backed: self.custody_vault.amount >= total_outstanding,
+            chains,
+        };
+        
+        emit!(ProofOfReserveEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            locked: report.locked,
+            total_outstanding,
+            fully_backed: report.fully_backed,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(report)
+    }
+}
+
+/// Tokens bridged between Solana and one remote chain
+#[account]
+pub struct ChainSupply {
+    pub mint: Pubkey,
+    pub chain_id: u16,
+    pub bridged_out: u64, // Sent from Solana to the chain
+    pub bridged_in: u64,  // Redeemed on Solana from the chain
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+impl ChainSupply {
+    // Supply currently represented on the remote chain by transfers from Solana
+    pub fn outstanding(&self) -> u64 {
+        self.bridged_out.saturating_sub(self.bridged_in)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ChainOutstanding {
+    pub chain_id: u16,
+    pub outstanding: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ReserveReport {
+    pub locked: u64, // Custody vault balance
+    pub total_outstanding: u64,
+    pub fully_backed: bool,
+    pub chains: Vec<ChainOutstanding>,
+}
+
+#[event]
+pub struct ProofOfReserveEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub locked: u64,
+    pub total_outstanding: u64,
+    pub fully_backed: bool,
+    pub timestamp: i64,
+}
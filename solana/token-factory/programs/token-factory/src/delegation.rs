@@ -0,0 +1,188 @@
+// Delegation module for Crossify Token Factory
+// This file contains capabilities a token authority grants to other keys, such as operator bots
+
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+use crate::{TokenData, TokenFactoryError, ACCOUNT_RESERVED_LEN, ACCOUNT_VERSION};
+
+// Capability bits; a delegation may hold several
+#[constant]
+pub const CAPABILITY_PUBLISH_MESSAGES: u8 = 1 << 0; // send_cross_chain_message
+#[constant]
+pub const CAPABILITY_PAUSE_TRADING: u8 = 1 << 1; // set_bonding_curve_enabled(false) only
+#[constant]
+pub const CAPABILITY_CLAIM_FEES: u8 = 1 << 2; // claim_creator_fees
+const ALL_CAPABILITIES: u8 = CAPABILITY_PUBLISH_MESSAGES | CAPABILITY_PAUSE_TRADING | CAPABILITY_CLAIM_FEES;
+
+#[derive(Accounts)]
+#[instruction(delegate: Pubkey)]
+pub struct GrantDelegation<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + size_of::<Delegation>(),
+        seeds = [b"delegation", token_data.mint.as_ref(), delegate.as_ref()],
+        bump,
+    )]
+    pub delegation: Account<'info, Delegation>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> GrantDelegation<'info> {
+    // Regranting replaces the capabilities and expiry of an existing delegation
+    pub fn process(&mut self, bump: u8, delegate: Pubkey, capabilities: u8, expires_at: i64) -> Result<()> {
+        let token_data = &self.token_data;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=grant_delegation token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            self.authority.key(),
+            token_data.authority
+        );
+        let now = Clock::get()?.unix_timestamp;
+        require_with_context!(
+            capabilities != 0 && capabilities & !ALL_CAPABILITIES == 0 && expires_at > now,
+            TokenFactoryError::InvalidDelegation,
+            "instruction=grant_delegation token_id={} capabilities={:#x} expires_at={} now={}",
+            token_data.token_id,
+            capabilities,
+            expires_at,
+            now
+        );
+        
+        let delegation = &mut self.delegation;
+        delegation.mint = token_data.mint;
+        delegation.delegate = delegate;
+        delegation.granted_by = self.authority.key();
+        delegation.capabilities = capabilities;
+        delegation.expires_at = expires_at;
+        delegation.bump = bump;
+        delegation.account_version = ACCOUNT_VERSION;
+        
+        emit!(DelegationGrantedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            delegate,
+            capabilities,
+            expires_at,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegation<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"delegation", token_data.mint.as_ref(), delegation.delegate.as_ref()],
+        bump = delegation.bump,
+    )]
+    pub delegation: Account<'info, Delegation>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> RevokeDelegation<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let token_data = &self.token_data;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=revoke_delegation token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            self.authority.key(),
+            token_data.authority
+        );
+        
+        emit!(DelegationRevokedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            delegate: self.delegation.delegate,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+/// Capabilities `delegate` may exercise on one token until `expires_at`
+#[account]
+pub struct Delegation {
+    pub mint: Pubkey,
+    pub delegate: Pubkey,
+    pub granted_by: Pubkey, // Authority at grant time; the delegation lapses if the authority changes
+    pub capabilities: u8,   // CAPABILITY_* bits
+    pub expires_at: i64,
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+// Passes if `signer` is the token authority, or holds an unexpired delegation with `capability`
+// granted by the current authority
+pub fn require_authority_or_delegate(
+    token_data: &TokenData,
+    signer: &Pubkey,
+    delegation: Option<&Account<Delegation>>,
+    capability: u8,
+    instruction: &str,
+) -> Result<()> {
+    if token_data.authority == *signer {
+        return Ok(());
+    }
+    let now = Clock::get()?.unix_timestamp;
+    let delegated = delegation.is_some_and(|delegation| {
+        delegation.mint == token_data.mint
+            && delegation.delegate == *signer
+            && delegation.granted_by == token_data.authority
+            && delegation.capabilities & capability != 0
+            && now < delegation.expires_at
+    });
+    require_with_context!(
+        delegated,
+        TokenFactoryError::InvalidAuthority,
+        "instruction={} token_id={} signer={} expected_authority={} capability={:#x}",
+        instruction,
+        token_data.token_id,
+        signer,
+        token_data.authority,
+        capability
+    );
+    Ok(())
+}
+
+#[event]
+pub struct DelegationGrantedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub delegate: Pubkey,
+    pub capabilities: u8,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DelegationRevokedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub delegate: Pubkey,
+    pub timestamp: i64,
+}
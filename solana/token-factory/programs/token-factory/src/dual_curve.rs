@@ -0,0 +1,892 @@
+// Dual curve module for Crossify Token Factory
+// This file contains a second quote asset for a token's curve: both assets trade against the one
+// shared curve supply, with secondary prices converted from the primary curve at an attested rate
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as ix_sysvar;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use std::mem::size_of;
+
+use crate::{
+    bps_of, quote_bonding_curve, record_buy_milestones, record_curve_sell, record_referral, release_from_supply_vault,
+    require_allowed_quote_mint, require_feature_enabled, require_no_round_trip, require_no_trade_bridge_mix,
+    require_trading_enabled, BuyerRecord, CreatorFeesClaimedEvent, ReferralStats, TokenData, TokenFactory,
+    TokenFactoryError, TokenStats, ACCOUNT_RESERVED_LEN, ACCOUNT_VERSION, FEATURE_TRADING,
+};
+
+// Fixed-point scale of SecondaryCurve.rate
+#[constant]
+pub const SECONDARY_RATE_SCALE: u64 = 1_000_000_000;
+
+// Bounds on the spread secondary trades pay over the converted primary price. The spread is what
+// closes the arbitrage between the two assets while the attested rate lags the market
+pub const MIN_SECONDARY_SPREAD_BPS: u16 = 30;
+pub const MAX_SECONDARY_SPREAD_BPS: u16 = 1_000;
+
+#[derive(Accounts)]
+pub struct ConfigureSecondaryQuote<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    pub secondary_quote_mint: Account<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + size_of::<SecondaryCurve>(),
+        seeds = [b"secondary_curve", token_data.mint.as_ref()],
+        bump,
+    )]
+    pub secondary_curve: Account<'info, SecondaryCurve>,
+    
+    /// CHECK: PDA that owns the reserve and creator fee vaults; it holds no data
+    #[account(
+        seeds = [b"reserve_authority", token_data.mint.as_ref()],
+        bump = token_data.bonding_curve.reserve_authority_bump,
+    )]
+    pub reserve_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        token::mint = secondary_quote_mint,
+        token::authority = reserve_authority,
+        seeds = [b"secondary_reserve", token_data.mint.as_ref()],
+        bump,
+    )]
+    pub secondary_reserve_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        token::mint = secondary_quote_mint,
+        token::authority = reserve_authority,
+        seeds = [b"secondary_fee_vault", token_data.mint.as_ref()],
+        bump,
+    )]
+    pub secondary_fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    
+    /// CHECK: Factory allowlist entry for the secondary quote mint, checked in process
+    #[account(
+        seeds = [b"quote_mint", token_data.factory.as_ref(), secondary_quote_mint.key().as_ref()],
+        bump,
+    )]
+    pub allowed_quote_mint: UncheckedAccount<'info>,
+    
+    /// CHECK: TokenStats PDA of the secondary quote mint, which must not exist
+    #[account(seeds = [b"token_stats", secondary_quote_mint.key().as_ref()], bump)]
+    pub quote_token_stats: UncheckedAccount<'info>,
+}
+
+impl<'info> ConfigureSecondaryQuote<'info> {
+    // The quote mint is fixed on first configuration; later calls only tune the spread, the rate
+    // age limit and whether secondary trading is open
+    pub fn process(&mut self, bump: u8, spread_bps: u16, max_rate_age: i64, enabled: bool) -> Result<()> {
+        let token_data = &self.token_data;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_secondary_quote token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            self.authority.key(),
+            token_data.authority
+        );
+        require_with_context!(
+            token_data.bonding_curve.quote_mint != Pubkey::default(),
+            TokenFactoryError::ReserveNotInitialized,
+            "instruction=configure_secondary_quote token_id={}",
+            token_data.token_id
+        );
+        require_allowed_quote_mint(
+            &self.secondary_quote_mint,
+            &self.allowed_quote_mint,
+            &self.quote_token_stats,
+            "configure_secondary_quote",
+        )?;
+        let secondary_quote_mint = self.secondary_quote_mint.key();
+        let secondary_curve = &mut self.secondary_curve;
+        require_with_context!(
+            (MIN_SECONDARY_SPREAD_BPS..=MAX_SECONDARY_SPREAD_BPS).contains(&spread_bps)
+                && max_rate_age > 0
+                && secondary_quote_mint != token_data.bonding_curve.quote_mint
+                && (secondary_curve.mint == Pubkey::default() || secondary_curve.quote_mint == secondary_quote_mint),
+            TokenFactoryError::InvalidSecondaryQuote,
+            "instruction=configure_secondary_quote token_id={} quote_mint={} spread_bps={} max_rate_age={}",
+            token_data.token_id,
+            secondary_quote_mint,
+            spread_bps,
+            max_rate_age
+        );
+        
+        if secondary_curve.mint == Pubkey::default() {
+            secondary_curve.mint = token_data.mint;
+            secondary_curve.quote_mint = secondary_quote_mint;
+            secondary_curve.bump = bump;
+            secondary_curve.account_version = ACCOUNT_VERSION;
+        }
+        secondary_curve.spread_bps = spread_bps;
+        secondary_curve.max_rate_age = max_rate_age;
+        secondary_curve.enabled = enabled;
+        
+        emit!(SecondaryQuoteConfiguredEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            quote_mint: secondary_quote_mint,
+            spread_bps,
+            max_rate_age,
+            enabled,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdateSecondaryRate<'info> {
+    #[account(seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    #[account(
+        mut,
+        seeds = [b"secondary_curve", secondary_curve.mint.as_ref()],
+        bump = secondary_curve.bump,
+    )]
+    pub secondary_curve: Account<'info, SecondaryCurve>,
+    
+    // The factory's price attestor, which already signs prices for chains without Wormhole
+    #[account(address = token_factory.price_attestor @ TokenFactoryError::InvalidAuthority)]
+    pub price_attestor: Signer<'info>,
+}
+
+impl<'info> UpdateSecondaryRate<'info> {
+    // `rate` is secondary quote base units per primary quote base unit, scaled by SECONDARY_RATE_SCALE
+    pub fn process(&mut self, rate: u64) -> Result<()> {
+        require_with_context!(
+            rate > 0,
+            TokenFactoryError::InvalidSecondaryQuote,
+            "instruction=update_secondary_rate mint={} rate={}",
+            self.secondary_curve.mint,
+            rate
+        );
+        
+        let now = Clock::get()?.unix_timestamp;
+        let secondary_curve = &mut self.secondary_curve;
+        secondary_curve.rate = rate;
+        secondary_curve.rate_updated_at = now;
+        
+        emit!(SecondaryRateUpdatedEvent {
+            mint: secondary_curve.mint,
+            quote_mint: secondary_curve.quote_mint,
+            rate,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct BuyTokensSecondary<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_stats", token_data.mint.as_ref()],
+        bump = token_stats.bump,
+    )]
+    pub token_stats: Account<'info, TokenStats>,
+    
+    #[account(
+        mut,
+        seeds = [b"secondary_curve", token_data.mint.as_ref()],
+        bump = secondary_curve.bump,
+    )]
+    pub secondary_curve: Account<'info, SecondaryCurve>,
+    
+    #[account(mut, address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA mint authority, signs the mint CPI
+    #[account(
+        seeds = [b"mint_authority", mint.key().as_ref()],
+        bump = token_data.mint_authority_bump,
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"secondary_reserve", mint.key().as_ref()],
+        bump,
+    )]
+    pub secondary_reserve_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = secondary_curve.quote_mint, token::authority = buyer)]
+    pub buyer_quote_account: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = mint, token::authority = buyer)]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + size_of::<BuyerRecord>(),
+        seeds = [b"buyer_record", mint.key().as_ref(), buyer.key().as_ref()],
+        bump,
+    )]
+    pub buyer_record: Account<'info, BuyerRecord>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar, used to reject trades bundled with a bridge_out of this mint
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    
+    // Read for the factory's feature switches
+    #[account(address = token_data.factory, seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    // Referral tally, for this trade's quote mint, of the frontend that routed the trade, if any
+    #[account(mut)]
+    pub referral_stats: Option<Account<'info, ReferralStats>>,
+    
+    // Primary reserve, read with the secondary one to detect graduation
+    #[account(
+        seeds = [b"reserve", mint.key().as_ref()],
+        bump,
+    )]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    
+    // Required for tokens whose curve supply comes from the supply vault
+    #[account(
+        mut,
+        seeds = [b"supply_vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub supply_vault: Option<Account<'info, TokenAccount>>,
+}
+
+impl<'info> BuyTokensSecondary<'info> {
+    pub fn process(&mut self, amount: u64, max_cost: u64) -> Result<()> {
+        require_feature_enabled(&self.token_factory, FEATURE_TRADING, "buy_tokens_secondary")?;
+        let token_data = &self.token_data;
+        let curve_supply = self.token_stats.curve_supply;
+        let now = Clock::get()?.unix_timestamp;
+        
+        require_trading_enabled(token_data, "buy_tokens_secondary", amount)?;
+        require_no_trade_bridge_mix(&self.instructions, &token_data.mint, false, "buy_tokens_secondary")?;
+        require_no_round_trip(&self.instructions, token_data, true, "buy_tokens_secondary")?;
+        require_with_context!(
+            now >= token_data.bonding_curve.trading_start_ts,
+            TokenFactoryError::TradingNotStarted,
+            "instruction=buy_tokens_secondary token_id={} now={} trading_start_ts={}",
+            token_data.token_id,
+            now,
+            token_data.bonding_curve.trading_start_ts
+        );
+        // Refunds are paid from the primary reserve alone
+        require_with_context!(
+            !token_data.refund_policy.enabled,
+            TokenFactoryError::SecondaryQuoteUnavailable,
+            "instruction=buy_tokens_secondary token_id={} refundable launch",
+            token_data.token_id
+        );
+        self.secondary_curve.require_tradable(now, "buy_tokens_secondary")?;
+        
+        // Priced off the shared supply, so both assets always see the same point on the curve
+        let primary_cost = quote_bonding_curve(&token_data.bonding_curve, token_data.decimals, curve_supply, amount)?;
+        let cost = self
+            .secondary_curve
+            .convert(primary_cost, true)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        require_with_context!(
+            cost > 0,
+            TokenFactoryError::InvalidAmount,
+            "instruction=buy_tokens_secondary token_id={} amount={} rounds to zero cost",
+            token_data.token_id,
+            amount
+        );
+        require_with_context!(
+            cost <= max_cost,
+            TokenFactoryError::SlippageExceeded,
+            "instruction=buy_tokens_secondary token_id={} amount={} cost={} max_cost={}",
+            token_data.token_id,
+            amount,
+            cost,
+            max_cost
+        );
+        
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.buyer_quote_account.to_account_info(),
+                    to: self.secondary_reserve_vault.to_account_info(),
+                    authority: self.buyer.to_account_info(),
+                },
+            ),
+            cost,
+        )?;
+        
+        // Minted like a primary buy, or handed out of the supply vault
+        let mint_key = token_data.mint;
+        if token_data.supply_from_vault {
+            release_from_supply_vault(
+                token_data,
+                &self.token_program,
+                self.supply_vault.as_ref(),
+                self.mint_authority.to_account_info(),
+                self.buyer_token_account.to_account_info(),
+                amount,
+                "buy_tokens_secondary",
+            )?;
+        } else {
+            let bump = [token_data.mint_authority_bump];
+            let signer_seeds: &[&[&[u8]]] = &[&[b"mint_authority", mint_key.as_ref(), &bump]];
+            token::mint_to(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    token::MintTo {
+                        mint: self.mint.to_account_info(),
+                        to: self.buyer_token_account.to_account_info(),
+                        authority: self.mint_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+            )?;
+        }
+        
+        let token_stats = &mut self.token_stats;
+        token_stats.curve_supply = curve_supply
+            .checked_add(amount)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        token_stats.total_volume = token_stats
+            .total_volume
+            .checked_add(primary_cost)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        let secondary_curve = &mut self.secondary_curve;
+        secondary_curve.secondary_supply = secondary_curve
+            .secondary_supply
+            .checked_add(amount)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        secondary_curve.total_volume = secondary_curve.total_volume.saturating_add(cost);
+        
+        let buyer_record = &mut self.buyer_record;
+        if buyer_record.wallet == Pubkey::default() {
+            buyer_record.wallet = self.buyer.key();
+            buyer_record.mint = mint_key;
+            buyer_record.account_version = ACCOUNT_VERSION;
+        }
+        if buyer_record.net_bought == 0 {
+            token_stats.curve_holders = token_stats.curve_holders.saturating_add(1);
+        }
+        buyer_record.net_bought = buyer_record
+            .net_bought
+            .checked_add(amount)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        
+        // Graduation counts both reserves, the secondary one valued in the primary quote asset
+        self.secondary_reserve_vault.reload()?;
+        let reserve_balance = secondary_curve
+            .to_primary(self.secondary_reserve_vault.amount)
+            .ok_or(TokenFactoryError::MathOverflow)?
+            .saturating_add(self.reserve_vault.amount);
+        record_buy_milestones(&mut self.token_data, reserve_balance, self.buyer.key(), now);
+        
+        record_referral(
+            self.referral_stats.as_mut(),
+            &self.token_data,
+            secondary_curve.quote_mint,
+            cost,
+            0,
+            now,
+            "buy_tokens_secondary",
+        )?;
+        
+        emit!(SecondaryTokensBoughtEvent {
+            token_id: self.token_data.token_id,
+            mint: mint_key,
+            buyer: self.buyer.key(),
+            quote_mint: secondary_curve.quote_mint,
+            amount,
+            cost,
+            primary_cost,
+            curve_supply: token_stats.curve_supply,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SellTokensSecondary<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_stats", token_data.mint.as_ref()],
+        bump = token_stats.bump,
+    )]
+    pub token_stats: Account<'info, TokenStats>,
+    
+    #[account(
+        mut,
+        seeds = [b"secondary_curve", token_data.mint.as_ref()],
+        bump = secondary_curve.bump,
+    )]
+    pub secondary_curve: Account<'info, SecondaryCurve>,
+    
+    #[account(mut, address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA that owns the reserve and creator fee vaults; it holds no data
+    #[account(
+        seeds = [b"reserve_authority", mint.key().as_ref()],
+        bump = token_data.bonding_curve.reserve_authority_bump,
+    )]
+    pub reserve_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"secondary_reserve", mint.key().as_ref()],
+        bump,
+    )]
+    pub secondary_reserve_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"secondary_fee_vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub secondary_fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = mint, token::authority = seller)]
+    pub seller_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = secondary_curve.quote_mint, token::authority = seller)]
+    pub seller_quote_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = 8 + size_of::<BuyerRecord>(),
+        seeds = [b"buyer_record", mint.key().as_ref(), seller.key().as_ref()],
+        bump,
+    )]
+    pub buyer_record: Account<'info, BuyerRecord>,
+    
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar, used to reject trades bundled with a bridge_out of this mint
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    
+    // Read for the factory's feature switches
+    #[account(address = token_data.factory, seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    // Referral tally, for this trade's quote mint, of the frontend that routed the trade, if any
+    #[account(mut)]
+    pub referral_stats: Option<Account<'info, ReferralStats>>,
+    
+    // Required for tokens whose curve supply comes from the supply vault
+    #[account(
+        mut,
+        seeds = [b"supply_vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub supply_vault: Option<Account<'info, TokenAccount>>,
+}
+
+impl<'info> SellTokensSecondary<'info> {
+    pub fn process(&mut self, amount: u64, min_proceeds: u64) -> Result<()> {
+        require_feature_enabled(&self.token_factory, FEATURE_TRADING, "sell_tokens_secondary")?;
+        let token_data = &self.token_data;
+        let curve_supply = self.token_stats.curve_supply;
+        let now = Clock::get()?.unix_timestamp;
+        
+        require_trading_enabled(token_data, "sell_tokens_secondary", amount)?;
+        require_no_trade_bridge_mix(&self.instructions, &token_data.mint, false, "sell_tokens_secondary")?;
+        require_no_round_trip(&self.instructions, token_data, false, "sell_tokens_secondary")?;
+        self.secondary_curve.require_tradable(now, "sell_tokens_secondary")?;
+        
+        // Each reserve only backs the supply bought with its own asset, so a stale rate can never
+        // drain one asset with tokens paid for in the other
+        require_with_context!(
+            amount <= self.secondary_curve.secondary_supply && amount <= curve_supply,
+            TokenFactoryError::InsufficientCurveSupply,
+            "instruction=sell_tokens_secondary token_id={} amount={} secondary_supply={} curve_supply={}",
+            token_data.token_id,
+            amount,
+            self.secondary_curve.secondary_supply,
+            curve_supply
+        );
+        
+        let remaining_supply = curve_supply - amount;
+        let primary_proceeds = quote_bonding_curve(
+            &token_data.bonding_curve,
+            token_data.decimals,
+            remaining_supply,
+            amount,
+        )?;
+        let gross_proceeds = self
+            .secondary_curve
+            .convert(primary_proceeds, false)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        require_with_context!(
+            gross_proceeds <= self.secondary_reserve_vault.amount,
+            TokenFactoryError::SecondaryReserveShortfall,
+            "instruction=sell_tokens_secondary token_id={} amount={} gross_proceeds={} reserve_balance={}",
+            token_data.token_id,
+            amount,
+            gross_proceeds,
+            self.secondary_reserve_vault.amount
+        );
+        let sell_tax = bps_of(gross_proceeds, token_data.bonding_curve.sell_tax_bps)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        let proceeds = gross_proceeds - sell_tax;
+        require_with_context!(
+            proceeds >= min_proceeds,
+            TokenFactoryError::SlippageExceeded,
+            "instruction=sell_tokens_secondary token_id={} amount={} proceeds={} min_proceeds={}",
+            token_data.token_id,
+            amount,
+            proceeds,
+            min_proceeds
+        );
+        
+        let trader = self.seller.key();
+        let closes_position =
+            record_curve_sell(token_data, &mut self.buyer_record, trader, amount, now, "sell_tokens_secondary")?;
+        
+        // A fixed supply cannot be burned away, so sold tokens go back into the supply vault
+        if token_data.supply_from_vault {
+            let supply_vault = self
+                .supply_vault
+                .as_ref()
+                .ok_or(TokenFactoryError::SupplyVaultExhausted)?;
+            token::transfer(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    token::Transfer {
+                        from: self.seller_token_account.to_account_info(),
+                        to: supply_vault.to_account_info(),
+                        authority: self.seller.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+        } else {
+            token::burn(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    token::Burn {
+                        mint: self.mint.to_account_info(),
+                        from: self.seller_token_account.to_account_info(),
+                        authority: self.seller.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+        }
+        
+        let mint_key = token_data.mint;
+        let bump = [token_data.bonding_curve.reserve_authority_bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"reserve_authority", mint_key.as_ref(), &bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.secondary_reserve_vault.to_account_info(),
+                    to: self.seller_quote_account.to_account_info(),
+                    authority: self.reserve_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            proceeds,
+        )?;
+        if sell_tax > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    token::Transfer {
+                        from: self.secondary_reserve_vault.to_account_info(),
+                        to: self.secondary_fee_vault.to_account_info(),
+                        authority: self.reserve_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                sell_tax,
+            )?;
+        }
+
+        let token_stats = &mut self.token_stats;
+        token_stats.curve_supply = remaining_supply;
+        if closes_position {
+            token_stats.curve_holders = token_stats.curve_holders.saturating_sub(1);
+        }
+        token_stats.total_volume = token_stats
+            .total_volume
+            .checked_add(primary_proceeds)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        let secondary_curve = &mut self.secondary_curve;
+        secondary_curve.secondary_supply -= amount;
+        secondary_curve.total_volume = secondary_curve.total_volume.saturating_add(gross_proceeds);
+        
+        record_referral(
+            self.referral_stats.as_mut(),
+            &self.token_data,
+            secondary_curve.quote_mint,
+            gross_proceeds,
+            sell_tax,
+            now,
+            "sell_tokens_secondary",
+        )?;
+        
+        emit!(SecondaryTokensSoldEvent {
+            token_id: self.token_data.token_id,
+            mint: mint_key,
+            seller: trader,
+            quote_mint: secondary_curve.quote_mint,
+            amount,
+            proceeds,
+            sell_tax,
+            curve_supply: remaining_supply,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ClaimSecondaryCreatorFees<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    /// CHECK: PDA that owns the reserve and creator fee vaults; it holds no data
+    #[account(
+        seeds = [b"reserve_authority", token_data.mint.as_ref()],
+        bump = token_data.bonding_curve.reserve_authority_bump,
+    )]
+    pub reserve_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"secondary_fee_vault", token_data.mint.as_ref()],
+        bump,
+    )]
+    pub secondary_fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = secondary_fee_vault.mint)]
+    pub destination: Account<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClaimSecondaryCreatorFees<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let token_data = &self.token_data;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=claim_secondary_creator_fees token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            self.authority.key(),
+            token_data.authority
+        );
+        let amount = self.secondary_fee_vault.amount;
+        require_with_context!(
+            amount > 0,
+            TokenFactoryError::NothingToClaim,
+            "instruction=claim_secondary_creator_fees token_id={}",
+            token_data.token_id
+        );
+        
+        let mint_key = token_data.mint;
+        let bump = [token_data.bonding_curve.reserve_authority_bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"reserve_authority", mint_key.as_ref(), &bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.secondary_fee_vault.to_account_info(),
+                    to: self.destination.to_account_info(),
+                    authority: self.reserve_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+        
+        emit!(CreatorFeesClaimedEvent {
+            token_id: token_data.token_id,
+            mint: mint_key,
+            destination: self.destination.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+// Tokens of `mint` bought with the secondary asset and not yet sold back, 0 when the token has no
+// secondary quote. Primary sells may not dip into them
+pub fn secondary_supply(secondary_curve: &AccountInfo) -> Result<u64> {
+    if secondary_curve.data_is_empty() {
+        return Ok(0);
+    }
+    let secondary_curve: Account<SecondaryCurve> = Account::try_from(secondary_curve)?;
+    Ok(secondary_curve.secondary_supply)
+}
+
+/// Second quote asset of a token's curve, priced from the primary curve at an attested rate
+#[account]
+pub struct SecondaryCurve {
+    pub mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub rate: u64, // Secondary quote base units per primary quote base unit, scaled by SECONDARY_RATE_SCALE
+    pub rate_updated_at: i64,
+    pub max_rate_age: i64, // Trading pauses once the rate is older than this many seconds
+    pub spread_bps: u16,   // Added to secondary buy prices and taken off secondary sell proceeds
+    pub enabled: bool,
+    pub secondary_supply: u64, // Shared curve supply bought with this asset and not sold back
+    pub total_volume: u64,     // Secondary quote tokens traded; sell tax stays in this asset
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+impl SecondaryCurve {
+    fn require_tradable(&self, now: i64, instruction: &str) -> Result<()> {
+        require_with_context!(
+            self.enabled && self.rate > 0,
+            TokenFactoryError::SecondaryQuoteUnavailable,
+            "instruction={} mint={} enabled={} rate={}",
+            instruction,
+            self.mint,
+            self.enabled,
+            self.rate
+        );
+        let age = now.saturating_sub(self.rate_updated_at);
+        require_with_context!(
+            age <= self.max_rate_age,
+            TokenFactoryError::SecondaryQuoteUnavailable,
+            "instruction={} mint={} rate_age={} max_rate_age={}",
+            instruction,
+            self.mint,
+            age,
+            self.max_rate_age
+        );
+        Ok(())
+    }
+
+    // Secondary quote tokens the secondary reserve must hold to buy back all supply bought with this
+    // asset, sold from the current supply down at the current rate
+    pub fn required_reserve(&self, token_data: &TokenData, curve_supply: u64) -> Result<u64> {
+        let amount = self.secondary_supply.min(curve_supply);
+        let primary_proceeds = quote_bonding_curve(
+            &token_data.bonding_curve,
+            token_data.decimals,
+            curve_supply - amount,
+            amount,
+        )?;
+        self.convert(primary_proceeds, false)
+            .ok_or_else(|| TokenFactoryError::MathOverflow.into())
+    }
+
+    // Primary quote value of `secondary_amount` at the attested rate, without the spread
+    fn to_primary(&self, secondary_amount: u64) -> Option<u64> {
+        let amount = (secondary_amount as u128)
+            .checked_mul(SECONDARY_RATE_SCALE as u128)?
+            .checked_div(self.rate as u128)?;
+        u64::try_from(amount).ok()
+    }
+
+    // Secondary amount for a primary quote amount, with the spread charged against the trader
+    fn convert(&self, primary_amount: u64, is_buy: bool) -> Option<u64> {
+        let spread_factor = if is_buy {
+            10_000 + self.spread_bps as u128
+        } else {
+            10_000 - self.spread_bps as u128
+        };
+        let numerator = (primary_amount as u128)
+            .checked_mul(self.rate as u128)?
+            .checked_mul(spread_factor)?;
+        let denominator = SECONDARY_RATE_SCALE as u128 * 10_000;
+        let mut amount = numerator / denominator;
+        if is_buy && numerator % denominator != 0 {
+            amount += 1;
+        }
+        u64::try_from(amount).ok()
+    }
+}
+
+#[event]
+pub struct SecondaryQuoteConfiguredEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub spread_bps: u16,
+    pub max_rate_age: i64,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SecondaryRateUpdatedEvent {
+    pub mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub rate: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SecondaryTokensBoughtEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub buyer: Pubkey,
+    pub quote_mint: Pubkey,
+    pub amount: u64,
+    pub cost: u64,         // In the secondary asset
+    pub primary_cost: u64, // What the same buy costs in the primary asset, before the spread
+    pub curve_supply: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SecondaryTokensSoldEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub seller: Pubkey,
+    pub quote_mint: Pubkey,
+    pub amount: u64,
+    pub proceeds: u64,
+    pub sell_tax: u64,
+    pub curve_supply: u64,
+    pub timestamp: i64,
+}
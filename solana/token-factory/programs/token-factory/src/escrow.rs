@@ -0,0 +1,375 @@
+// Escrow module for Crossify Token Factory
+// This file contains fixed-amount OTC swaps between tokens created by the same factory
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use std::mem::size_of;
+
+use crate::{bps_of, TokenData, TokenFactory, TokenFactoryError, ACCOUNT_RESERVED_LEN, ACCOUNT_VERSION};
+
+#[derive(Accounts)]
+#[instruction(offer_id: u64)]
+pub struct CreateOffer<'info> {
+    #[account(seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    #[account(
+        constraint = offer_token_data.factory == token_factory.key() @ TokenFactoryError::ForeignToken,
+    )]
+    pub offer_token_data: Account<'info, TokenData>,
+    
+    #[account(
+        constraint = request_token_data.factory == token_factory.key() @ TokenFactoryError::ForeignToken,
+    )]
+    pub request_token_data: Account<'info, TokenData>,
+    
+    #[account(address = offer_token_data.mint)]
+    pub offer_mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = maker,
+        space = 8 + size_of::<Offer>(),
+        seeds = [b"offer", maker.key().as_ref(), &offer_id.to_le_bytes()],
+        bump,
+    )]
+    pub offer: Account<'info, Offer>,
+    
+    #[account(
+        init,
+        payer = maker,
+        seeds = [b"offer_vault", offer.key().as_ref()],
+        bump,
+        token::mint = offer_mint,
+        token::authority = offer,
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = offer_mint,
+        token::authority = maker,
+    )]
+    pub maker_offer_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> CreateOffer<'info> {
+    pub fn process(&mut self, bump: u8, offer_id: u64, offer_amount: u64, request_amount: u64) -> Result<()> {
+        require_with_context!(
+            offer_amount > 0 && request_amount > 0,
+            TokenFactoryError::InvalidAmount,
+            "instruction=create_offer offer_token_id={} request_token_id={} offer_amount={} request_amount={}",
+            self.offer_token_data.token_id,
+            self.request_token_data.token_id,
+            offer_amount,
+            request_amount
+        );
+        require_with_context!(
+            self.offer_token_data.mint != self.request_token_data.mint,
+            TokenFactoryError::InvalidOffer,
+            "instruction=create_offer token_id={} offering and requesting the same token",
+            self.offer_token_data.token_id
+        );
+        
+        // Lock the offered tokens in escrow
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.maker_offer_account.to_account_info(),
+                    to: self.escrow_vault.to_account_info(),
+                    authority: self.maker.to_account_info(),
+                },
+            ),
+            offer_amount,
+        )?;
+        
+        let offer = &mut self.offer;
+        offer.factory = self.token_factory.key();
+        offer.maker = self.maker.key();
+        offer.offer_id = offer_id;
+        offer.offer_mint = self.offer_token_data.mint;
+        offer.offer_amount = offer_amount;
+        offer.request_mint = self.request_token_data.mint;
+        offer.request_amount = request_amount;
+        offer.escrow_vault = self.escrow_vault.key();
+        offer.bump = bump;
+        offer.account_version = ACCOUNT_VERSION;
+        
+        emit!(OfferCreatedEvent {
+            offer: offer.key(),
+            maker: offer.maker,
+            offer_mint: offer.offer_mint,
+            offer_amount,
+            request_mint: offer.request_mint,
+            request_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct AcceptOffer<'info> {
+    #[account(
+        mut,
+        has_one = maker,
+        has_one = escrow_vault,
+        close = maker,
+    )]
+    pub offer: Account<'info, Offer>,
+    
+    #[account(address = offer.factory, seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    #[account(mut)]
+    pub escrow_vault: Account<'info, TokenAccount>,
+    
+    /// CHECK: Receives the closed accounts' rent; checked against `offer.maker`
+    #[account(mut)]
+    pub maker: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        token::mint = offer.request_mint,
+        token::authority = maker,
+    )]
+    pub maker_request_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = offer.request_mint,
+        token::authority = taker,
+    )]
+    pub taker_request_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = offer.offer_mint,
+        token::authority = taker,
+    )]
+    pub taker_offer_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = offer.offer_mint,
+        constraint = treasury_offer_account.owner == token_factory.treasury @ TokenFactoryError::InvalidTreasury,
+    )]
+    pub treasury_offer_account: Account<'info, TokenAccount>,
+    
+    pub taker: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> AcceptOffer<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let offer = &self.offer;
+        let fee = bps_of(offer.offer_amount, self.token_factory.otc_fee_bps)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        let taker_amount = offer.offer_amount - fee;
+        
+        // Taker pays the maker in full
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.taker_request_account.to_account_info(),
+                    to: self.maker_request_account.to_account_info(),
+                    authority: self.taker.to_account_info(),
+                },
+            ),
+            offer.request_amount,
+        )?;
+        
+        // Escrow releases the offered tokens, minus the treasury fee
+        let maker_key = offer.maker;
+        let offer_id = offer.offer_id.to_le_bytes();
+        let bump = [offer.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"offer", maker_key.as_ref(), &offer_id, &bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.escrow_vault.to_account_info(),
+                    to: self.taker_offer_account.to_account_info(),
+                    authority: self.offer.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            taker_amount,
+        )?;
+        if fee > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    token::Transfer {
+                        from: self.escrow_vault.to_account_info(),
+                        to: self.treasury_offer_account.to_account_info(),
+                        authority: self.offer.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                fee,
+            )?;
+        }
+
+        close_escrow_vault(
+            &self.token_program,
+            &self.escrow_vault,
+            &self.maker.to_account_info(),
+            &self.offer.to_account_info(),
+            signer_seeds,
+        )?;
+        
+        emit!(OfferAcceptedEvent {
+            offer: self.offer.key(),
+            maker: maker_key,
+            taker: self.taker.key(),
+            offer_amount: self.offer.offer_amount,
+            request_amount: self.offer.request_amount,
+            fee,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CancelOffer<'info> {
+    #[account(
+        mut,
+        has_one = maker,
+        has_one = escrow_vault,
+        close = maker,
+    )]
+    pub offer: Account<'info, Offer>,
+    
+    #[account(mut)]
+    pub escrow_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = offer.offer_mint,
+        token::authority = maker,
+    )]
+    pub maker_offer_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> CancelOffer<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let offer = &self.offer;
+        let maker_key = offer.maker;
+        let offer_id = offer.offer_id.to_le_bytes();
+        let bump = [offer.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"offer", maker_key.as_ref(), &offer_id, &bump]];
+        
+        // Return the escrowed tokens to the maker
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.escrow_vault.to_account_info(),
+                    to: self.maker_offer_account.to_account_info(),
+                    authority: self.offer.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            self.escrow_vault.amount,
+        )?;
+        
+        close_escrow_vault(
+            &self.token_program,
+            &self.escrow_vault,
+            &self.maker.to_account_info(),
+            &self.offer.to_account_info(),
+            signer_seeds,
+        )?;
+        
+        emit!(OfferCancelledEvent {
+            offer: self.offer.key(),
+            maker: maker_key,
+            offer_amount: self.offer.offer_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+fn close_escrow_vault<'info>(
+    token_program: &Program<'info, Token>,
+    escrow_vault: &Account<'info, TokenAccount>,
+    destination: &AccountInfo<'info>,
+    offer: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    token::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        token::CloseAccount {
+            account: escrow_vault.to_account_info(),
+            destination: destination.clone(),
+            authority: offer.clone(),
+        },
+        signer_seeds,
+    ))
+}
+
+#[account]
+pub struct Offer {
+    pub factory: Pubkey,
+    pub maker: Pubkey,
+    pub offer_id: u64,
+    pub offer_mint: Pubkey,
+    pub offer_amount: u64,
+    pub request_mint: Pubkey,
+    pub request_amount: u64,
+    pub escrow_vault: Pubkey,
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+#[event]
+pub struct OfferCreatedEvent {
+    pub offer: Pubkey,
+    pub maker: Pubkey,
+    pub offer_mint: Pubkey,
+    pub offer_amount: u64,
+    pub request_mint: Pubkey,
+    pub request_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OfferAcceptedEvent {
+    pub offer: Pubkey,
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub offer_amount: u64,
+    pub request_amount: u64,
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OfferCancelledEvent {
+    pub offer: Pubkey,
+    pub maker: Pubkey,
+    pub offer_amount: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,213 @@
+// Factory shard module for Crossify Token Factory
+// This file contains sequential token ids drawn from per-shard counters, so concurrent launches
+// do not all write the single TokenFactory account
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use std::mem::size_of;
+
+use crate::{
+    bind_symbol_registry, emit_digest, init_token_data, init_token_stats, require_collection_member, SymbolRegistry,
+    TokenCreatedEvent, TokenData, TokenFactory, TokenFactoryError, TokenStats, ACCOUNT_RESERVED_LEN, ACCOUNT_VERSION,
+    DIGEST_ACTION_CREATED,
+};
+
+// Counters the factory's sequential ids are spread over
+#[constant]
+pub const FACTORY_SHARD_COUNT: u8 = 16;
+
+// Sharded ids have the second-highest bit set, apart from create_token's and derived ids
+pub const SHARDED_TOKEN_ID_FLAG: u64 = 1 << 62;
+
+#[derive(Accounts)]
+#[instruction(shard: u8, name: String, symbol: String, decimals: u8, metadata_uri: String)]
+pub struct CreateTokenSharded<'info> {
+    // Read-only; the shard carries the counter instead
+    #[account(seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + size_of::<FactoryShard>(),
+        seeds = [b"factory_shard", token_factory.key().as_ref(), &[shard]],
+        bump,
+    )]
+    pub factory_shard: Account<'info, FactoryShard>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = TokenData::space_for(name.len(), symbol.len(), metadata_uri.len()),
+    )]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = decimals,
+        mint::authority = authority.key(),
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + size_of::<TokenStats>(),
+        seeds = [b"token_stats", mint.key().as_ref()],
+        bump,
+    )]
+    pub token_stats: Account<'info, TokenStats>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = authority,
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+    
+    // Creator's claim on the symbol; mandatory when the factory requires unique symbols
+    #[account(mut)]
+    pub symbol_registry: Option<Account<'info, SymbolRegistry>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    
+    // An NFT of the factory's required collection held by the creator, and its Metaplex metadata;
+    // mandatory while creation is gated
+    pub collection_nft_account: Option<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Address, owner and contents are checked against collection_nft_account in process
+    pub collection_nft_metadata: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> CreateTokenSharded<'info> {
+    // Same as create_token, except the id comes from the creator's shard and the token is not added
+    // to the paged index, which follows the factory's token_count; indexers pick it up from the event
+    #[allow(clippy::too_many_arguments)]
+    pub fn process(
+        &mut self,
+        shard_bump: u8,
+        token_stats_bump: u8,
+        shard: u8,
+        name: String,
+        symbol: String,
+        decimals: u8,
+        metadata_uri: String,
+        initial_supply: u64,
+    ) -> Result<()> {
+        let token_factory = &self.token_factory;
+        let token_data = &mut self.token_data;
+        let mint = self.mint.key();
+        let authority = self.authority.key();
+        
+        // Spreading creators by key keeps any one shard from becoming the new hot account
+        let expected_shard = shard_for_creator(&authority);
+        require_with_context!(
+            shard == expected_shard,
+            TokenFactoryError::InvalidFactoryShard,
+            "instruction=create_token_sharded creator={} shard={} expected_shard={}",
+            authority,
+            shard,
+            expected_shard
+        );
+        let factory_shard = &mut self.factory_shard;
+        if factory_shard.factory == Pubkey::default() {
+            factory_shard.factory = token_factory.key();
+            factory_shard.shard = shard;
+            factory_shard.bump = shard_bump;
+            factory_shard.account_version = ACCOUNT_VERSION;
+        }
+        let token_id = sharded_token_id(shard, factory_shard.token_count)?;
+        factory_shard.token_count = factory_shard
+            .token_count
+            .checked_add(1)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        
+        init_token_data(
+            token_data,
+            token_factory.key(),
+            mint,
+            token_id,
+            name,
+            symbol,
+            decimals,
+            metadata_uri,
+            authority,
+            initial_supply,
+            Clock::get()?.slot,
+        );
+        init_token_stats(&mut self.token_stats, mint, token_data.token_id, token_stats_bump);
+        bind_symbol_registry(
+            &token_factory.key(),
+            self.symbol_registry.as_mut(),
+            token_factory.require_unique_symbols,
+            &token_data.symbol,
+            &authority,
+            &mint,
+        )?;
+        require_collection_member(
+            &token_factory.required_collection,
+            self.collection_nft_account.as_ref(),
+            self.collection_nft_metadata.as_ref().map(|metadata| metadata.as_ref()),
+            &authority,
+        )?;
+        
+        token::mint_to(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::MintTo {
+                    mint: self.mint.to_account_info(),
+                    to: self.token_account.to_account_info(),
+                    authority: self.authority.to_account_info(),
+                },
+            ),
+            initial_supply,
+        )?;
+        
+        let now = Clock::get()?.unix_timestamp;
+        emit!(TokenCreatedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            name: token_data.name.clone(),
+            symbol: token_data.symbol.clone(),
+            decimals: token_data.decimals,
+            initial_supply: token_data.initial_supply,
+            timestamp: now,
+        });
+        emit_digest(DIGEST_ACTION_CREATED, token_data.mint, authority, token_data.initial_supply, now);
+        
+        Ok(())
+    }
+}
+
+/// One slice of the factory's sequential id space
+#[account]
+pub struct FactoryShard {
+    pub factory: Pubkey,
+    pub shard: u8,
+    pub token_count: u64, // Tokens created through this shard
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+// Shard a creator must use: first byte of keccak(creator), modulo FACTORY_SHARD_COUNT
+pub fn shard_for_creator(creator: &Pubkey) -> u8 {
+    keccak::hash(creator.as_ref()).0[0] % FACTORY_SHARD_COUNT
+}
+
+// Ids interleave across shards: the n-th token of shard s gets n * FACTORY_SHARD_COUNT + s
+pub fn sharded_token_id(shard: u8, shard_token_count: u64) -> Result<u64> {
+    let sequence = shard_token_count
+        .checked_mul(FACTORY_SHARD_COUNT as u64)
+        .and_then(|sequence| sequence.checked_add(shard as u64))
+        .filter(|sequence| *sequence < SHARDED_TOKEN_ID_FLAG)
+        .ok_or(TokenFactoryError::MathOverflow)?;
+    Ok(sequence | SHARDED_TOKEN_ID_FLAG)
+}
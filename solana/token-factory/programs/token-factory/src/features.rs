@@ -0,0 +1,40 @@
+// Features module for Crossify Token Factory
+// This file contains the factory-wide switches that take whole subsystems offline without a
+// redeploy
+
+use anchor_lang::prelude::*;
+
+use crate::{TokenFactory, TokenFactoryError};
+
+// Bits of TokenFactory.disabled_features. Bits are cleared by default, so factories created
+// before the switches existed keep every subsystem running
+#[constant]
+pub const FEATURE_TRADING: u64 = 1 << 0; // Curve buys and sells, in either quote asset or from other chains
+#[constant]
+pub const FEATURE_BRIDGING: u64 = 1 << 1; // Token transfers and remote trades between chains
+#[constant]
+pub const FEATURE_PRESALES: u64 = 1 << 2; // Starting and bidding in Dutch auctions
+#[constant]
+pub const FEATURE_GOVERNANCE: u64 = 1 << 3; // Creating, voting on and executing proposals
+
+pub const ALL_FEATURES: u64 = FEATURE_TRADING | FEATURE_BRIDGING | FEATURE_PRESALES | FEATURE_GOVERNANCE;
+
+// Checked first thing in every instruction of a switchable subsystem. Exits such as refunds,
+// auction settlement and fee claims are never switched off, so funds cannot be stranded
+pub fn require_feature_enabled(token_factory: &TokenFactory, feature: u64, instruction: &str) -> Result<()> {
+    require_with_context!(
+        token_factory.disabled_features & feature == 0,
+        TokenFactoryError::FeatureDisabled,
+        "instruction={} feature={:#x} disabled_features={:#x}",
+        instruction,
+        feature,
+        token_factory.disabled_features
+    );
+    Ok(())
+}
+
+#[event]
+pub struct FeaturesConfiguredEvent {
+    pub disabled_features: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,673 @@
+// Governance module for Crossify Token Factory
+// This file contains token-weighted proposals, voted on with tokens held in escrow until voting closes
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use std::mem::size_of;
+
+use crate::admin_log::{
+    admin_value, record_admin_action, ADMIN_ACTION_GOVERNANCE_CURVE, ADMIN_ACTION_GOVERNANCE_TREASURY_SPEND,
+};
+use crate::{
+    require_curve_parameters_mutable, require_feature_enabled, TokenData, TokenFactory, TokenFactoryError, TokenStats,
+    ACCOUNT_RESERVED_LEN, ACCOUNT_VERSION, FEATURE_GOVERNANCE,
+};
+
+#[derive(Accounts)]
+pub struct ConfigureGovernance<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA that owns the governance treasury; it holds no data
+    #[account(seeds = [b"governance", mint.key().as_ref()], bump)]
+    pub governance_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"governance_treasury", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = governance_authority,
+    )]
+    pub governance_treasury: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> ConfigureGovernance<'info> {
+    pub fn process(
+        &mut self,
+        bump: u8,
+        voting_period: i64,
+        timelock_delay: i64,
+        quorum_votes: u64,
+        proposal_threshold: u64,
+    ) -> Result<()> {
+        let token_data = &mut self.token_data;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_governance token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            self.authority.key(),
+            token_data.authority
+        );
+        require_with_context!(
+            voting_period > 0 && timelock_delay >= 0,
+            TokenFactoryError::InvalidGovernanceConfig,
+            "instruction=configure_governance token_id={} voting_period={} timelock_delay={}",
+            token_data.token_id,
+            voting_period,
+            timelock_delay
+        );
+        
+        // Handing control to holders is one-way: the governance PDA becomes the token's authority,
+        // so every creator-only instruction, snapshots included, is closed to the creator from here
+        token_data.authority = self.governance_authority.key();
+        token_data.governance = GovernanceConfig {
+            enabled: true,
+            voting_period,
+            timelock_delay,
+            quorum_votes,
+            proposal_threshold,
+            bump,
+        };
+        
+        emit!(GovernanceConfiguredEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            voting_period,
+            timelock_delay,
+            quorum_votes,
+            proposal_threshold,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_stats", token_data.mint.as_ref()],
+        bump = token_stats.bump,
+    )]
+    pub token_stats: Account<'info, TokenStats>,
+    
+    // Holds the proposer's voting power
+    #[account(token::mint = token_data.mint, token::authority = proposer)]
+    pub proposer_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + size_of::<Proposal>(),
+        seeds = [b"proposal", token_data.mint.as_ref(), &token_stats.proposal_count.to_le_bytes()],
+        bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+    
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    // Read for the factory's feature switches
+    #[account(address = token_data.factory, seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+}
+
+impl<'info> CreateProposal<'info> {
+    pub fn process(&mut self, bump: u8, action: ProposalAction) -> Result<()> {
+        require_feature_enabled(&self.token_factory, FEATURE_GOVERNANCE, "create_proposal")?;
+        let token_data = &self.token_data;
+        let governance = &token_data.governance;
+        let proposer = self.proposer.key();
+        
+        require_with_context!(
+            governance.enabled,
+            TokenFactoryError::GovernanceNotEnabled,
+            "instruction=create_proposal token_id={}",
+            token_data.token_id
+        );
+        
+        // Proposers need enough tokens on hand; votes are what lock them
+        let balance = self.proposer_token_account.amount;
+        require_with_context!(
+            balance >= governance.proposal_threshold,
+            TokenFactoryError::InsufficientVotingPower,
+            "instruction=create_proposal token_id={} balance={} proposal_threshold={}",
+            token_data.token_id,
+            balance,
+            governance.proposal_threshold
+        );
+        
+        if let ProposalAction::CurveParameters { curve_type, reserve_ratio, .. } = action {
+            require_with_context!(
+                curve_type <= 2 && reserve_ratio <= 1000,
+                TokenFactoryError::InvalidCurveType,
+                "instruction=create_proposal token_id={} curve_type={} reserve_ratio={}",
+                token_data.token_id,
+                curve_type,
+                reserve_ratio
+            );
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let voting_ends_at = now
+            .checked_add(governance.voting_period)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        let execute_after = voting_ends_at
+            .checked_add(governance.timelock_delay)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        
+        let proposal = &mut self.proposal;
+        proposal.mint = token_data.mint;
+        proposal.proposal_id = self.token_stats.proposal_count;
+        proposal.proposer = proposer;
+        proposal.action = action;
+        proposal.voting_ends_at = voting_ends_at;
+        proposal.execute_after = execute_after;
+        proposal.yes_votes = 0;
+        proposal.no_votes = 0;
+        proposal.executed = false;
+        proposal.bump = bump;
+        proposal.account_version = ACCOUNT_VERSION;
+        
+        self.token_stats.proposal_count = self
+            .token_stats
+            .proposal_count
+            .checked_add(1)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        
+        emit!(ProposalCreatedEvent {
+            token_id: token_data.token_id,
+            mint: proposal.mint,
+            proposal_id: proposal.proposal_id,
+            proposer,
+            voting_ends_at,
+            execute_after,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(constraint = token_data.mint == proposal.mint @ TokenFactoryError::InvalidProposal)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+    
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + size_of::<VoteRecord>(),
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump,
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    
+    #[account(address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA that owns the vote escrows; it holds no data
+    #[account(
+        seeds = [b"governance", mint.key().as_ref()],
+        bump = token_data.governance.bump,
+    )]
+    pub governance_authority: UncheckedAccount<'info>,
+    
+    // Holds the vote's tokens until voting closes, so they cannot be moved and voted again
+    #[account(
+        init,
+        payer = voter,
+        seeds = [b"vote_escrow", vote_record.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = governance_authority,
+    )]
+    pub vote_escrow: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = mint, token::authority = voter)]
+    pub voter_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    
+    // Read for the factory's feature switches
+    #[account(address = token_data.factory, seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+}
+
+impl<'info> CastVote<'info> {
+    // Voting power is the `amount` of tokens the voter locks into the vote's escrow
+    pub fn process(&mut self, support: bool, amount: u64) -> Result<()> {
+        require_feature_enabled(&self.token_factory, FEATURE_GOVERNANCE, "cast_vote")?;
+        let proposal = &mut self.proposal;
+        let voter = self.voter.key();
+        let now = Clock::get()?.unix_timestamp;
+        
+        require_with_context!(
+            now < proposal.voting_ends_at,
+            TokenFactoryError::VotingClosed,
+            "instruction=cast_vote proposal_id={} now={} voting_ends_at={}",
+            proposal.proposal_id,
+            now,
+            proposal.voting_ends_at
+        );
+        require_with_context!(
+            amount > 0,
+            TokenFactoryError::InvalidAmount,
+            "instruction=cast_vote proposal_id={} voter={} amount={}",
+            proposal.proposal_id,
+            voter,
+            amount
+        );
+        
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.voter_token_account.to_account_info(),
+                    to: self.vote_escrow.to_account_info(),
+                    authority: self.voter.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        if support {
+            proposal.yes_votes = proposal
+                .yes_votes
+                .checked_add(amount)
+                .ok_or(TokenFactoryError::MathOverflow)?;
+        } else {
+            proposal.no_votes = proposal
+                .no_votes
+                .checked_add(amount)
+                .ok_or(TokenFactoryError::MathOverflow)?;
+        }
+
+        let vote_record = &mut self.vote_record;
+        vote_record.proposal = proposal.key();
+        vote_record.voter = voter;
+        vote_record.account_version = ACCOUNT_VERSION;
+        vote_record.support = support;
+        vote_record.weight = amount;
+        
+        emit!(VoteCastEvent {
+            mint: proposal.mint,
+            proposal_id: proposal.proposal_id,
+            voter,
+            support,
+            weight: amount,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ReclaimVoteTokens<'info> {
+    #[account(constraint = token_data.mint == proposal.mint @ TokenFactoryError::InvalidProposal)]
+    pub token_data: Account<'info, TokenData>,
+    
+    pub proposal: Account<'info, Proposal>,
+    
+    #[account(has_one = proposal, has_one = voter)]
+    pub vote_record: Account<'info, VoteRecord>,
+    
+    /// CHECK: PDA that owns the vote escrows; it holds no data
+    #[account(
+        seeds = [b"governance", token_data.mint.as_ref()],
+        bump = token_data.governance.bump,
+    )]
+    pub governance_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"vote_escrow", vote_record.key().as_ref()],
+        bump,
+    )]
+    pub vote_escrow: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = token_data.mint, token::authority = voter)]
+    pub voter_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ReclaimVoteTokens<'info> {
+    // The vote record stays behind, so the voter cannot vote on the proposal again
+    pub fn process(&mut self) -> Result<()> {
+        let proposal = &self.proposal;
+        let now = Clock::get()?.unix_timestamp;
+        require_with_context!(
+            now >= proposal.voting_ends_at,
+            TokenFactoryError::VotingNotClosed,
+            "instruction=reclaim_vote_tokens proposal_id={} now={} voting_ends_at={}",
+            proposal.proposal_id,
+            now,
+            proposal.voting_ends_at
+        );
+        
+        let mint_key = self.token_data.mint;
+        let bump = [self.token_data.governance.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"governance", mint_key.as_ref(), &bump]];
+        let amount = self.vote_escrow.amount;
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.vote_escrow.to_account_info(),
+                    to: self.voter_token_account.to_account_info(),
+                    authority: self.governance_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+        token::close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            token::CloseAccount {
+                account: self.vote_escrow.to_account_info(),
+                destination: self.voter.to_account_info(),
+                authority: self.governance_authority.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+        
+        emit!(VoteTokensReclaimedEvent {
+            mint: mint_key,
+            proposal_id: proposal.proposal_id,
+            voter: self.voter.key(),
+            amount,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        mut,
+        constraint = proposal.mint == token_data.mint @ TokenFactoryError::InvalidProposal,
+    )]
+    pub proposal: Account<'info, Proposal>,
+    
+    /// CHECK: PDA that owns the governance treasury; it holds no data
+    #[account(
+        seeds = [b"governance", token_data.mint.as_ref()],
+        bump = token_data.governance.bump,
+    )]
+    pub governance_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"governance_treasury", token_data.mint.as_ref()],
+        bump,
+    )]
+    pub governance_treasury: Account<'info, TokenAccount>,
+    
+    // Only used by treasury spends, where it must belong to the proposal's recipient
+    #[account(mut, token::mint = token_data.mint)]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    // Read for the factory's feature switches
+    #[account(address = token_data.factory, seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    /// CHECK: Factory admin log, checked against its derived address; skipped until opened
+    #[account(mut, seeds = [b"admin_log", token_factory.key().as_ref()], bump)]
+    pub admin_log: UncheckedAccount<'info>,
+    
+    // Read so curve parameter changes respect the same freeze as configure_bonding_curve
+    #[account(
+        seeds = [b"token_stats", token_data.mint.as_ref()],
+        bump = token_stats.bump,
+    )]
+    pub token_stats: Account<'info, TokenStats>,
+}
+
+impl<'info> ExecuteProposal<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        require_feature_enabled(&self.token_factory, FEATURE_GOVERNANCE, "execute_proposal")?;
+        let proposal = &self.proposal;
+        let governance = &self.token_data.governance;
+        let now = Clock::get()?.unix_timestamp;
+        
+        require_with_context!(
+            !proposal.executed,
+            TokenFactoryError::ProposalAlreadyExecuted,
+            "instruction=execute_proposal proposal_id={}",
+            proposal.proposal_id
+        );
+        // The timelock starts when voting closes
+        require_with_context!(
+            now >= proposal.execute_after,
+            TokenFactoryError::TimelockNotElapsed,
+            "instruction=execute_proposal proposal_id={} now={} execute_after={}",
+            proposal.proposal_id,
+            now,
+            proposal.execute_after
+        );
+        require_with_context!(
+            proposal.yes_votes > proposal.no_votes && proposal.yes_votes >= governance.quorum_votes,
+            TokenFactoryError::ProposalNotPassed,
+            "instruction=execute_proposal proposal_id={} yes_votes={} no_votes={} quorum_votes={}",
+            proposal.proposal_id,
+            proposal.yes_votes,
+            proposal.no_votes,
+            governance.quorum_votes
+        );
+        
+        match proposal.action {
+            ProposalAction::CurveParameters { curve_type, base_price, slope, reserve_ratio } => {
+                require_curve_parameters_mutable(&self.token_data, self.token_stats.curve_supply, "execute_proposal")?;
+                record_admin_action(
+                    &self.admin_log,
+                    self.proposal.key(),
+                    ADMIN_ACTION_GOVERNANCE_CURVE,
+                    0,
+                    self.token_data.mint,
+                    admin_value(&[
+                        self.token_data.bonding_curve.curve_type as u64,
+                        self.token_data.bonding_curve.base_price,
+                        self.token_data.bonding_curve.slope,
+                        self.token_data.bonding_curve.reserve_ratio as u64,
+                    ]),
+                    admin_value(&[curve_type as u64, base_price, slope, reserve_ratio as u64]),
+                )?;
+                let bonding_curve = &mut self.token_data.bonding_curve;
+                bonding_curve.curve_type = curve_type;
+                bonding_curve.base_price = base_price;
+                bonding_curve.slope = slope;
+                bonding_curve.reserve_ratio = reserve_ratio;
+                bonding_curve.configured = true;
+            }
+            ProposalAction::TreasurySpend { recipient, amount } => {
+                require_with_context!(
+                    self.recipient_token_account.owner == recipient,
+                    TokenFactoryError::InvalidProposal,
+                    "instruction=execute_proposal proposal_id={} recipient={} token_account_owner={}",
+                    proposal.proposal_id,
+                    recipient,
+                    self.recipient_token_account.owner
+                );
+                
+                let mint_key = self.token_data.mint;
+                let bump = [governance.bump];
+                let signer_seeds: &[&[&[u8]]] = &[&[b"governance", mint_key.as_ref(), &bump]];
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        self.token_program.to_account_info(),
+                        token::Transfer {
+                            from: self.governance_treasury.to_account_info(),
+                            to: self.recipient_token_account.to_account_info(),
+                            authority: self.governance_authority.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    amount,
+                )?;
+                record_admin_action(
+                    &self.admin_log,
+                    self.proposal.key(),
+                    ADMIN_ACTION_GOVERNANCE_TREASURY_SPEND,
+                    0,
+                    self.token_data.mint,
+                    admin_value(&[self.governance_treasury.amount]),
+                    admin_value(&[self.governance_treasury.amount.saturating_sub(amount)]),
+                )?;
+            }
+        }
+
+        self.proposal.executed = true;
+        
+        emit!(ProposalExecutedEvent {
+            token_id: self.token_data.token_id,
+            mint: self.token_data.mint,
+            proposal_id: self.proposal.proposal_id,
+            yes_votes: self.proposal.yes_votes,
+            no_votes: self.proposal.no_votes,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct GovernanceConfig {
+    pub enabled: bool,
+    pub voting_period: i64,  // Seconds votes are accepted
+    pub timelock_delay: i64, // Seconds between the end of voting and execution
+    pub quorum_votes: u64,
+    pub proposal_threshold: u64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum ProposalAction {
+    CurveParameters {
+        curve_type: u8,
+        base_price: u64,
+        slope: u64,
+        reserve_ratio: u16,
+    },
+    TreasurySpend {
+        recipient: Pubkey,
+        amount: u64,
+    },
+}
+
+#[account]
+pub struct Proposal {
+    pub mint: Pubkey,
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub action: ProposalAction,
+    pub voting_ends_at: i64,
+    pub execute_after: i64,
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    pub executed: bool,
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+#[account]
+pub struct VoteRecord {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub support: bool,
+    pub weight: u64,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+#[event]
+pub struct GovernanceConfiguredEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub voting_period: i64,
+    pub timelock_delay: i64,
+    pub quorum_votes: u64,
+    pub proposal_threshold: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalCreatedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub voting_ends_at: i64,
+    pub execute_after: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteCastEvent {
+    pub mint: Pubkey,
+    pub proposal_id: u64,
+    pub voter: Pubkey,
+    pub support: bool,
+    pub weight: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteTokensReclaimedEvent {
+    pub mint: Pubkey,
+    pub proposal_id: u64,
+    pub voter: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalExecutedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub proposal_id: u64,
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,277 @@
+// Introspection module for Crossify Token Factory
+// This file contains checks on the other instructions of the current transaction via the instructions sysvar
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+use anchor_lang::solana_program::sysvar::instructions as ix_sysvar;
+use anchor_lang::Discriminator;
+
+use crate::instruction as factory_ix;
+use crate::{TokenData, TokenFactoryError};
+
+// Whether another top-level instruction of this transaction calls this program with one of
+// `discriminators` and passes `mint` among its accounts
+pub fn transaction_has_instruction_for_mint(
+    instructions: &AccountInfo,
+    discriminators: &[[u8; 8]],
+    mint: &Pubkey,
+) -> Result<bool> {
+    let current_index = ix_sysvar::load_current_index_checked(instructions)? as usize;
+    let mut index = 0;
+    while let Ok(ix) = ix_sysvar::load_instruction_at_checked(index, instructions) {
+        if index != current_index
+            && ix.program_id == crate::ID
+            && ix.data.len() >= 8
+            && discriminators.iter().any(|discriminator| ix.data[..8] == discriminator[..])
+            && ix.accounts.iter().any(|meta| meta.pubkey == *mint)
+        {
+            return Ok(true);
+        }
+        index += 1;
+    }
+    Ok(false)
+}
+
+// The instructions sysvar lists only top-level instructions, so a program that invoked trades
+// or bridge_out through CPI could combine them out of sight of the checks below
+pub fn require_top_level(instruction: &str) -> Result<()> {
+    let stack_height = get_stack_height();
+    require_with_context!(
+        stack_height == TRANSACTION_LEVEL_STACK_HEIGHT,
+        TokenFactoryError::CpiNotAllowed,
+        "instruction={} stack_height={}",
+        instruction,
+        stack_height
+    );
+    Ok(())
+}
+
+// Curve trades and bridge_out of the same mint must not share a transaction, so a bridge
+// message never reports curve state that a neighbouring trade is about to unwind
+pub fn require_no_trade_bridge_mix(
+    instructions: &AccountInfo,
+    mint: &Pubkey,
+    is_bridge: bool,
+    instruction: &str,
+) -> Result<()> {
+    require_top_level(instruction)?;
+    // Remote trades hold no instructions sysvar, so bridge_out alone looks for them
+    let conflicting: &[[u8; 8]] = if is_bridge {
+        &[
+            factory_ix::BuyTokens::DISCRIMINATOR,
+            factory_ix::SellTokens::DISCRIMINATOR,
+            factory_ix::BuyTokensSecondary::DISCRIMINATOR,
+            factory_ix::SellTokensSecondary::DISCRIMINATOR,
+            factory_ix::ExecuteRemoteBuy::DISCRIMINATOR,
+            factory_ix::ExecuteRemoteSell::DISCRIMINATOR,
+        ]
+    } else {
+        &[factory_ix::BridgeOut::DISCRIMINATOR]
+    };
+    require_with_context!(
+        !transaction_has_instruction_for_mint(instructions, conflicting, mint)?,
+        TokenFactoryError::ConflictingInstructions,
+        "instruction={} mint={} transaction also trades and bridges this mint",
+        instruction,
+        mint
+    );
+    Ok(())
+}
+
+// Tokens that opt in refuse a buy and a sell of the same mint in one transaction, which
+// would otherwise move the curve price and stats and restore them atomically
+pub fn require_no_round_trip(
+    instructions: &AccountInfo,
+    token_data: &TokenData,
+    is_buy: bool,
+    instruction: &str,
+) -> Result<()> {
+    if !token_data.block_round_trips {
+        return Ok(());
+    }
+    require_top_level(instruction)?;
+    // Either quote asset counts, so a round trip cannot straddle the two
+    let opposite = if is_buy {
+        [factory_ix::SellTokens::DISCRIMINATOR, factory_ix::SellTokensSecondary::DISCRIMINATOR]
+    } else {
+        [factory_ix::BuyTokens::DISCRIMINATOR, factory_ix::BuyTokensSecondary::DISCRIMINATOR]
+    };
+    require_with_context!(
+        !transaction_has_instruction_for_mint(instructions, &opposite, &token_data.mint)?,
+        TokenFactoryError::ConflictingInstructions,
+        "instruction={} token_id={} transaction both buys and sells this mint",
+        instruction,
+        token_data.token_id
+    );
+    Ok(())
+}
+
+// Ed25519 program instruction layout: signature count, padding, then one offsets record of
+// seven u16s per signature; u16::MAX as an instruction index means "this instruction"
+const ED25519_OFFSETS_START: usize = 2;
+const ED25519_OFFSETS_LEN: usize = 14;
+const ED25519_PUBKEY_LEN: usize = 32;
+
+// Whether the instruction just before the current one is an Ed25519 program check of exactly
+// one signature by `signer` over `message`; the runtime has already verified the signature
+pub fn previous_ed25519_signs(instructions: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<bool> {
+    let current_index = ix_sysvar::load_current_index_checked(instructions)? as usize;
+    if current_index == 0 {
+        return Ok(false);
+    }
+    let ix = ix_sysvar::load_instruction_at_checked(current_index - 1, instructions)?;
+    let data = &ix.data;
+    if ix.program_id != ed25519_program::ID
+        || data.len() < ED25519_OFFSETS_START + ED25519_OFFSETS_LEN
+        || data[0] != 1
+    {
+        return Ok(false);
+    }
+    let read_u16 = |index: usize| {
+        let offset = ED25519_OFFSETS_START + index * 2;
+        u16::from_le_bytes([data[offset], data[offset + 1]])
+    };
+    let (signature_ix, pubkey_offset, pubkey_ix) = (read_u16(1), read_u16(2) as usize, read_u16(3));
+    let (message_offset, message_len, message_ix) = (read_u16(4) as usize, read_u16(5) as usize, read_u16(6));
+    
+    // Every part must live in the Ed25519 instruction itself, or it could point elsewhere
+    if signature_ix != u16::MAX || pubkey_ix != u16::MAX || message_ix != u16::MAX {
+        return Ok(false);
+    }
+    let signed_pubkey = data.get(pubkey_offset..pubkey_offset + ED25519_PUBKEY_LEN);
+    let signed_message = data.get(message_offset..message_offset + message_len);
+    Ok(signed_pubkey == Some(signer.as_ref()) && signed_message == Some(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+    use anchor_lang::solana_program::sysvar::instructions::{
+        construct_instructions_data, store_current_index, BorrowedAccountMeta, BorrowedInstruction,
+    };
+    use std::cell::Cell;
+    use std::sync::Once;
+    
+    thread_local! {
+        // Stack height the runtime reports to the instruction under test
+        static STACK_HEIGHT: Cell<usize> = const { Cell::new(TRANSACTION_LEVEL_STACK_HEIGHT) };
+    }
+
+    struct StackHeightStubs;
+    
+    impl SyscallStubs for StackHeightStubs {
+        fn sol_get_stack_height(&self) -> u64 {
+            STACK_HEIGHT.with(|stack_height| stack_height.get()) as u64
+        }
+    }
+
+    fn install_stack_height_stubs() {
+        static INSTALL: Once = Once::new();
+        INSTALL.call_once(|| {
+            set_syscall_stubs(Box::new(StackHeightStubs));
+        });
+    }
+    
+    // Serialized instructions sysvar for a transaction of factory instructions, each given by its
+    // discriminator and the mint it touches, executing the one at `current_index`
+    fn instructions_sysvar(instructions: &[([u8; 8], Pubkey)], current_index: u16) -> Vec<u8> {
+        let borrowed: Vec<BorrowedInstruction> = instructions
+            .iter()
+            .map(|(discriminator, mint)| BorrowedInstruction {
+                program_id: &crate::ID,
+                accounts: vec![BorrowedAccountMeta {
+                    pubkey: mint,
+                    is_signer: false,
+                    is_writable: true,
+                }],
+                data: discriminator,
+            })
+            .collect();
+        let mut data = construct_instructions_data(&borrowed);
+        store_current_index(&mut data, current_index);
+        data
+    }
+
+    fn check(instructions: &[([u8; 8], Pubkey)], current_index: u16, mint: &Pubkey, is_bridge: bool) -> Result<()> {
+        install_stack_height_stubs();
+        let mut data = instructions_sysvar(instructions, current_index);
+        let mut lamports = 0;
+        let owner = Pubkey::default();
+        let info = AccountInfo::new(&ix_sysvar::ID, false, false, &mut lamports, &mut data, &owner, false, 0);
+        require_no_trade_bridge_mix(&info, mint, is_bridge, "test")
+    }
+
+    #[test]
+    fn buy_and_bridge_out_of_one_mint_are_rejected_together() {
+        let mint = Pubkey::new_unique();
+        let transaction = [
+            (factory_ix::BuyTokens::DISCRIMINATOR, mint),
+            (factory_ix::BridgeOut::DISCRIMINATOR, mint),
+        ];
+        let conflict: Error = TokenFactoryError::ConflictingInstructions.into();
+        
+        // The buy sees the bridge_out after it, and the bridge_out sees the buy before it
+        assert_eq!(check(&transaction, 0, &mint, false).unwrap_err(), conflict);
+        assert_eq!(check(&transaction, 1, &mint, true).unwrap_err(), conflict);
+    }
+
+    #[test]
+    fn every_curve_trade_conflicts_with_bridge_out() {
+        let mint = Pubkey::new_unique();
+        for trade in [
+            factory_ix::SellTokens::DISCRIMINATOR,
+            factory_ix::BuyTokensSecondary::DISCRIMINATOR,
+            factory_ix::SellTokensSecondary::DISCRIMINATOR,
+        ] {
+            let transaction = [(factory_ix::BridgeOut::DISCRIMINATOR, mint), (trade, mint)];
+            assert!(check(&transaction, 0, &mint, true).is_err());
+            assert!(check(&transaction, 1, &mint, false).is_err());
+        }
+    }
+
+    #[test]
+    fn remote_trades_conflict_with_bridge_out() {
+        let mint = Pubkey::new_unique();
+        for trade in [
+            factory_ix::ExecuteRemoteBuy::DISCRIMINATOR,
+            factory_ix::ExecuteRemoteSell::DISCRIMINATOR,
+        ] {
+            let transaction = [(trade, mint), (factory_ix::BridgeOut::DISCRIMINATOR, mint)];
+            assert!(check(&transaction, 1, &mint, true).is_err());
+        }
+    }
+
+    #[test]
+    fn trades_and_bridges_invoked_through_cpi_are_rejected() {
+        // A wrapper's single top-level instruction CPIs into both buy_tokens and bridge_out
+        let (mint, wrapper_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let transaction = [([0; 8], wrapper_mint)];
+        assert!(check(&transaction, 0, &mint, false).is_ok());
+        
+        STACK_HEIGHT.with(|stack_height| stack_height.set(TRANSACTION_LEVEL_STACK_HEIGHT + 1));
+        let cpi: Error = TokenFactoryError::CpiNotAllowed.into();
+        assert_eq!(check(&transaction, 0, &mint, false).unwrap_err(), cpi);
+        assert_eq!(check(&transaction, 0, &mint, true).unwrap_err(), cpi);
+        STACK_HEIGHT.with(|stack_height| stack_height.set(TRANSACTION_LEVEL_STACK_HEIGHT));
+    }
+
+    #[test]
+    fn trades_and_bridges_of_other_mints_are_allowed() {
+        let (mint, other_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let transaction = [
+            (factory_ix::BuyTokens::DISCRIMINATOR, mint),
+            (factory_ix::BridgeOut::DISCRIMINATOR, other_mint),
+        ];
+        assert!(check(&transaction, 0, &mint, false).is_ok());
+        assert!(check(&transaction, 1, &other_mint, true).is_ok());
+        
+        // Two trades of the same mint are not a trade and bridge mix
+        let trades = [
+            (factory_ix::BuyTokens::DISCRIMINATOR, mint),
+            (factory_ix::SellTokens::DISCRIMINATOR, mint),
+        ];
+        assert!(check(&trades, 0, &mint, false).is_ok());
+    }
+}
@@ -1,17 +1,347 @@
+// Anchor's `Error` is large by design and every instruction returns it
+#![allow(clippy::result_large_err)]
+// `u64::is_multiple_of` is newer than the SBF toolchain the program is built with
+#![allow(clippy::manual_is_multiple_of)]
+
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::spl_token::instruction::AuthorityType;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use crossify_common::CurveError;
 use std::mem::size_of;
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
+// Maximum treasury fee on OTC swaps, in basis points (5%)
+#[constant]
+pub const MAX_OTC_FEE_BPS: u16 = 500;
+
+// Maximum creator tax on curve sells, in basis points (5%)
+#[constant]
+pub const MAX_SELL_TAX_BPS: u16 = 500;
+
+// Maximum protocol fee on outbound bridge messages, in lamports (0.1 SOL)
+#[constant]
+pub const MAX_BRIDGE_FEE_LAMPORTS: u64 = 100_000_000;
+
+// Maximum keeper reward per synced token, in lamports (0.01 SOL)
+#[constant]
+pub const MAX_KEEPER_REWARD_LAMPORTS: u64 = 10_000_000;
+
+// Tokens listed per on-chain index page, in creation order
+#[constant]
+pub const TOKEN_INDEX_PAGE_SIZE: u64 = 100;
+
+// Category tags per token; tag 0 marks an empty slot
+pub const MAX_TOKEN_TAGS: usize = 4;
+
+// Highest Wormhole chain id a token can list as supported
+#[constant]
+pub const MAX_CHAIN_ID: u16 = 255;
+
+// Longest name and metadata URI update_token_metadata accepts, matching Metaplex limits
+pub const MAX_NAME_LEN: usize = 32;
+pub const MAX_METADATA_URI_LEN: usize = 200;
+
+// spl-governance program, whose Realms can take over factory administration
+pub mod spl_governance {
+    use anchor_lang::prelude::*;
+    
+    declare_id!("GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw");
+}
+
+// Metaplex Token Metadata, owner of the metadata naming an NFT's collection
+pub mod mpl_token_metadata {
+    use anchor_lang::prelude::*;
+    
+    declare_id!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+}
+
+// Metaplex Bubblegum, which mints the compressed launch certificates
+pub mod mpl_bubblegum {
+    use anchor_lang::prelude::*;
+    
+    declare_id!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+}
+
+// SPL Account Compression, owner of the certificate merkle tree
+pub mod spl_account_compression {
+    use anchor_lang::prelude::*;
+    
+    declare_id!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+}
+
+// SPL Noop, through which the compression program logs new leaves
+pub mod spl_noop {
+    use anchor_lang::prelude::*;
+    
+    declare_id!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+}
+
+// Wormhole deployments are chosen at build time: `mainnet-beta` for mainnet, devnet otherwise
+#[cfg(all(feature = "devnet", feature = "mainnet-beta"))]
+compile_error!("features `devnet` and `mainnet-beta` are mutually exclusive");
+
+// Wormhole Core Bridge program, owner of the PostedVAA accounts redeemed here
+pub mod wormhole_core_bridge {
+    use anchor_lang::prelude::*;
+    
+    #[cfg(feature = "mainnet-beta")]
+    declare_id!("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+    #[cfg(not(feature = "mainnet-beta"))]
+    declare_id!("3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5");
+}
+
+// Wormhole Token Bridge program, which attests mints and issues their wrapped assets
+pub mod wormhole_token_bridge {
+    use anchor_lang::prelude::*;
+    
+    #[cfg(feature = "mainnet-beta")]
+    declare_id!("wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb");
+    #[cfg(not(feature = "mainnet-beta"))]
+    declare_id!("DZnkkTmCiFWfYTfT41X3Rd1kDgozqzxWaHqsw6W4x2oe");
+}
+
+// Like `require!`, but logs structured context with `msg!` before failing so
+// the reason for a rejected transaction is readable from explorer logs.
+macro_rules! require_with_context {
+    ($invariant:expr, $error:expr, $($context:tt)+) => {
+        if !($invariant) {
+            msg!($($context)+);
+            return Err($error.into());
+        }
+    };
+}
+
+pub mod admin_log;
+pub mod auction;
+pub mod basket;
+pub mod bootstrap;
+pub mod bridge;
+pub mod certificate;
+pub mod chain_registry;
+pub mod cleanup;
+pub mod collection_gate;
+pub mod custody;
+pub mod delegation;
+pub mod dual_curve;
+pub mod escrow;
+pub mod factory_shard;
+pub mod features;
+pub mod governance;
+pub mod introspection;
+pub mod liquidity_lock;
+pub mod lookup_table;
+pub mod migration;
+pub mod mirror;
+pub mod notification;
+pub mod price_feed;
+pub mod quote_allowlist;
+pub mod referral;
+pub mod registration;
+pub mod remote_trade;
+pub mod session;
+pub mod snapshot;
+pub mod solvency;
+pub mod splitter;
+pub mod symbol_registry;
+pub mod summary;
+pub mod supply_vault;
+pub mod sync;
+pub mod token_bridge;
+pub mod trade_history;
+pub mod trading;
+pub mod upgrade;
+pub mod vesting;
+pub mod voucher;
+pub mod wormhole;
+pub use admin_log::*;
+pub use auction::*;
+pub use basket::*;
+pub use bootstrap::*;
+pub use bridge::*;
+pub use certificate::*;
+pub use chain_registry::*;
+pub use cleanup::*;
+pub use collection_gate::*;
+pub use custody::*;
+pub use delegation::*;
+pub use dual_curve::*;
+pub use escrow::*;
+pub use factory_shard::*;
+pub use features::*;
+pub use governance::*;
+pub use introspection::*;
+pub use liquidity_lock::*;
+pub use lookup_table::*;
+pub use migration::*;
+pub use mirror::*;
+pub use notification::*;
+pub use price_feed::*;
+pub use quote_allowlist::*;
+pub use referral::*;
+pub use registration::*;
+pub use remote_trade::*;
+pub use session::*;
+pub use snapshot::*;
+pub use solvency::*;
+pub use splitter::*;
+pub use symbol_registry::*;
+pub use summary::*;
+pub use supply_vault::*;
+pub use sync::*;
+pub use token_bridge::*;
+pub use trade_history::*;
+pub use trading::*;
+pub use upgrade::*;
+pub use vesting::*;
+pub use voucher::*;
+pub use crossify_common::{bps_of, decimals_unit, EXPONENTIAL_SLOPE_SCALE};
+use wormhole::{serialize_supply_update_message, PriceUpdatePayload, SupplyUpdatePayload, UniversalAddress};
+
 #[program]
 pub mod token_factory {
     use super::*;
 
     pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
         let token_factory = &mut ctx.accounts.token_factory;
+        
+        // One factory per deployment, so token ids share a single namespace
+        require_with_context!(
+            token_factory.version == 0,
+            TokenFactoryError::FactoryAlreadyInitialized,
+            "instruction=initialize token_factory={} authority={}",
+            token_factory.key(),
+            token_factory.authority
+        );
+        
         token_factory.authority = ctx.accounts.authority.key();
         token_factory.token_count = 0;
+        token_factory.treasury = ctx.accounts.authority.key();
+        token_factory.otc_fee_bps = 0;
+        token_factory.bridge_fee_lamports = 0;
+        token_factory.sync_interval = 0;
+        token_factory.keeper_reward_lamports = 0;
+        token_factory.verifier = Pubkey::default();
+        token_factory.require_unique_symbols = false;
+        token_factory.symbol_claim_fee_lamports = 0;
+        token_factory.version = TOKEN_FACTORY_VERSION;
+        token_factory.lookup_table = Pubkey::default();
+        token_factory.price_attestor = Pubkey::default();
+        token_factory.required_collection = Pubkey::default();
+        token_factory.disabled_features = 0;
+        Ok(())
+    }
+
+    // Same as initialize, plus roles, fees and chain registry entries, so a fresh deployment
+    // is usable after one transaction
+    pub fn initialize_v2<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitializeV2<'info>>,
+        params: FactoryBootstrapParams,
+    ) -> Result<()> {
+        ctx.accounts.process(params, ctx.remaining_accounts)
+    }
+
+    pub fn configure_treasury(
+        ctx: Context<ConfigureTreasury>,
+        treasury: Pubkey,
+        otc_fee_bps: u16,
+    ) -> Result<()> {
+        let token_factory = &mut ctx.accounts.token_factory;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority
+        require_with_context!(
+            token_factory.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_treasury signer={} expected_authority={}",
+            authority.key(),
+            token_factory.authority
+        );
+        require_with_context!(
+            otc_fee_bps <= MAX_OTC_FEE_BPS,
+            TokenFactoryError::FeeTooHigh,
+            "instruction=configure_treasury otc_fee_bps={} max={}",
+            otc_fee_bps,
+            MAX_OTC_FEE_BPS
+        );
+        
+        record_admin_action(
+            &ctx.accounts.admin_log,
+            authority.key(),
+            ADMIN_ACTION_TREASURY,
+            0,
+            Pubkey::default(),
+            token_factory.treasury.to_bytes(),
+            treasury.to_bytes(),
+        )?;
+        record_admin_action(
+            &ctx.accounts.admin_log,
+            authority.key(),
+            ADMIN_ACTION_OTC_FEE,
+            0,
+            Pubkey::default(),
+            admin_value(&[token_factory.otc_fee_bps as u64]),
+            admin_value(&[otc_fee_bps as u64]),
+        )?;
+        token_factory.treasury = treasury;
+        token_factory.otc_fee_bps = otc_fee_bps;
+        
+        emit!(TreasuryConfiguredEvent {
+            treasury,
+            otc_fee_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    pub fn hand_authority_to_realm(ctx: Context<HandAuthorityToRealm>) -> Result<()> {
+        let token_factory = &mut ctx.accounts.token_factory;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority
+        require_with_context!(
+            token_factory.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=hand_authority_to_realm signer={} expected_authority={}",
+            authority.key(),
+            token_factory.authority
+        );
+        
+        let governance_data = ctx.accounts.governance.try_borrow_data()?;
+        let realm = governance_realm(&governance_data);
+        require_with_context!(
+            realm.is_some(),
+            TokenFactoryError::InvalidRealmGovernance,
+            "instruction=hand_authority_to_realm governance={} account_type={:?} data_len={}",
+            ctx.accounts.governance.key(),
+            governance_data.first(),
+            governance_data.len()
+        );
+        let realm = realm.unwrap();
+        
+        // The governance's native treasury signs proposals' admin instructions
+        let native_treasury = ctx.accounts.native_treasury.key();
+        record_admin_action(
+            &ctx.accounts.admin_log,
+            authority.key(),
+            ADMIN_ACTION_AUTHORITY,
+            0,
+            Pubkey::default(),
+            token_factory.authority.to_bytes(),
+            native_treasury.to_bytes(),
+        )?;
+        token_factory.authority = native_treasury;
+        
+        emit!(FactoryAuthorityChangedEvent {
+            previous_authority: authority.key(),
+            new_authority: native_treasury,
+            realm,
+            governance: ctx.accounts.governance.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
         Ok(())
     }
 
@@ -25,22 +355,59 @@ pub mod token_factory {
     ) -> Result<()> {
         let token_factory = &mut ctx.accounts.token_factory;
         let token_data = &mut ctx.accounts.token_data;
+        let token_stats = &mut ctx.accounts.token_stats;
         let mint = &ctx.accounts.mint;
-        let token_account = &ctx.accounts.token_account;
         let authority = &ctx.accounts.authority;
         
         // Initialize token data
-        token_data.mint = mint.key();
-        token_data.name = name;
-        token_data.symbol = symbol;
-        token_data.decimals = decimals;
-        token_data.metadata_uri = metadata_uri;
-        token_data.authority = authority.key();
-        token_data.initial_supply = initial_supply;
-        token_data.cross_chain_enabled = false;
-        token_data.cross_chain_info = CrossChainInfo::default();
-        token_data.token_id = token_factory.token_count;
-        token_data.bonding_curve = BondingCurve::default();
+        init_token_data(
+            token_data,
+            token_factory.key(),
+            mint.key(),
+            token_factory.token_count,
+            name,
+            symbol,
+            decimals,
+            metadata_uri,
+            authority.key(),
+            initial_supply,
+            Clock::get()?.slot,
+        );
+        
+        // Initialize supply accounting
+        init_token_stats(
+            token_stats,
+            mint.key(),
+            token_data.token_id,
+            *ctx.bumps.get("token_stats").unwrap(),
+        );
+        
+        // List the token in the factory index so UIs can page without scanning
+        list_token(
+            &mut ctx.accounts.token_index_page,
+            token_factory.key(),
+            token_data.token_id,
+            token_data.key(),
+            *ctx.bumps.get("token_index_page").unwrap(),
+        );
+        
+        // Consume the creator's symbol claim, if one is presented or the factory requires it
+        bind_symbol_registry(
+            &token_factory.key(),
+            ctx.accounts.symbol_registry.as_mut(),
+            token_factory.require_unique_symbols,
+            &token_data.symbol,
+            &authority.key(),
+            &mint.key(),
+        )?;
+        
+        // Members-only launch rounds: the creator must hold an NFT of the required collection
+        require_collection_member(
+            &token_factory.required_collection,
+            ctx.accounts.collection_nft_account.as_ref(),
+            ctx.accounts.collection_nft_metadata.as_ref().map(|metadata| metadata.as_ref()),
+            &authority.key(),
+        )?;
         
         // Mint initial supply to token account
         token::mint_to(
@@ -56,8 +423,12 @@ pub mod token_factory {
         )?;
         
         // Increment token count
-        token_factory.token_count += 1;
+        token_factory.token_count = token_factory
+            .token_count
+            .checked_add(1)
+            .ok_or(TokenFactoryError::MathOverflow)?;
         
+        let now = Clock::get()?.unix_timestamp;
         emit!(TokenCreatedEvent {
             token_id: token_data.token_id,
             mint: token_data.mint,
@@ -65,39 +436,143 @@ pub mod token_factory {
             symbol: token_data.symbol.clone(),
             decimals: token_data.decimals,
             initial_supply: token_data.initial_supply,
+            timestamp: now,
         });
+        emit_digest(DIGEST_ACTION_CREATED, token_data.mint, authority.key(), token_data.initial_supply, now);
         
         Ok(())
     }
 
+    // Alternative to create_token that leaves the factory untouched, for parallel launches
+    pub fn create_token_with_derived_id(
+        ctx: Context<CreateTokenWithDerivedId>,
+        name: String,
+        symbol: String,
+        decimals: u8,
+        metadata_uri: String,
+        initial_supply: u64,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("token_stats").unwrap();
+        ctx.accounts.process(bump, name, symbol, decimals, metadata_uri, initial_supply)
+    }
+
+    // Sequential ids without the factory write lock; `shard` must be shard_for_creator(authority)
+    pub fn create_token_sharded(
+        ctx: Context<CreateTokenSharded>,
+        shard: u8,
+        name: String,
+        symbol: String,
+        decimals: u8,
+        metadata_uri: String,
+        initial_supply: u64,
+    ) -> Result<()> {
+        let shard_bump = *ctx.bumps.get("factory_shard").unwrap();
+        let token_stats_bump = *ctx.bumps.get("token_stats").unwrap();
+        ctx.accounts.process(
+            shard_bump,
+            token_stats_bump,
+            shard,
+            name,
+            symbol,
+            decimals,
+            metadata_uri,
+            initial_supply,
+        )
+    }
+
+    pub fn estimate_create_token_cost(
+        ctx: Context<EstimateCreateTokenCost>,
+        name_len: u32,
+        symbol_len: u32,
+        metadata_uri_len: u32,
+    ) -> Result<CreateTokenCost> {
+        ctx.accounts.process(name_len, symbol_len, metadata_uri_len)
+    }
+
     pub fn enable_cross_chain(
         ctx: Context<EnableCrossChain>,
-        wormhole_emitter: Pubkey,
+        wormhole_emitter: UniversalAddress,
         chain_ids: Vec<u16>,
+        bridge_mode: u8,
     ) -> Result<()> {
         let token_data = &mut ctx.accounts.token_data;
         let authority = &ctx.accounts.authority;
         
         // Verify authority
-        require!(token_data.authority == authority.key(), TokenFactoryError::InvalidAuthority);
+        require_with_context!(
+            token_data.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=enable_cross_chain token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            authority.key(),
+            token_data.authority
+        );
+        
+        require_with_context!(
+            !wormhole_emitter.is_zero(),
+            TokenFactoryError::InvalidUniversalAddress,
+            "instruction=enable_cross_chain token_id={} wormhole_emitter is zero",
+            token_data.token_id
+        );
         
-        // Enable cross-chain functionality
+        // The mode cannot change once set: remote supply is backed by either burns or custody.
+        // Tokens enabled before modes existed have been burning and stay in burn mode.
+        let previously_enabled = !token_data.cross_chain_info.wormhole_emitter.is_zero();
+        let allowed = match token_data.bridge_mode {
+            0 if previously_enabled => bridge_mode == BRIDGE_MODE_BURN,
+            0 => bridge_mode == BRIDGE_MODE_BURN || bridge_mode == BRIDGE_MODE_LOCK,
+            current => bridge_mode == current,
+        };
+        require_with_context!(
+            allowed,
+            TokenFactoryError::InvalidBridgeMode,
+            "instruction=enable_cross_chain token_id={} bridge_mode={} current_mode={}",
+            token_data.token_id,
+            bridge_mode,
+            token_data.bridge_mode
+        );
+        
+        // Enable cross-chain functionality; repeating the call with the same chains is a no-op
+        let mut supported_chains = ChainSet::default();
+        for chain_id in chain_ids.iter() {
+            require_with_context!(
+                supported_chains.insert(*chain_id),
+                TokenFactoryError::UnsupportedChain,
+                "instruction=enable_cross_chain token_id={} chain_id={} max_chain_id={}",
+                token_data.token_id,
+                chain_id,
+                MAX_CHAIN_ID
+            );
+        }
+        let previous_emitter = token_data.cross_chain_info.wormhole_emitter;
+        if token_data.cross_chain_enabled && previous_emitter != wormhole_emitter {
+            emit!(CrossChainEmitterUpdatedEvent {
+                token_id: token_data.token_id,
+                mint: token_data.mint,
+                previous_emitter,
+                wormhole_emitter,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
         token_data.cross_chain_enabled = true;
         token_data.cross_chain_info.wormhole_emitter = wormhole_emitter;
-        token_data.cross_chain_info.supported_chains = chain_ids;
+        token_data.cross_chain_info.supported_chains = supported_chains;
+        token_data.bridge_mode = bridge_mode;
         
         emit!(CrossChainEnabledEvent {
             token_id: token_data.token_id,
             mint: token_data.mint,
             wormhole_emitter,
-            supported_chains: chain_ids.clone(),
+            supported_chains: supported_chains.chain_ids(),
+            bridge_mode,
+            timestamp: Clock::get()?.unix_timestamp,
         });
         
         Ok(())
     }
 
     pub fn configure_bonding_curve(
-        ctx: Context<ConfigureBondingCurve>,
+        ctx: Context<ConfigureCurveParameters>,
         curve_type: u8,
         base_price: u64,
         slope: u64,
@@ -107,18 +582,47 @@ pub mod token_factory {
         let authority = &ctx.accounts.authority;
         
         // Verify authority
-        require!(token_data.authority == authority.key(), TokenFactoryError::InvalidAuthority);
+        require_with_context!(
+            token_data.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_bonding_curve token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            authority.key(),
+            token_data.authority
+        );
+        
+        // Once holders govern the token, parameter changes go through proposals
+        require_with_context!(
+            !token_data.governance.enabled,
+            TokenFactoryError::GovernanceControlled,
+            "instruction=configure_bonding_curve token_id={}",
+            token_data.token_id
+        );
+        
+        require_curve_parameters_mutable(token_data, ctx.accounts.token_stats.curve_supply, "configure_bonding_curve")?;
         
         // Validate curve parameters
-        require!(curve_type <= 2, TokenFactoryError::InvalidCurveType);
-        require!(reserve_ratio <= 1000, TokenFactoryError::InvalidReserveRatio); // Max 100.0%
+        require_with_context!(
+            curve_type <= 2,
+            TokenFactoryError::InvalidCurveType,
+            "instruction=configure_bonding_curve token_id={} curve_type={}",
+            token_data.token_id,
+            curve_type
+        );
+        require_with_context!(
+            reserve_ratio <= 1000, // Max 100.0%
+            TokenFactoryError::InvalidReserveRatio,
+            "instruction=configure_bonding_curve token_id={} reserve_ratio={}",
+            token_data.token_id,
+            reserve_ratio
+        );
         
         // Configure bonding curve
         token_data.bonding_curve.curve_type = curve_type;
         token_data.bonding_curve.base_price = base_price;
         token_data.bonding_curve.slope = slope;
         token_data.bonding_curve.reserve_ratio = reserve_ratio;
-        token_data.bonding_curve.enabled = true;
+        token_data.bonding_curve.configured = true;
         
         emit!(BondingCurveConfiguredEvent {
             token_id: token_data.token_id,
@@ -127,6 +631,52 @@ pub mod token_factory {
             base_price,
             slope,
             reserve_ratio,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    pub fn set_bonding_curve_enabled(ctx: Context<SetBondingCurveEnabled>, enabled: bool) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority; delegates may pause trading but only the authority resumes it
+        if enabled {
+            require_with_context!(
+                token_data.authority == authority.key(),
+                TokenFactoryError::InvalidAuthority,
+                "instruction=set_bonding_curve_enabled token_id={} signer={} expected_authority={}",
+                token_data.token_id,
+                authority.key(),
+                token_data.authority
+            );
+        } else {
+            require_authority_or_delegate(
+                token_data,
+                &authority.key(),
+                ctx.accounts.delegation.as_ref(),
+                CAPABILITY_PAUSE_TRADING,
+                "set_bonding_curve_enabled",
+            )?;
+        }
+        
+        // A curve has to be configured before it can be toggled
+        require_with_context!(
+            token_data.bonding_curve.configured,
+            TokenFactoryError::CurveNotConfigured,
+            "instruction=set_bonding_curve_enabled token_id={} enabled={}",
+            token_data.token_id,
+            enabled
+        );
+        
+        token_data.bonding_curve.enabled = enabled;
+        
+        emit!(BondingCurveToggledEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            enabled,
+            timestamp: Clock::get()?.unix_timestamp,
         });
         
         Ok(())
@@ -139,30 +689,25 @@ pub mod token_factory {
     ) -> Result<u64> {
         let token_data = &ctx.accounts.token_data;
         
-        // Verify bonding curve is enabled
-        require!(token_data.bonding_curve.enabled, TokenFactoryError::BondingCurveNotEnabled);
+        // Verify bonding curve is configured and enabled
+        require_with_context!(
+            token_data.bonding_curve.configured,
+            TokenFactoryError::CurveNotConfigured,
+            "instruction=calculate_price token_id={} supply={} amount={}",
+            token_data.token_id,
+            supply,
+            amount
+        );
+        require_with_context!(
+            token_data.bonding_curve.enabled,
+            TokenFactoryError::CurveDisabled,
+            "instruction=calculate_price token_id={} supply={} amount={}",
+            token_data.token_id,
+            supply,
+            amount
+        );
         
-        let price = match token_data.bonding_curve.curve_type {
-            0 => calculate_linear_price(
-                supply,
-                amount,
-                token_data.bonding_curve.base_price,
-                token_data.bonding_curve.slope,
-            ),
-            1 => calculate_exponential_price(
-                supply,
-                amount,
-                token_data.bonding_curve.base_price,
-                token_data.bonding_curve.slope,
-            ),
-            2 => calculate_bancor_price(
-                supply,
-                amount,
-                token_data.bonding_curve.base_price,
-                token_data.bonding_curve.reserve_ratio,
-            ),
-            _ => return Err(TokenFactoryError::InvalidCurveType.into()),
-        };
+        let price = quote_bonding_curve(&token_data.bonding_curve, token_data.decimals, supply, amount)?;
         
         emit!(PriceCalculatedEvent {
             token_id: token_data.token_id,
@@ -170,6 +715,7 @@ pub mod token_factory {
             supply,
             amount,
             price,
+            timestamp: Clock::get()?.unix_timestamp,
         });
         
         Ok(price)
@@ -184,17 +730,40 @@ pub mod token_factory {
         let authority = &ctx.accounts.authority;
         
         // Verify authority
-        require!(token_data.authority == authority.key(), TokenFactoryError::InvalidAuthority);
+        require_authority_or_delegate(
+            token_data,
+            &authority.key(),
+            ctx.accounts.delegation.as_ref(),
+            CAPABILITY_PUBLISH_MESSAGES,
+            "send_cross_chain_message",
+        )?;
         
         // Verify cross-chain is enabled
-        require!(token_data.cross_chain_enabled, TokenFactoryError::CrossChainNotEnabled);
+        require_with_context!(
+            token_data.cross_chain_enabled,
+            TokenFactoryError::CrossChainNotEnabled,
+            "instruction=send_cross_chain_message token_id={} target_chain={}",
+            token_data.token_id,
+            target_chain
+        );
         
         // Verify target chain is supported
-        require!(
-            token_data.cross_chain_info.supported_chains.contains(&target_chain),
-            TokenFactoryError::UnsupportedChain
+        require_with_context!(
+            token_data.cross_chain_info.supported_chains.contains(target_chain),
+            TokenFactoryError::UnsupportedChain,
+            "instruction=send_cross_chain_message token_id={} target_chain={} supported_chains={:?}",
+            token_data.token_id,
+            target_chain,
+            token_data.cross_chain_info.supported_chains.chain_ids()
         );
         
+        collect_bridge_fee(
+            &ctx.accounts.token_factory,
+            authority,
+            &ctx.accounts.treasury.to_account_info(),
+            &ctx.accounts.system_program,
+        )?;
+        
         // In a real implementation, this would call the Wormhole bridge to send the message
         // For now, we just emit an event
         emit!(CrossChainMessageSentEvent {
@@ -202,92 +771,2046 @@ pub mod token_factory {
             mint: token_data.mint,
             target_chain,
             payload: payload.clone(),
+            timestamp: Clock::get()?.unix_timestamp,
         });
         
         Ok(())
     }
-}
 
-// Helper functions for price calculation
-fn calculate_linear_price(supply: u64, amount: u64, base_price: u64, slope: u64) -> u64 {
-    // P = base_price + slope * supply
-    let current_price = base_price.saturating_add(slope.saturating_mul(supply));
-    current_price.saturating_mul(amount)
-}
+    pub fn burn_tokens(
+        ctx: Context<BurnTokens>,
+        amount: u64,
+        publish_supply_update: bool,
+    ) -> Result<()> {
+        let token_data = &ctx.accounts.token_data;
+        let owner = &ctx.accounts.owner;
+        
+        require_with_context!(
+            amount > 0,
+            TokenFactoryError::InvalidAmount,
+            "instruction=burn_tokens token_id={} amount={}",
+            token_data.token_id,
+            amount
+        );
+        require_with_context!(
+            !publish_supply_update || token_data.cross_chain_enabled,
+            TokenFactoryError::CrossChainNotEnabled,
+            "instruction=burn_tokens token_id={} amount={} publish_supply_update={}",
+            token_data.token_id,
+            amount,
+            publish_supply_update
+        );
+        
+        // Burn from the caller's token account
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.token_account.to_account_info(),
+                    authority: owner.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        // Track the burn in the factory's accounting
+        let token_stats = &mut ctx.accounts.token_stats;
+        token_stats.total_burned = token_stats
+            .total_burned
+            .checked_add(amount)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        
+        ctx.accounts.mint.reload()?;
+        let total_supply = ctx.accounts.mint.supply;
+        let timestamp = Clock::get()?.unix_timestamp;
+        
+        emit!(TokensBurnedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            owner: owner.key(),
+            amount,
+            total_burned: token_stats.total_burned,
+            total_supply,
+            timestamp,
+        });
+        
+        if publish_supply_update {
+            let payload = serialize_supply_update_message(&SupplyUpdatePayload {
+                factory: token_data.factory,
+                token_id: token_data.token_id,
+                total_supply,
+                total_burned: token_stats.total_burned,
+                timestamp,
+            });
+            
+            // In a real implementation, this would call the Wormhole bridge to send the message
+            // For now, we just emit an event per supported chain
+            for target_chain in token_data.cross_chain_info.supported_chains.chain_ids() {
+                emit!(CrossChainMessageSentEvent {
+                    token_id: token_data.token_id,
+                    mint: token_data.mint,
+                    target_chain,
+                    payload: payload.clone(),
+                    timestamp,
+                });
+            }
+        }
 
-fn calculate_exponential_price(supply: u64, amount: u64, base_price: u64, slope: u64) -> u64 {
-    // P = base_price * (1 + slope)^supply
-    // For simplicity, we approximate this with a simpler formula
-    let exponent = slope.saturating_mul(supply) / 10000; // Scaled slope
-    let current_price = base_price.saturating_add(base_price.saturating_mul(exponent) / 100);
-    current_price.saturating_mul(amount)
-}
+        Ok(())
+    }
 
-fn calculate_bancor_price(supply: u64, amount: u64, base_price: u64, reserve_ratio: u16) -> u64 {
-    // Bancor formula: P = base_price * (supply / initial_supply)^((1 / reserve_ratio) - 1)
-    // For simplicity, we approximate this with a simpler formula
-    let ratio_factor = 1000_u64.saturating_sub(reserve_ratio as u64) / 1000;
-    let supply_factor = if supply > 1000 { supply / 1000 } else { 1 };
-    let current_price = base_price.saturating_mul(supply_factor.saturating_pow(ratio_factor as u32));
-    current_price.saturating_mul(amount)
-}
+    pub fn delegate_mint_authority(ctx: Context<DelegateMintAuthority>) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=delegate_mint_authority token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            authority.key(),
+            token_data.authority
+        );
+        
+        // Hand the mint authority over to the program-derived address
+        token::set_authority(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::SetAuthority {
+                    current_authority: authority.to_account_info(),
+                    account_or_mint: ctx.accounts.mint.to_account_info(),
+                },
+            ),
+            AuthorityType::MintTokens,
+            Some(ctx.accounts.mint_authority.key()),
+        )?;
+        
+        token_data.mint_authority_bump = *ctx.bumps.get("mint_authority").unwrap();
+        
+        emit!(MintAuthorityDelegatedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            previous_authority: authority.key(),
+            mint_authority: ctx.accounts.mint_authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + size_of::<TokenFactory>()
-    )]
-    pub token_factory: Account<'info, TokenFactory>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    pub fn configure_emissions(
+        ctx: Context<ConfigureEmissions>,
+        recipient: Pubkey,
+        start_ts: i64,
+        epoch_duration: i64,
+        amount_per_epoch: u64,
+        total_epochs: u32,
+    ) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let authority = &ctx.accounts.authority;
+        let mint_authority = ctx.accounts.mint_authority.key();
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_emissions token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            authority.key(),
+            token_data.authority
+        );
+        
+        // Schedules are immutable once set so holders can rely on them
+        require_with_context!(
+            !token_data.emission_schedule.enabled,
+            TokenFactoryError::EmissionScheduleAlreadySet,
+            "instruction=configure_emissions token_id={}",
+            token_data.token_id
+        );
+        
+        // Emissions are minted by the program, so it must hold the mint authority
+        require_with_context!(
+            ctx.accounts.mint.mint_authority == COption::Some(mint_authority),
+            TokenFactoryError::MintAuthorityNotDelegated,
+            "instruction=configure_emissions token_id={} expected_mint_authority={}",
+            token_data.token_id,
+            mint_authority
+        );
+        
+        // Validate schedule parameters
+        require_with_context!(
+            epoch_duration > 0 && amount_per_epoch > 0 && total_epochs > 0,
+            TokenFactoryError::InvalidEmissionSchedule,
+            "instruction=configure_emissions token_id={} epoch_duration={} amount_per_epoch={} total_epochs={}",
+            token_data.token_id,
+            epoch_duration,
+            amount_per_epoch,
+            total_epochs
+        );
+        require_with_context!(
+            amount_per_epoch.checked_mul(total_epochs as u64).is_some(),
+            TokenFactoryError::MathOverflow,
+            "instruction=configure_emissions token_id={} amount_per_epoch={} total_epochs={}",
+            token_data.token_id,
+            amount_per_epoch,
+            total_epochs
+        );
+        
+        token_data.emission_schedule = EmissionSchedule {
+            enabled: true,
+            recipient,
+            start_ts,
+            epoch_duration,
+            amount_per_epoch,
+            total_epochs,
+            epochs_minted: 0,
+        };
+        
+        emit!(EmissionScheduleConfiguredEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            recipient,
+            start_ts,
+            epoch_duration,
+            amount_per_epoch,
+            total_epochs,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct CreateToken<'info> {
-    #[account(mut)]
-    pub token_factory: Account<'info, TokenFactory>,
-    
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + size_of::<TokenData>() + 256, // Extra space for strings
-    )]
-    pub token_data: Account<'info, TokenData>,
-    
-    #[account(
-        init,
-        payer = authority,
-        mint::decimals = decimals,
-        mint::authority = authority.key(),
-    )]
-    pub mint: Account<'info, Mint>,
-    
-    #[account(
-        init,
-        payer = authority,
-        token::mint = mint,
-        token::authority = authority,
-    )]
-    pub token_account: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+    pub fn crank_emission(ctx: Context<CrankEmission>) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let now = Clock::get()?.unix_timestamp;
+        
+        require_with_context!(
+            token_data.emission_schedule.enabled,
+            TokenFactoryError::EmissionScheduleNotSet,
+            "instruction=crank_emission token_id={}",
+            token_data.token_id
+        );
+        
+        // Mint every epoch that has fully elapsed but not yet been minted
+        let schedule = &token_data.emission_schedule;
+        let elapsed_epochs = if now > schedule.start_ts {
+            ((now - schedule.start_ts) / schedule.epoch_duration).min(schedule.total_epochs as i64) as u32
+        } else {
+            0
+        };
+        let due_epochs = elapsed_epochs.saturating_sub(schedule.epochs_minted);
+        require_with_context!(
+            due_epochs > 0,
+            TokenFactoryError::NoEmissionDue,
+            "instruction=crank_emission token_id={} now={} epochs_minted={} total_epochs={}",
+            token_data.token_id,
+            now,
+            schedule.epochs_minted,
+            schedule.total_epochs
+        );
+        let amount = schedule
+            .amount_per_epoch
+            .checked_mul(due_epochs as u64)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        
+        let mint_key = token_data.mint;
+        let bump = [token_data.mint_authority_bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"mint_authority", mint_key.as_ref(), &bump]];
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::MintTo {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.recipient_token_account.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+        
+        token_data.emission_schedule.epochs_minted = elapsed_epochs;
+        
+        emit!(EmissionMintedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            recipient: token_data.emission_schedule.recipient,
+            epochs: due_epochs,
+            amount,
+            epochs_minted: elapsed_epochs,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    pub fn take_snapshot(
+        ctx: Context<TakeSnapshot>,
+        balances_root: [u8; 32],
+        distribution_amount: u64,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("snapshot").unwrap();
+        ctx.accounts.process(bump, balances_root, distribution_amount)
+    }
+
+    pub fn claim_snapshot_distribution(
+        ctx: Context<ClaimSnapshotDistribution>,
+        balance: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts.process(balance, proof)
+    }
+
+    pub fn create_offer(
+        ctx: Context<CreateOffer>,
+        offer_id: u64,
+        offer_amount: u64,
+        request_amount: u64,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("offer").unwrap();
+        ctx.accounts.process(bump, offer_id, offer_amount, request_amount)
+    }
+
+    pub fn accept_offer(ctx: Context<AcceptOffer>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn cancel_offer(ctx: Context<CancelOffer>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn configure_governance(
+        ctx: Context<ConfigureGovernance>,
+        voting_period: i64,
+        timelock_delay: i64,
+        quorum_votes: u64,
+        proposal_threshold: u64,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("governance_authority").unwrap();
+        ctx.accounts.process(bump, voting_period, timelock_delay, quorum_votes, proposal_threshold)
+    }
+
+    pub fn create_proposal(ctx: Context<CreateProposal>, action: ProposalAction) -> Result<()> {
+        let bump = *ctx.bumps.get("proposal").unwrap();
+        ctx.accounts.process(bump, action)
+    }
+
+    pub fn cast_vote(ctx: Context<CastVote>, support: bool, amount: u64) -> Result<()> {
+        ctx.accounts.process(support, amount)
+    }
+
+    pub fn reclaim_vote_tokens(ctx: Context<ReclaimVoteTokens>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn configure_sell_tax(ctx: Context<ConfigureBondingCurve>, sell_tax_bps: u16) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_sell_tax token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            authority.key(),
+            token_data.authority
+        );
+        
+        // Buyers must know the tax before they buy
+        require_with_context!(
+            !token_data.bonding_curve.trading_started,
+            TokenFactoryError::TradingAlreadyStarted,
+            "instruction=configure_sell_tax token_id={} sell_tax_bps={}",
+            token_data.token_id,
+            sell_tax_bps
+        );
+        require_with_context!(
+            sell_tax_bps <= MAX_SELL_TAX_BPS,
+            TokenFactoryError::FeeTooHigh,
+            "instruction=configure_sell_tax token_id={} sell_tax_bps={} max={}",
+            token_data.token_id,
+            sell_tax_bps,
+            MAX_SELL_TAX_BPS
+        );
+        
+        token_data.bonding_curve.sell_tax_bps = sell_tax_bps;
+        
+        emit!(SellTaxConfiguredEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            sell_tax_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    pub fn initialize_reserve(ctx: Context<InitializeReserve>) -> Result<()> {
+        let bump = *ctx.bumps.get("reserve_authority").unwrap();
+        ctx.accounts.process(bump)
+    }
+
+    pub fn buy_tokens(ctx: Context<BuyTokens>, amount: u64, max_cost: u64) -> Result<()> {
+        ctx.accounts.process(amount, max_cost)
+    }
+
+    pub fn sell_tokens(ctx: Context<SellTokens>, amount: u64, min_proceeds: u64) -> Result<()> {
+        ctx.accounts.process(amount, min_proceeds)
+    }
+
+    pub fn configure_secondary_quote(
+        ctx: Context<ConfigureSecondaryQuote>,
+        spread_bps: u16,
+        max_rate_age: i64,
+        enabled: bool,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("secondary_curve").unwrap();
+        ctx.accounts.process(bump, spread_bps, max_rate_age, enabled)
+    }
+
+    pub fn update_secondary_rate(ctx: Context<UpdateSecondaryRate>, rate: u64) -> Result<()> {
+        ctx.accounts.process(rate)
+    }
+
+    pub fn buy_tokens_secondary(ctx: Context<BuyTokensSecondary>, amount: u64, max_cost: u64) -> Result<()> {
+        ctx.accounts.process(amount, max_cost)
+    }
+
+    pub fn sell_tokens_secondary(ctx: Context<SellTokensSecondary>, amount: u64, min_proceeds: u64) -> Result<()> {
+        ctx.accounts.process(amount, min_proceeds)
+    }
+
+    pub fn claim_secondary_creator_fees(ctx: Context<ClaimSecondaryCreatorFees>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn open_buyer_record(ctx: Context<OpenBuyerRecord>, wallet: Pubkey) -> Result<()> {
+        ctx.accounts.process(wallet)
+    }
+
+    pub fn lock_liquidity(ctx: Context<LockLiquidity>, amount: u64, burn: bool) -> Result<()> {
+        ctx.accounts.process(amount, burn)
+    }
+
+    pub fn create_basket<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateBasket<'info>>,
+        decimals: u8,
+        units: Vec<u64>,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("basket").unwrap();
+        ctx.accounts.process(bump, decimals, units, ctx.remaining_accounts)
+    }
+
+    pub fn mint_basket<'info>(ctx: Context<'_, '_, '_, 'info, MintBasket<'info>>, amount: u64) -> Result<()> {
+        ctx.accounts.process(amount, ctx.remaining_accounts)
+    }
+
+    pub fn redeem_basket<'info>(ctx: Context<'_, '_, '_, 'info, RedeemBasket<'info>>, amount: u64) -> Result<()> {
+        ctx.accounts.process(amount, ctx.remaining_accounts)
+    }
+
+    pub fn update_price_feed(ctx: Context<UpdatePriceFeed>) -> Result<()> {
+        let bump = *ctx.bumps.get("price_feed").unwrap();
+        ctx.accounts.process(bump)
+    }
+
+    pub fn get_lending_price(ctx: Context<GetLendingPrice>, max_age: i64) -> Result<u64> {
+        ctx.accounts.process(max_age)
+    }
+
+    pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn get_creator_summary<'info>(
+        ctx: Context<'_, '_, '_, 'info, GetCreatorSummary>,
+        creator: Pubkey,
+    ) -> Result<CreatorSummary> {
+        ctx.accounts.process(creator, ctx.remaining_accounts)
+    }
+
+    pub fn configure_payout_splitter(
+        ctx: Context<ConfigurePayoutSplitter>,
+        recipients: Vec<PayoutRecipient>,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("payout_splitter").unwrap();
+        ctx.accounts.process(bump, recipients)
+    }
+
+    pub fn remove_payout_splitter(ctx: Context<RemovePayoutSplitter>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn claim_split_creator_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimSplitCreatorFees<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts)
+    }
+
+    pub fn configure_sell_limits(
+        ctx: Context<ConfigureBondingCurve>,
+        cooldown_seconds: i64,
+        max_sell_per_window: u64,
+        window_seconds: i64,
+    ) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_sell_limits token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            authority.key(),
+            token_data.authority
+        );
+        
+        // Buyers must know the rules before they buy
+        require_with_context!(
+            !token_data.bonding_curve.trading_started,
+            TokenFactoryError::TradingAlreadyStarted,
+            "instruction=configure_sell_limits token_id={}",
+            token_data.token_id
+        );
+        require_with_context!(
+            cooldown_seconds >= 0 && (max_sell_per_window == 0 || window_seconds > 0),
+            TokenFactoryError::InvalidSellLimits,
+            "instruction=configure_sell_limits token_id={} cooldown_seconds={} max_sell_per_window={} window_seconds={}",
+            token_data.token_id,
+            cooldown_seconds,
+            max_sell_per_window,
+            window_seconds
+        );
+        
+        token_data.sell_limits = SellLimits {
+            cooldown_seconds,
+            max_sell_per_window,
+            window_seconds,
+        };
+        
+        emit!(SellLimitsConfiguredEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            cooldown_seconds,
+            max_sell_per_window,
+            window_seconds,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    pub fn configure_round_trip_guard(ctx: Context<ConfigureBondingCurve>, block_round_trips: bool) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_round_trip_guard token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            authority.key(),
+            token_data.authority
+        );
+        require_with_context!(
+            !token_data.bonding_curve.trading_started,
+            TokenFactoryError::TradingAlreadyStarted,
+            "instruction=configure_round_trip_guard token_id={}",
+            token_data.token_id
+        );
+        
+        token_data.block_round_trips = block_round_trips;
+        
+        emit!(RoundTripGuardConfiguredEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            block_round_trips,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    pub fn configure_metadata_freeze(ctx: Context<ConfigureBondingCurve>, keep_metadata_mutable: bool) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_metadata_freeze token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            authority.key(),
+            token_data.authority
+        );
+        
+        // Buyers must know whether metadata can change after graduation
+        require_with_context!(
+            !token_data.bonding_curve.trading_started,
+            TokenFactoryError::TradingAlreadyStarted,
+            "instruction=configure_metadata_freeze token_id={}",
+            token_data.token_id
+        );
+        
+        token_data.keep_metadata_mutable = keep_metadata_mutable;
+        
+        emit!(MetadataFreezeConfiguredEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            keep_metadata_mutable,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    pub fn update_token_metadata(
+        ctx: Context<UpdateTokenMetadata>,
+        name: String,
+        metadata_uri: String,
+    ) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=update_token_metadata token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            authority.key(),
+            token_data.authority
+        );
+        require_with_context!(
+            !token_data.metadata_frozen,
+            TokenFactoryError::MetadataFrozen,
+            "instruction=update_token_metadata token_id={}",
+            token_data.token_id
+        );
+        
+        // Same bounds as Metaplex metadata; the account itself grows to fit
+        require_with_context!(
+            name.len() <= MAX_NAME_LEN && metadata_uri.len() <= MAX_METADATA_URI_LEN,
+            TokenFactoryError::InvalidMetadata,
+            "instruction=update_token_metadata token_id={} name_len={} metadata_uri_len={}",
+            token_data.token_id,
+            name.len(),
+            metadata_uri.len()
+        );
+        
+        token_data.name = name.clone();
+        token_data.metadata_uri = metadata_uri.clone();
+        
+        emit!(TokenMetadataUpdatedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            name,
+            metadata_uri,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    pub fn schedule_trading_start(ctx: Context<ConfigureBondingCurve>, trading_start_ts: i64) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=schedule_trading_start token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            authority.key(),
+            token_data.authority
+        );
+        require_with_context!(
+            !token_data.bonding_curve.trading_started,
+            TokenFactoryError::TradingAlreadyStarted,
+            "instruction=schedule_trading_start token_id={} trading_start_ts={}",
+            token_data.token_id,
+            trading_start_ts
+        );
+        
+        token_data.bonding_curve.trading_start_ts = trading_start_ts;
+        
+        emit!(TradingStartScheduledEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            trading_start_ts,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    pub fn configure_refundable_launch(
+        ctx: Context<ConfigureBondingCurve>,
+        graduation_threshold: u64,
+        deadline: i64,
+    ) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_refundable_launch token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            authority.key(),
+            token_data.authority
+        );
+        require_with_context!(
+            !token_data.bonding_curve.trading_started,
+            TokenFactoryError::TradingAlreadyStarted,
+            "instruction=configure_refundable_launch token_id={}",
+            token_data.token_id
+        );
+        require_with_context!(
+            graduation_threshold > 0 && deadline > Clock::get()?.unix_timestamp,
+            TokenFactoryError::InvalidRefundPolicy,
+            "instruction=configure_refundable_launch token_id={} graduation_threshold={} deadline={}",
+            token_data.token_id,
+            graduation_threshold,
+            deadline
+        );
+        
+        token_data.bonding_curve.graduation_threshold = graduation_threshold;
+        token_data.refund_policy = RefundPolicy {
+            enabled: true,
+            deadline,
+            failed: false,
+            refund_reserve: 0,
+            refund_supply: 0,
+        };
+        
+        emit!(RefundableLaunchConfiguredEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            graduation_threshold,
+            deadline,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    pub fn refund(ctx: Context<Refund>, amount: u64) -> Result<()> {
+        ctx.accounts.process(amount)
+    }
+
+    pub fn start_dutch_auction(
+        ctx: Context<StartDutchAuction>,
+        tranche_amount: u64,
+        start_price: u64,
+        floor_price: u64,
+        start_ts: i64,
+        duration: i64,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("auction").unwrap();
+        ctx.accounts
+            .process(bump, tranche_amount, start_price, floor_price, start_ts, duration)
+    }
+
+    pub fn place_auction_bid(ctx: Context<PlaceAuctionBid>, amount: u64, max_price: u64) -> Result<()> {
+        ctx.accounts.process(amount, max_price)
+    }
+
+    pub fn settle_dutch_auction(ctx: Context<SettleDutchAuction>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn claim_auction_tokens(ctx: Context<ClaimAuctionTokens>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn fund_voucher_vault(ctx: Context<FundVoucherVault>, amount: u64) -> Result<()> {
+        ctx.accounts.process(amount)
+    }
+
+    pub fn claim_voucher(
+        ctx: Context<ClaimVoucher>,
+        voucher_id: u64,
+        amount: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        let voucher_bump = *ctx.bumps.get("voucher_authority").unwrap();
+        ctx.accounts.process(voucher_bump, voucher_id, amount, expires_at)
+    }
+
+    pub fn tag_token(ctx: Context<TagToken>, tag: u8, page: u64) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=tag_token token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            authority.key(),
+            token_data.authority
+        );
+        require_with_context!(
+            tag != 0 && !token_data.tags.contains(&tag),
+            TokenFactoryError::InvalidTag,
+            "instruction=tag_token token_id={} tag={}",
+            token_data.token_id,
+            tag
+        );
+        let slot = token_data.tags.iter().position(|existing| *existing == 0);
+        require_with_context!(
+            slot.is_some(),
+            TokenFactoryError::TooManyTags,
+            "instruction=tag_token token_id={} tag={} max_tags={}",
+            token_data.token_id,
+            tag,
+            MAX_TOKEN_TAGS
+        );
+        
+        let tag_index_page = &mut ctx.accounts.tag_index_page;
+        require_with_context!(
+            (tag_index_page.entries.len() as u64) < TOKEN_INDEX_PAGE_SIZE,
+            TokenFactoryError::IndexPageFull,
+            "instruction=tag_token token_id={} tag={} page={}",
+            token_data.token_id,
+            tag,
+            page
+        );
+        if tag_index_page.factory == Pubkey::default() {
+            tag_index_page.factory = token_data.factory;
+            tag_index_page.tag = tag;
+            tag_index_page.page = page;
+            tag_index_page.bump = *ctx.bumps.get("tag_index_page").unwrap();
+            tag_index_page.account_version = ACCOUNT_VERSION;
+        }
+        tag_index_page.entries.push(token_data.key());
+        token_data.tags[slot.unwrap()] = tag;
+        
+        emit!(TokenTaggedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            tag,
+            page,
+            tagged: true,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    pub fn untag_token(ctx: Context<UntagToken>, tag: u8, page: u64) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=untag_token token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            authority.key(),
+            token_data.authority
+        );
+        
+        let tag_index_page = &mut ctx.accounts.tag_index_page;
+        let slot = token_data.tags.iter().position(|existing| tag != 0 && *existing == tag);
+        let entry = tag_index_page
+            .entries
+            .iter()
+            .position(|entry| *entry == token_data.key());
+        require_with_context!(
+            slot.is_some() && entry.is_some(),
+            TokenFactoryError::InvalidTag,
+            "instruction=untag_token token_id={} tag={} page={}",
+            token_data.token_id,
+            tag,
+            page
+        );
+        
+        tag_index_page.entries.swap_remove(entry.unwrap());
+        token_data.tags[slot.unwrap()] = 0;
+        
+        emit!(TokenTaggedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            tag,
+            page,
+            tagged: false,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    pub fn bridge_out(
+        ctx: Context<BridgeOut>,
+        amount: u64,
+        target_chain: u16,
+        recipient: UniversalAddress,
+    ) -> Result<()> {
+        let chain_supply_bump = *ctx.bumps.get("chain_supply").unwrap();
+        ctx.accounts.process(amount, target_chain, recipient, chain_supply_bump)
+    }
+
+    pub fn configure_bridge_cap(ctx: Context<ConfigureBondingCurve>, daily_cap: u64) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let authority = &ctx.accounts.authority;
+        let now = Clock::get()?.unix_timestamp;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_bridge_cap token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            authority.key(),
+            token_data.authority
+        );
+        
+        // Lowering the cap (or capping an uncapped token) is immediate; raising it is delayed
+        // so a compromised creator key cannot lift the cap and drain in one go
+        let limits = &mut token_data.bridge_limits;
+        let current_cap = limits.effective_daily_cap(now);
+        let is_increase = current_cap != 0 && (daily_cap == 0 || daily_cap > current_cap);
+        let effective_ts = if is_increase {
+            limits.daily_cap = current_cap;
+            limits.pending_daily_cap = daily_cap;
+            limits.pending_cap_effective_ts = now
+                .checked_add(BRIDGE_CAP_INCREASE_DELAY)
+                .ok_or(TokenFactoryError::MathOverflow)?;
+            limits.pending_cap_effective_ts
+        } else {
+            limits.daily_cap = daily_cap;
+            limits.pending_daily_cap = 0;
+            limits.pending_cap_effective_ts = 0;
+            now
+        };
+        
+        emit!(BridgeCapConfiguredEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            daily_cap,
+            effective_ts,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    pub fn add_supported_chain(ctx: Context<EnableCrossChain>, chain_id: u16) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=add_supported_chain token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            authority.key(),
+            token_data.authority
+        );
+        require_with_context!(
+            token_data.cross_chain_enabled,
+            TokenFactoryError::CrossChainNotEnabled,
+            "instruction=add_supported_chain token_id={} chain_id={}",
+            token_data.token_id,
+            chain_id
+        );
+        require_with_context!(
+            token_data.cross_chain_info.supported_chains.insert(chain_id),
+            TokenFactoryError::UnsupportedChain,
+            "instruction=add_supported_chain token_id={} chain_id={} max_chain_id={}",
+            token_data.token_id,
+            chain_id,
+            MAX_CHAIN_ID
+        );
+        
+        emit!(SupportedChainUpdatedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            chain_id,
+            supported: true,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    pub fn remove_supported_chain(ctx: Context<EnableCrossChain>, chain_id: u16) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=remove_supported_chain token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            authority.key(),
+            token_data.authority
+        );
+        require_with_context!(
+            token_data.cross_chain_info.supported_chains.contains(chain_id),
+            TokenFactoryError::UnsupportedChain,
+            "instruction=remove_supported_chain token_id={} chain_id={}",
+            token_data.token_id,
+            chain_id
+        );
+        
+        token_data.cross_chain_info.supported_chains.remove(chain_id);
+        
+        emit!(SupportedChainUpdatedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            chain_id,
+            supported: false,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    pub fn disable_cross_chain(ctx: Context<EnableCrossChain>) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=disable_cross_chain token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            authority.key(),
+            token_data.authority
+        );
+        require_with_context!(
+            token_data.cross_chain_enabled,
+            TokenFactoryError::CrossChainNotEnabled,
+            "instruction=disable_cross_chain token_id={}",
+            token_data.token_id
+        );
+        
+        // Emitter and chains are kept so enable_cross_chain can restore or replace them
+        token_data.cross_chain_enabled = false;
+        
+        emit!(CrossChainDisabledEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    pub fn configure_bridge_fee(ctx: Context<ConfigureTreasury>, bridge_fee_lamports: u64) -> Result<()> {
+        let token_factory = &mut ctx.accounts.token_factory;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority
+        require_with_context!(
+            token_factory.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_bridge_fee signer={} expected_authority={}",
+            authority.key(),
+            token_factory.authority
+        );
+        require_with_context!(
+            bridge_fee_lamports <= MAX_BRIDGE_FEE_LAMPORTS,
+            TokenFactoryError::FeeTooHigh,
+            "instruction=configure_bridge_fee bridge_fee_lamports={} max={}",
+            bridge_fee_lamports,
+            MAX_BRIDGE_FEE_LAMPORTS
+        );
+        
+        record_admin_action(
+            &ctx.accounts.admin_log,
+            authority.key(),
+            ADMIN_ACTION_BRIDGE_FEE,
+            0,
+            Pubkey::default(),
+            admin_value(&[token_factory.bridge_fee_lamports]),
+            admin_value(&[bridge_fee_lamports]),
+        )?;
+        token_factory.bridge_fee_lamports = bridge_fee_lamports;
+        
+        emit!(BridgeFeeConfiguredEvent {
+            bridge_fee_lamports,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    pub fn configure_verifier(ctx: Context<ConfigureTreasury>, verifier: Pubkey) -> Result<()> {
+        let token_factory = &mut ctx.accounts.token_factory;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority
+        require_with_context!(
+            token_factory.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_verifier signer={} expected_authority={}",
+            authority.key(),
+            token_factory.authority
+        );
+        
+        record_admin_action(
+            &ctx.accounts.admin_log,
+            authority.key(),
+            ADMIN_ACTION_VERIFIER,
+            0,
+            Pubkey::default(),
+            token_factory.verifier.to_bytes(),
+            verifier.to_bytes(),
+        )?;
+        token_factory.verifier = verifier;
+        
+        emit!(VerifierConfiguredEvent {
+            verifier,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    pub fn configure_price_attestor(ctx: Context<ConfigureTreasury>, price_attestor: Pubkey) -> Result<()> {
+        let token_factory = &mut ctx.accounts.token_factory;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority
+        require_with_context!(
+            token_factory.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_price_attestor signer={} expected_authority={}",
+            authority.key(),
+            token_factory.authority
+        );
+        
+        record_admin_action(
+            &ctx.accounts.admin_log,
+            authority.key(),
+            ADMIN_ACTION_PRICE_ATTESTOR,
+            0,
+            Pubkey::default(),
+            token_factory.price_attestor.to_bytes(),
+            price_attestor.to_bytes(),
+        )?;
+        token_factory.price_attestor = price_attestor;
+        
+        emit!(PriceAttestorConfiguredEvent {
+            price_attestor,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    pub fn configure_symbol_registry(
+        ctx: Context<ConfigureTreasury>,
+        require_unique_symbols: bool,
+        symbol_claim_fee_lamports: u64,
+    ) -> Result<()> {
+        let token_factory = &mut ctx.accounts.token_factory;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority
+        require_with_context!(
+            token_factory.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_symbol_registry signer={} expected_authority={}",
+            authority.key(),
+            token_factory.authority
+        );
+        
+        record_admin_action(
+            &ctx.accounts.admin_log,
+            authority.key(),
+            ADMIN_ACTION_SYMBOL_REGISTRY,
+            0,
+            Pubkey::default(),
+            admin_value(&[token_factory.require_unique_symbols as u64, token_factory.symbol_claim_fee_lamports]),
+            admin_value(&[require_unique_symbols as u64, symbol_claim_fee_lamports]),
+        )?;
+        token_factory.require_unique_symbols = require_unique_symbols;
+        token_factory.symbol_claim_fee_lamports = symbol_claim_fee_lamports;
+        
+        emit!(SymbolRegistryConfiguredEvent {
+            require_unique_symbols,
+            symbol_claim_fee_lamports,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    pub fn configure_creation_gate(ctx: Context<ConfigureTreasury>, required_collection: Pubkey) -> Result<()> {
+        let token_factory = &mut ctx.accounts.token_factory;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority
+        require_with_context!(
+            token_factory.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_creation_gate signer={} expected_authority={}",
+            authority.key(),
+            token_factory.authority
+        );
+        
+        record_admin_action(
+            &ctx.accounts.admin_log,
+            authority.key(),
+            ADMIN_ACTION_CREATION_GATE,
+            0,
+            Pubkey::default(),
+            token_factory.required_collection.to_bytes(),
+            required_collection.to_bytes(),
+        )?;
+        token_factory.required_collection = required_collection;
+        
+        emit!(CreationGateConfiguredEvent {
+            required_collection,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    // Takes subsystems offline, or back online, for every token at once
+    pub fn configure_features(ctx: Context<ConfigureTreasury>, disabled_features: u64) -> Result<()> {
+        let token_factory = &mut ctx.accounts.token_factory;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority
+        require_with_context!(
+            token_factory.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_features signer={} expected_authority={}",
+            authority.key(),
+            token_factory.authority
+        );
+        require_with_context!(
+            disabled_features & !ALL_FEATURES == 0,
+            TokenFactoryError::InvalidFeatures,
+            "instruction=configure_features disabled_features={:#x} known_features={:#x}",
+            disabled_features,
+            ALL_FEATURES
+        );
+        
+        record_admin_action(
+            &ctx.accounts.admin_log,
+            authority.key(),
+            ADMIN_ACTION_FEATURES,
+            0,
+            Pubkey::default(),
+            admin_value(&[token_factory.disabled_features]),
+            admin_value(&[disabled_features]),
+        )?;
+        token_factory.disabled_features = disabled_features;
+        
+        emit!(FeaturesConfiguredEvent {
+            disabled_features,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    pub fn claim_symbol(ctx: Context<ClaimSymbol>, symbol: String) -> Result<()> {
+        let bump = *ctx.bumps.get("symbol_registry").unwrap();
+        ctx.accounts.process(bump, symbol)
+    }
+
+    pub fn configure_chain(
+        ctx: Context<ConfigureChain>,
+        chain_id: u16,
+        fee_params: DeliveryFeeParams,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("chain_config").unwrap();
+        ctx.accounts.process(bump, chain_id, fee_params)
+    }
+
+    pub fn configure_chain_finality(
+        ctx: Context<ConfigureChainFinality>,
+        chain_id: u16,
+        min_source_confirmation: u32,
+        max_staleness: u32,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("chain_finality").unwrap();
+        ctx.accounts.process(bump, chain_id, min_source_confirmation, max_staleness)
+    }
+
+    pub fn configure_emitter_policy(
+        ctx: Context<ConfigureEmitterPolicy>,
+        chain_id: u16,
+        emitter: UniversalAddress,
+        allowed_message_types: u64,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("emitter_policy").unwrap();
+        ctx.accounts.process(bump, chain_id, emitter, allowed_message_types)
+    }
+
+    pub fn quote_delivery_fee(
+        ctx: Context<QuoteDeliveryFee>,
+        chain_id: u16,
+        payload_len: u32,
+    ) -> Result<u64> {
+        ctx.accounts.process(chain_id, payload_len)
+    }
+
+    pub fn redeem_transfer(ctx: Context<RedeemTransfer>) -> Result<()> {
+        let bump = *ctx.bumps.get("received_message").unwrap();
+        ctx.accounts.process(bump)
+    }
+
+    pub fn claim_escrowed_transfer(ctx: Context<ClaimEscrowedTransfer>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn receive_curve_update(ctx: Context<ReceiveCurveUpdate>) -> Result<()> {
+        let bump = *ctx.bumps.get("mirrored_curve").unwrap();
+        let nonce_bump = *ctx.bumps.get("price_nonce").unwrap();
+        ctx.accounts.process(bump, nonce_bump)
+    }
+
+    pub fn submit_attested_price(
+        ctx: Context<SubmitAttestedPrice>,
+        chain_id: u16,
+        update: PriceUpdatePayload,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("mirrored_curve").unwrap();
+        let nonce_bump = *ctx.bumps.get("price_nonce").unwrap();
+        ctx.accounts.process(bump, nonce_bump, chain_id, update)
+    }
+
+    pub fn initialize_custody(ctx: Context<InitializeCustody>) -> Result<()> {
+        let bump = *ctx.bumps.get("mint_authority").unwrap();
+        ctx.accounts.process(bump)
+    }
+
+    pub fn proof_of_reserve<'info>(
+        ctx: Context<'_, '_, '_, 'info, ProofOfReserve<'info>>,
+    ) -> Result<ReserveReport> {
+        ctx.accounts.process(ctx.remaining_accounts)
+    }
+
+    pub fn attest_token(ctx: Context<AttestToken>, nonce: u32) -> Result<()> {
+        ctx.accounts.process(nonce)
+    }
+
+    pub fn record_wrapped_asset(
+        ctx: Context<RecordWrappedAsset>,
+        chain_id: u16,
+        wrapped_address: UniversalAddress,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("wrapped_asset").unwrap();
+        ctx.accounts.process(bump, chain_id, wrapped_address)
+    }
+
+    pub fn publish_batch_sync<'info>(
+        ctx: Context<'_, '_, '_, 'info, PublishBatchSync<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.process(ctx.remaining_accounts)
+    }
+
+    pub fn configure_sync_schedule(
+        ctx: Context<ConfigureTreasury>,
+        sync_interval: i64,
+        keeper_reward_lamports: u64,
+    ) -> Result<()> {
+        let token_factory = &mut ctx.accounts.token_factory;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority
+        require_with_context!(
+            token_factory.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_sync_schedule signer={} expected_authority={}",
+            authority.key(),
+            token_factory.authority
+        );
+        require_with_context!(
+            sync_interval >= 0 && keeper_reward_lamports <= MAX_KEEPER_REWARD_LAMPORTS,
+            TokenFactoryError::FeeTooHigh,
+            "instruction=configure_sync_schedule sync_interval={} keeper_reward_lamports={} max={}",
+            sync_interval,
+            keeper_reward_lamports,
+            MAX_KEEPER_REWARD_LAMPORTS
+        );
+        
+        record_admin_action(
+            &ctx.accounts.admin_log,
+            authority.key(),
+            ADMIN_ACTION_SYNC_SCHEDULE,
+            0,
+            Pubkey::default(),
+            admin_value(&[token_factory.sync_interval as u64, token_factory.keeper_reward_lamports]),
+            admin_value(&[sync_interval as u64, keeper_reward_lamports]),
+        )?;
+        token_factory.sync_interval = sync_interval;
+        token_factory.keeper_reward_lamports = keeper_reward_lamports;
+        
+        emit!(SyncScheduleConfiguredEvent {
+            sync_interval,
+            keeper_reward_lamports,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    pub fn crank_sync<'info>(ctx: Context<'_, '_, '_, 'info, CrankSync<'info>>) -> Result<()> {
+        let keeper_vault_bump = *ctx.bumps.get("keeper_vault").unwrap();
+        ctx.accounts.process(keeper_vault_bump, ctx.remaining_accounts)
+    }
+
+    // Marks a token's creator as verified, or revokes an earlier attestation
+    pub fn attest_creator(ctx: Context<AttestCreator>, verified: bool) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let now = Clock::get()?.unix_timestamp;
+        
+        token_data.creator_verified = verified;
+        token_data.creator_verified_at = if verified { now } else { 0 };
+        
+        emit!(CreatorAttestedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            creator: token_data.authority,
+            verifier: ctx.accounts.verifier.key(),
+            verified,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    pub fn verify_solvency(ctx: Context<VerifySolvency>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn boost_reserve(ctx: Context<BoostReserve>, amount: u64) -> Result<()> {
+        ctx.accounts.process(amount)
+    }
+
+    // Queues a withdrawal of excess reserve; an amount of 0 cancels the pending one
+    pub fn request_reserve_withdrawal(ctx: Context<ConfigureBondingCurve>, amount: u64) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let authority = &ctx.accounts.authority;
+        let now = Clock::get()?.unix_timestamp;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=request_reserve_withdrawal token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            authority.key(),
+            token_data.authority
+        );
+        
+        let available_at = if amount == 0 {
+            0
+        } else {
+            now.checked_add(RESERVE_WITHDRAWAL_DELAY).ok_or(TokenFactoryError::MathOverflow)?
+        };
+        token_data.pending_reserve_withdrawal = PendingReserveWithdrawal { amount, available_at };
+        
+        emit!(ReserveWithdrawalRequestedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            amount,
+            available_at,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    pub fn withdraw_excess_reserve(ctx: Context<WithdrawExcessReserve>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn configure_solvency_guard(ctx: Context<ConfigureBondingCurve>, pause_on_insolvency: bool) -> Result<()> {
+        let token_data = &mut ctx.accounts.token_data;
+        let authority = &ctx.accounts.authority;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_solvency_guard token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            authority.key(),
+            token_data.authority
+        );
+        
+        token_data.bonding_curve.pause_on_insolvency = pause_on_insolvency;
+        
+        emit!(SolvencyGuardConfiguredEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            pause_on_insolvency,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    pub fn grant_delegation(
+        ctx: Context<GrantDelegation>,
+        delegate: Pubkey,
+        capabilities: u8,
+        expires_at: i64,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("delegation").unwrap();
+        ctx.accounts.process(bump, delegate, capabilities, expires_at)
+    }
+
+    pub fn revoke_delegation(ctx: Context<RevokeDelegation>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn create_session(
+        ctx: Context<CreateSession>,
+        session_key: Pubkey,
+        mint: Pubkey,
+        max_quote_spend: u64,
+        max_tokens_sold: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("session").unwrap();
+        ctx.accounts.process(bump, session_key, mint, max_quote_spend, max_tokens_sold, expires_at)
+    }
+
+    pub fn close_session(ctx: Context<CloseSession>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn open_trade_history(ctx: Context<OpenTradeHistory>) -> Result<()> {
+        let bump = *ctx.bumps.get("trade_history").unwrap();
+        ctx.accounts.process(bump)
+    }
+
+    pub fn close_trade_history(ctx: Context<CloseTradeHistory>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn migrate_token_data(ctx: Context<MigrateTokenData>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn create_lookup_table(ctx: Context<CreateLookupTable>, recent_slot: u64) -> Result<()> {
+        let bump = *ctx.bumps.get("lookup_table_authority").unwrap();
+        ctx.accounts.process(bump, recent_slot)
+    }
+
+    pub fn extend_lookup_table(ctx: Context<ExtendLookupTable>, addresses: Vec<Pubkey>) -> Result<()> {
+        let bump = *ctx.bumps.get("lookup_table_authority").unwrap();
+        ctx.accounts.process(bump, addresses)
+    }
+
+    pub fn register_existing_token(
+        ctx: Context<RegisterExistingToken>,
+        name: String,
+        symbol: String,
+        metadata_uri: String,
+    ) -> Result<()> {
+        let token_stats_bump = *ctx.bumps.get("token_stats").unwrap();
+        let token_index_page_bump = *ctx.bumps.get("token_index_page").unwrap();
+        ctx.accounts.process(token_stats_bump, token_index_page_bump, name, symbol, metadata_uri)
+    }
+
+    pub fn close_expired_session(ctx: Context<CloseExpiredSession>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn close_expired_delegation(ctx: Context<CloseExpiredDelegation>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn close_stale_buyer_record(ctx: Context<CloseStaleBuyerRecord>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn record_upgrade_authority(ctx: Context<RecordUpgradeAuthority>) -> Result<()> {
+        let bump = *ctx.bumps.get("upgrade_authority_record").unwrap();
+        ctx.accounts.process(bump)
+    }
+
+    pub fn renounce_upgrade_authority(ctx: Context<RenounceUpgradeAuthority>) -> Result<()> {
+        ctx.accounts.process()
+    }
+
+    pub fn configure_launch_certificates(ctx: Context<ConfigureLaunchCertificates>, enabled: bool) -> Result<()> {
+        let bump = *ctx.bumps.get("certificate_config").unwrap();
+        ctx.accounts.process(bump, enabled)
+    }
+
+    pub fn mint_launch_certificate(ctx: Context<MintLaunchCertificate>) -> Result<()> {
+        let bump = *ctx.bumps.get("launch_certificate").unwrap();
+        ctx.accounts.process(bump)
+    }
+
+    pub fn execute_remote_buy(ctx: Context<ExecuteRemoteBuy>) -> Result<()> {
+        let bump = *ctx.bumps.get("received_message").unwrap();
+        ctx.accounts.process(bump)
+    }
+
+    pub fn execute_remote_sell(ctx: Context<ExecuteRemoteSell>, seller: Pubkey) -> Result<()> {
+        let bump = *ctx.bumps.get("received_message").unwrap();
+        ctx.accounts.process(bump, seller)
+    }
+
+    pub fn bridge_remote_proceeds(ctx: Context<BridgeRemoteProceeds>, nonce: u32) -> Result<()> {
+        ctx.accounts.process(nonce)
+    }
+
+    pub fn open_admin_log(ctx: Context<OpenAdminLog>) -> Result<()> {
+        let bump = *ctx.bumps.get("admin_log").unwrap();
+        ctx.accounts.process(bump)
+    }
+
+    pub fn fund_supply_vault(ctx: Context<FundSupplyVault>, amount: u64) -> Result<()> {
+        let mint_authority_bump = *ctx.bumps.get("mint_authority").unwrap();
+        ctx.accounts.process(mint_authority_bump, amount)
+    }
+
+    pub fn configure_quote_mint(
+        ctx: Context<ConfigureQuoteMint>,
+        expected_decimals: u8,
+        allowed: bool,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("allowed_quote_mint").unwrap();
+        ctx.accounts.process(bump, expected_decimals, allowed)
+    }
+
+    pub fn configure_price_guard(
+        ctx: Context<ConfigurePriceGuard>,
+        chain_id: u16,
+        max_deviation_bps: u16,
+        window: u32,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("price_guard").unwrap();
+        ctx.accounts.process(bump, chain_id, max_deviation_bps, window)
+    }
+
+    pub fn approve_price_reset(ctx: Context<ApprovePriceReset>, chain_id: u16, valid_for: i64) -> Result<()> {
+        let bump = *ctx.bumps.get("price_reset").unwrap();
+        ctx.accounts.process(bump, chain_id, valid_for)
+    }
+
+    pub fn register_referral_code(ctx: Context<RegisterReferralCode>, code: String) -> Result<()> {
+        let bump = *ctx.bumps.get("referral_code").unwrap();
+        ctx.accounts.process(bump, code)
+    }
+
+    pub fn open_referral_stats(ctx: Context<OpenReferralStats>) -> Result<()> {
+        let bump = *ctx.bumps.get("referral_stats").unwrap();
+        ctx.accounts.process(bump)
+    }
+
+    pub fn create_vesting_vault(
+        ctx: Context<CreateVestingVault>,
+        beneficiary: Pubkey,
+        amount: u64,
+        schedule: VestingSchedule,
+    ) -> Result<()> {
+        let bump = *ctx.bumps.get("vesting_vault").unwrap();
+        ctx.accounts.process(bump, beneficiary, amount, schedule)
+    }
+
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        ctx.accounts.process()
+    }
+}
+
+// Price of `amount` base units starting at `supply` base units under a configured curve.
+// A trade pays the area under the curve between the two supplies, so splitting a trade up never
+// changes its total and selling what was just bought returns exactly what it cost.
+pub fn quote_bonding_curve(bonding_curve: &BondingCurve, decimals: u8, supply: u64, amount: u64) -> Result<u64> {
+    crossify_common::quote(&bonding_curve.params(), decimals, supply, amount).map_err(curve_error)
+}
+
+// Curve parameters price every holder's exit, so they are fixed once anyone holds curve supply,
+// and while a reserve withdrawal sized against the current parameters is pending
+pub fn require_curve_parameters_mutable(token_data: &TokenData, curve_supply: u64, instruction: &str) -> Result<()> {
+    require_with_context!(
+        !token_data.bonding_curve.trading_started && curve_supply == 0,
+        TokenFactoryError::TradingAlreadyStarted,
+        "instruction={} token_id={} curve_supply={}",
+        instruction,
+        token_data.token_id,
+        curve_supply
+    );
+    require_with_context!(
+        token_data.pending_reserve_withdrawal.amount == 0,
+        TokenFactoryError::ReserveWithdrawalPending,
+        "instruction={} token_id={} pending_withdrawal={}",
+        instruction,
+        token_data.token_id,
+        token_data.pending_reserve_withdrawal.amount
+    );
+    Ok(())
+}
+
+// spl-governance account types of governance accounts, in both the V1 and V2 layouts
+pub const SPL_GOVERNANCE_ACCOUNT_TYPES: [u8; 8] = [3, 4, 9, 10, 18, 19, 20, 21];
+
+// Realm a governance account belongs to. Governance accounts start with their account type followed
+// by the Realm; Realms, token owner records and proposals share the prefix, so the type is checked.
+pub fn governance_realm(data: &[u8]) -> Option<Pubkey> {
+    if !SPL_GOVERNANCE_ACCOUNT_TYPES.contains(data.first()?) {
+        return None;
+    }
+    Pubkey::try_from(data.get(1..33)?).ok()
+}
+
+// Quote paid to take the curve from zero to `supply` base units; never decreases as `supply` grows
+pub fn curve_area(bonding_curve: &BondingCurve, decimals: u8, supply: u64) -> Result<u128> {
+    crossify_common::curve_area(&bonding_curve.params(), decimals, supply).map_err(curve_error)
+}
+
+pub fn curve_error(err: CurveError) -> Error {
+    match err {
+        CurveError::MathOverflow => TokenFactoryError::MathOverflow.into(),
+        CurveError::InvalidCurveType { .. } => {
+            msg!("{}", err);
+            TokenFactoryError::InvalidCurveType.into()
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + size_of::<TokenFactory>(),
+        seeds = [b"token_factory"],
+        bump,
+    )]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    // Only the upgrade authority may create the singleton, so nobody can front-run the deployer
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::TokenFactory>,
+    
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key())
+            @ TokenFactoryError::InvalidAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    /// CHECK: Core Bridge this build targets; must be deployed on the cluster being initialized
+    #[account(
+        address = wormhole_core_bridge::ID,
+        constraint = core_bridge_program.executable @ TokenFactoryError::BridgeNotDeployed
+    )]
+    pub core_bridge_program: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureTreasury<'info> {
+    #[account(mut, seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    pub authority: Signer<'info>,
+    
+    /// CHECK: Factory admin log, checked against its derived address; skipped until opened
+    #[account(mut, seeds = [b"admin_log", token_factory.key().as_ref()], bump)]
+    pub admin_log: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct HandAuthorityToRealm<'info> {
+    #[account(mut, seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    /// CHECK: Governance account of the Realm; ownership by spl-governance is enforced
+    #[account(owner = spl_governance::ID)]
+    pub governance: UncheckedAccount<'info>,
+    
+    /// CHECK: Native treasury PDA of the governance, derived under spl-governance
+    #[account(
+        seeds = [b"native-treasury", governance.key().as_ref()],
+        bump,
+        seeds::program = spl_governance::ID,
+    )]
+    pub native_treasury: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+    
+    /// CHECK: Factory admin log, checked against its derived address; skipped until opened
+    #[account(mut, seeds = [b"admin_log", token_factory.key().as_ref()], bump)]
+    pub admin_log: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String, symbol: String, decimals: u8, metadata_uri: String)]
+pub struct CreateToken<'info> {
+    #[account(mut, seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = TokenData::space_for(name.len(), symbol.len(), metadata_uri.len()),
+    )]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = decimals,
+        mint::authority = authority.key(),
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + size_of::<TokenStats>(),
+        seeds = [b"token_stats", mint.key().as_ref()],
+        bump,
+    )]
+    pub token_stats: Account<'info, TokenStats>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = authority,
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+    
+    // The first token of every page pays for the page
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + TokenIndexPage::LEN,
+        seeds = [
+            b"token_index",
+            token_factory.key().as_ref(),
+            &(token_factory.token_count / TOKEN_INDEX_PAGE_SIZE).to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub token_index_page: Account<'info, TokenIndexPage>,
+    
+    // Creator's claim on the symbol; mandatory when the factory requires unique symbols
+    #[account(mut)]
+    pub symbol_registry: Option<Account<'info, SymbolRegistry>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
+    
+    // An NFT of the factory's required collection held by the creator, and its Metaplex metadata;
+    // mandatory while creation is gated
+    pub collection_nft_account: Option<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Address, owner and contents are checked against collection_nft_account in process
+    pub collection_nft_metadata: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct EnableCrossChain<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureBondingCurve<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureCurveParameters<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        seeds = [b"token_stats", token_data.mint.as_ref()],
+        bump = token_stats.bump,
+    )]
+    pub token_stats: Account<'info, TokenStats>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetBondingCurveEnabled<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    // Token authority, or a delegate pausing under `delegation`
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    pub delegation: Option<Account<'info, Delegation>>,
 }
 
 #[derive(Accounts)]
-pub struct EnableCrossChain<'info> {
+pub struct CalculatePrice<'info> {
+    pub token_data: Account<'info, TokenData>,
+}
+
+#[derive(Accounts)]
+pub struct SendCrossChainMessage<'info> {
+    #[account(address = token_data.factory, seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    pub token_data: Account<'info, TokenData>,
+    
+    /// CHECK: Receives the protocol fee in lamports; checked against the factory treasury
+    #[account(mut, address = token_factory.treasury @ TokenFactoryError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+    
+    // Token authority, or a delegate publishing under `delegation`
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    pub delegation: Option<Account<'info, Delegation>>,
+}
+
+#[derive(Accounts)]
+pub struct BurnTokens<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_stats", token_data.mint.as_ref()],
+        bump = token_stats.bump,
+    )]
+    pub token_stats: Account<'info, TokenStats>,
+    
+    #[account(mut, address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+    
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct DelegateMintAuthority<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(mut, address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA that becomes the mint authority; it holds no data
+    #[account(seeds = [b"mint_authority", mint.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureEmissions<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA mint authority, only its address is compared
+    #[account(seeds = [b"mint_authority", mint.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CrankEmission<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(mut, address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA mint authority, signs the mint CPI
+    #[account(
+        seeds = [b"mint_authority", mint.key().as_ref()],
+        bump = token_data.mint_authority_bump,
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        constraint = recipient_token_account.owner == token_data.emission_schedule.recipient
+            @ TokenFactoryError::InvalidEmissionRecipient,
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(tag: u8, page: u64)]
+pub struct TagToken<'info> {
     #[account(mut)]
     pub token_data: Account<'info, TokenData>,
     
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + TagIndexPage::LEN,
+        seeds = [b"tag_index", token_data.factory.as_ref(), &[tag], &page.to_le_bytes()],
+        bump,
+    )]
+    pub tag_index_page: Account<'info, TagIndexPage>,
+    
     #[account(mut)]
     pub authority: Signer<'info>,
     
@@ -295,110 +2818,499 @@ pub struct EnableCrossChain<'info> {
 }
 
 #[derive(Accounts)]
-pub struct ConfigureBondingCurve<'info> {
+#[instruction(tag: u8, page: u64)]
+pub struct UntagToken<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        mut,
+        seeds = [b"tag_index", token_data.factory.as_ref(), &[tag], &page.to_le_bytes()],
+        bump = tag_index_page.bump,
+    )]
+    pub tag_index_page: Account<'info, TagIndexPage>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AttestCreator<'info> {
+    #[account(address = token_data.factory, seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
     #[account(mut)]
     pub token_data: Account<'info, TokenData>,
     
+    #[account(address = token_factory.verifier @ TokenFactoryError::InvalidVerifier)]
+    pub verifier: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String, metadata_uri: String)]
+pub struct UpdateTokenMetadata<'info> {
+    // Grows with longer strings, never shrinks
+    #[account(
+        mut,
+        realloc = TokenData::space_for(name.len(), token_data.symbol.len(), metadata_uri.len())
+            .max(token_data.to_account_info().data_len()),
+        realloc::payer = authority,
+        realloc::zero = false,
+    )]
+    pub token_data: Account<'info, TokenData>,
+    
     #[account(mut)]
     pub authority: Signer<'info>,
     
     pub system_program: Program<'info, System>,
 }
 
-#[derive(Accounts)]
-pub struct CalculatePrice<'info> {
-    pub token_data: Account<'info, TokenData>,
+#[account]
+pub struct TokenFactory {
+    pub authority: Pubkey,
+    pub token_count: u64,
+    pub treasury: Pubkey,
+    pub otc_fee_bps: u16,
+    pub bridge_fee_lamports: u64, // Charged on outbound bridge messages, paid to the treasury
+    pub sync_interval: i64, // Minimum age of a token's last sync before crank_sync, 0 = off
+    pub keeper_reward_lamports: u64, // Paid per synced token from the keeper vault
+    pub verifier: Pubkey, // Key allowed to attest token creators, default = attestations off
+    pub require_unique_symbols: bool, // create_token must present the creator's SymbolRegistry claim
+    pub symbol_claim_fee_lamports: u64,
+    pub version: u8,
+    pub lookup_table: Pubkey, // Address lookup table for v0 transactions, default until created
+    pub price_attestor: Pubkey, // ed25519 key signing prices for chains without Wormhole, default = off
+    pub required_collection: Pubkey, // Verified collection create_token requires an NFT of, default = open
+    pub disabled_features: u64, // FEATURE_* bits switched off by the authority, 0 = everything on
+}
+
+#[account]
+pub struct TokenData {
+    pub factory: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub metadata_uri: String,
+    pub authority: Pubkey,
+    pub initial_supply: u64,
+    pub token_id: u64,
+    pub cross_chain_enabled: bool,
+    pub cross_chain_info: CrossChainInfo,
+    pub bonding_curve: BondingCurve,
+    pub mint_authority_bump: u8,
+    pub emission_schedule: EmissionSchedule,
+    pub governance: GovernanceConfig,
+    pub sell_limits: SellLimits,
+    pub refund_policy: RefundPolicy,
+    pub tags: [u8; MAX_TOKEN_TAGS], // Creator-set categories (meme, game, RWA, ...), 0 = unused
+    pub bridge_limits: BridgeLimits,
+    pub block_round_trips: bool, // Reject transactions that both buy and sell this mint
+    pub creator_verified: bool, // Attested by the factory verifier
+    pub creator_verified_at: i64,
+    pub metadata_frozen: bool, // Name and metadata URI can no longer change
+    pub keep_metadata_mutable: bool, // Creator opted out of freezing metadata on graduation
+    pub pending_reserve_withdrawal: PendingReserveWithdrawal,
+    pub version: u8, // Layout version; later fields are appended after it and must decode from zeroes
+    pub imported: bool, // Registered from an existing mint rather than created by the factory
+    pub bridge_mode: u8, // BRIDGE_MODE_*, 0 for tokens enabled before modes existed
+    pub liquidity_locked: bool, // Graduated pool LP tokens were locked or burned
+    pub liquidity_lock: LiquidityLock,
+    pub created_slot: u64, // 0 for tokens created before version 5
+    pub supply_from_vault: bool, // Imported with a revoked mint authority; curve buys draw from the supply vault
+}
+
+impl TokenData {
+    pub const SPACE: usize = 8 + size_of::<TokenData>() + 256; // Extra space for strings
+    
+    // Account size holding strings of these lengths, never less than SPACE
+    pub fn space_for(name_len: usize, symbol_len: usize, metadata_uri_len: usize) -> usize {
+        Self::SPACE + (name_len + symbol_len + metadata_uri_len).saturating_sub(256)
+    }
+}
+
+/// Per-token supply accounting kept by the factory, derived from the mint
+#[account]
+pub struct TokenStats {
+    pub mint: Pubkey,
+    pub token_id: u64,
+    pub total_burned: u64,
+    pub snapshot_count: u64,
+    pub proposal_count: u64,
+    pub curve_supply: u64, // Tokens minted through the curve and not sold back
+    pub total_volume: u64, // Quote tokens traded through the curve
+    pub bridge_window_start_ts: i64, // Last outbound transfer, where `bridged_in_window` was counted
+    pub bridged_in_window: u64, // Outbound volume still counted against the daily cap; see decayed_bridge_volume
+    pub total_bridged_out: u64,
+    pub total_bridged_in: u64,
+    pub last_synced_reserve: u64, // Reserve balance published in the last liquidity update
+    pub last_sync_ts: i64,
+    pub total_boosted: u64, // Quote tokens deposited into the reserve without minting
+    pub bump: u8,
+    pub version: u8,
+    pub curve_holders: u64, // Wallets with a positive net curve position; ignores plain transfers
+    pub last_sync_hash: [u8; 32], // Hash of the state published in the last sync
+    pub total_creator_fees: u64, // Sell tax paid into the creator fee vault since version 4
+    pub price_update_nonce: u64, // Nonce of the last price update published by sync
+}
+
+/// Page `page` of the factory's tokens: TokenData addresses for ids page*100..page*100+99
+#[account]
+pub struct TokenIndexPage {
+    pub factory: Pubkey,
+    pub page: u64,
+    pub entries: Vec<Pubkey>,
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+impl TokenIndexPage {
+    pub const LEN: usize = 32 + 8 + 4 + 32 * TOKEN_INDEX_PAGE_SIZE as usize + 1 + 1 + ACCOUNT_RESERVED_LEN;
+}
+
+/// Page `page` of the tokens carrying `tag`; untagging swap-removes, so order is not preserved
+#[account]
+pub struct TagIndexPage {
+    pub factory: Pubkey,
+    pub tag: u8,
+    pub page: u64,
+    pub entries: Vec<Pubkey>,
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+impl TagIndexPage {
+    pub const LEN: usize = 32 + 1 + 8 + 4 + 32 * TOKEN_INDEX_PAGE_SIZE as usize + 1 + 1 + ACCOUNT_RESERVED_LEN;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct CrossChainInfo {
+    pub wormhole_emitter: UniversalAddress,
+    pub supported_chains: ChainSet,
+}
+
+/// Wormhole chain ids 0..=MAX_CHAIN_ID as a fixed-size bitmap
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ChainSet {
+    pub bits: [u64; 4],
+}
+
+impl ChainSet {
+    pub fn contains(&self, chain_id: u16) -> bool {
+        chain_id <= MAX_CHAIN_ID && self.bits[(chain_id / 64) as usize] & (1 << (chain_id % 64)) != 0
+    }
+
+    // Returns false if `chain_id` cannot be represented
+    pub fn insert(&mut self, chain_id: u16) -> bool {
+        if chain_id > MAX_CHAIN_ID {
+            return false;
+        }
+        self.bits[(chain_id / 64) as usize] |= 1 << (chain_id % 64);
+        true
+    }
+
+    pub fn remove(&mut self, chain_id: u16) {
+        if chain_id <= MAX_CHAIN_ID {
+            self.bits[(chain_id / 64) as usize] &= !(1 << (chain_id % 64));
+        }
+    }
+
+    pub fn chain_ids(&self) -> Vec<u16> {
+        (0..=MAX_CHAIN_ID).filter(|chain_id| self.contains(*chain_id)).collect()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct BondingCurve {
+    pub configured: bool,
+    pub enabled: bool,
+    pub curve_type: u8, // 0: Linear, 1: Exponential, 2: Bancor
+    pub base_price: u64,
+    pub slope: u64,
+    pub reserve_ratio: u16, // For Bancor formula, represented as parts per 1000
+    pub quote_mint: Pubkey, // Set once the reserve is initialized
+    pub reserve_authority_bump: u8,
+    pub trading_started: bool,
+    pub trading_start_ts: i64, // Buys are rejected before this time
+    pub sell_tax_bps: u16, // Creator tax on sells, paid to the creator fee vault
+    pub graduation_threshold: u64, // Reserve balance, in quote units, that marks a successful launch
+    pub threshold_reached: bool,
+    pub auction_pending: bool, // Initial Dutch auction is running; curve trading waits for settlement
+    pub pause_on_insolvency: bool, // verify_solvency disables the curve when the reserve falls short
+}
+
+impl BondingCurve {
+    pub fn params(&self) -> crossify_common::CurveParams {
+        crossify_common::CurveParams {
+            curve_type: self.curve_type,
+            base_price: self.base_price,
+            slope: self.slope,
+            reserve_ratio: self.reserve_ratio,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct EmissionSchedule {
+    pub enabled: bool,
+    pub recipient: Pubkey,
+    pub start_ts: i64,
+    pub epoch_duration: i64, // Seconds per epoch
+    pub amount_per_epoch: u64,
+    pub total_epochs: u32,
+    pub epochs_minted: u32,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct SellLimits {
+    pub cooldown_seconds: i64,    // 0 disables the cooldown
+    pub max_sell_per_window: u64, // 0 disables the windowed cap
+    pub window_seconds: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct RefundPolicy {
+    pub enabled: bool,
+    pub deadline: i64, // Graduation threshold must be reached by this time
+    pub failed: bool,
+    pub refund_reserve: u64, // Reserve balance when the launch was locked
+    pub refund_supply: u64,  // Curve supply when the launch was locked
+}
+
+#[event]
+pub struct TokenCreatedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub initial_supply: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CrossChainEnabledEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub wormhole_emitter: UniversalAddress,
+    pub supported_chains: Vec<u16>,
+    pub bridge_mode: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BondingCurveConfiguredEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub curve_type: u8,
+    pub base_price: u64,
+    pub slope: u64,
+    pub reserve_ratio: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BondingCurveToggledEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PriceCalculatedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub supply: u64,
+    pub amount: u64,
+    pub price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CrossChainMessageSentEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub target_chain: u16,
+    pub payload: Vec<u8>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokensBurnedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_burned: u64,
+    pub total_supply: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MintAuthorityDelegatedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub previous_authority: Pubkey,
+    pub mint_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmissionScheduleConfiguredEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub start_ts: i64,
+    pub epoch_duration: i64,
+    pub amount_per_epoch: u64,
+    pub total_epochs: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmissionMintedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub epochs: u32,
+    pub amount: u64,
+    pub epochs_minted: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryConfiguredEvent {
+    pub treasury: Pubkey,
+    pub otc_fee_bps: u16,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct SendCrossChainMessage<'info> {
-    pub token_data: Account<'info, TokenData>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct FactoryAuthorityChangedEvent {
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub realm: Pubkey,
+    pub governance: Pubkey,
+    pub timestamp: i64,
 }
 
-#[account]
-pub struct TokenFactory {
-    pub authority: Pubkey,
-    pub token_count: u64,
+#[event]
+pub struct SellTaxConfiguredEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub sell_tax_bps: u16,
+    pub timestamp: i64,
 }
 
-#[account]
-pub struct TokenData {
+#[event]
+pub struct SellLimitsConfiguredEvent {
+    pub token_id: u64,
     pub mint: Pubkey,
-    pub name: String,
-    pub symbol: String,
-    pub decimals: u8,
-    pub metadata_uri: String,
-    pub authority: Pubkey,
-    pub initial_supply: u64,
+    pub cooldown_seconds: i64,
+    pub max_sell_per_window: u64,
+    pub window_seconds: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoundTripGuardConfiguredEvent {
     pub token_id: u64,
-    pub cross_chain_enabled: bool,
-    pub cross_chain_info: CrossChainInfo,
-    pub bonding_curve: BondingCurve,
+    pub mint: Pubkey,
+    pub block_round_trips: bool,
+    pub timestamp: i64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
-pub struct CrossChainInfo {
-    pub wormhole_emitter: Pubkey,
-    pub supported_chains: Vec<u16>,
+#[event]
+pub struct TradingStartScheduledEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub trading_start_ts: i64,
+    pub timestamp: i64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
-pub struct BondingCurve {
-    pub enabled: bool,
-    pub curve_type: u8, // 0: Linear, 1: Exponential, 2: Bancor
-    pub base_price: u64,
-    pub slope: u64,
-    pub reserve_ratio: u16, // For Bancor formula, represented as parts per 1000
+#[event]
+pub struct RefundableLaunchConfiguredEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub graduation_threshold: u64,
+    pub deadline: i64,
+    pub timestamp: i64,
 }
 
 #[event]
-pub struct TokenCreatedEvent {
+pub struct TokenTaggedEvent {
     pub token_id: u64,
     pub mint: Pubkey,
-    pub name: String,
-    pub symbol: String,
-    pub decimals: u8,
-    pub initial_supply: u64,
+    pub tag: u8,
+    pub page: u64,
+    pub tagged: bool,
+    pub timestamp: i64,
 }
 
 #[event]
-pub struct CrossChainEnabledEvent {
+pub struct SupportedChainUpdatedEvent {
     pub token_id: u64,
     pub mint: Pubkey,
-    pub wormhole_emitter: Pubkey,
-    pub supported_chains: Vec<u16>,
+    pub chain_id: u16,
+    pub supported: bool,
+    pub timestamp: i64,
 }
 
 #[event]
-pub struct BondingCurveConfiguredEvent {
+pub struct CrossChainEmitterUpdatedEvent {
     pub token_id: u64,
     pub mint: Pubkey,
-    pub curve_type: u8,
-    pub base_price: u64,
-    pub slope: u64,
-    pub reserve_ratio: u16,
+    pub previous_emitter: UniversalAddress,
+    pub wormhole_emitter: UniversalAddress,
+    pub timestamp: i64,
 }
 
 #[event]
-pub struct PriceCalculatedEvent {
+pub struct CrossChainDisabledEvent {
     pub token_id: u64,
     pub mint: Pubkey,
-    pub supply: u64,
-    pub amount: u64,
-    pub price: u64,
+    pub timestamp: i64,
 }
 
 #[event]
-pub struct CrossChainMessageSentEvent {
+pub struct VerifierConfiguredEvent {
+    pub verifier: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PriceAttestorConfiguredEvent {
+    pub price_attestor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreatorAttestedEvent {
     pub token_id: u64,
     pub mint: Pubkey,
-    pub target_chain: u16,
-    pub payload: Vec<u8>,
+    pub creator: Pubkey,
+    pub verifier: Pubkey,
+    pub verified: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MetadataFreezeConfiguredEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub keep_metadata_mutable: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenMetadataUpdatedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub name: String,
+    pub metadata_uri: String,
+    pub timestamp: i64,
 }
 
 #[error_code]
@@ -418,6 +3330,615 @@ pub enum TokenFactoryError {
     #[msg("Invalid reserve ratio")]
     InvalidReserveRatio,
     
-    #[msg("Bonding curve not enabled")]
-    BondingCurveNotEnabled,
+    #[msg("Bonding curve has not been configured")]
+    CurveNotConfigured,
+    
+    #[msg("Bonding curve is configured but currently disabled")]
+    CurveDisabled,
+    
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    
+    #[msg("Mint authority has not been delegated to the program")]
+    MintAuthorityNotDelegated,
+    
+    #[msg("Emission schedule is already configured")]
+    EmissionScheduleAlreadySet,
+    
+    #[msg("Emission schedule is not configured")]
+    EmissionScheduleNotSet,
+    
+    #[msg("Invalid emission schedule parameters")]
+    InvalidEmissionSchedule,
+    
+    #[msg("Token account does not belong to the emission recipient")]
+    InvalidEmissionRecipient,
+    
+    #[msg("No emission epoch is due yet")]
+    NoEmissionDue,
+    
+    #[msg("Balance proof does not match the snapshot")]
+    InvalidSnapshotProof,
+    
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+    
+    #[msg("Fee exceeds the allowed maximum")]
+    FeeTooHigh,
+    
+    #[msg("Token was not created by this factory")]
+    ForeignToken,
+    
+    #[msg("Offer must swap two different tokens")]
+    InvalidOffer,
+    
+    #[msg("Token account does not belong to the treasury")]
+    InvalidTreasury,
+    
+    #[msg("Governance is not enabled for this token")]
+    GovernanceNotEnabled,
+    
+    #[msg("Token parameters are controlled by governance")]
+    GovernanceControlled,
+    
+    #[msg("Invalid governance parameters")]
+    InvalidGovernanceConfig,
+    
+    #[msg("Snapshot is not the latest snapshot for this token")]
+    InvalidSnapshot,
+    
+    #[msg("Voting power below the proposal threshold")]
+    InsufficientVotingPower,
+    
+    #[msg("Proposal does not match the supplied accounts")]
+    InvalidProposal,
+    
+    #[msg("Voting period has ended")]
+    VotingClosed,
+    
+    #[msg("Timelock has not elapsed")]
+    TimelockNotElapsed,
+    
+    #[msg("Proposal did not pass")]
+    ProposalNotPassed,
+    
+    #[msg("Proposal was already executed")]
+    ProposalAlreadyExecuted,
+    
+    #[msg("Account is not an spl-governance governance account")]
+    InvalidRealmGovernance,
+    
+    #[msg("Trading on the curve has already started")]
+    TradingAlreadyStarted,
+    
+    #[msg("Curve reserve has not been initialized")]
+    ReserveNotInitialized,
+    
+    #[msg("Price moved beyond the allowed slippage")]
+    SlippageExceeded,
+    
+    #[msg("Amount exceeds the supply sold through the curve")]
+    InsufficientCurveSupply,
+    
+    #[msg("Invalid sell limit parameters")]
+    InvalidSellLimits,
+    
+    #[msg("Sell cooldown has not elapsed for this wallet")]
+    SellCooldownActive,
+    
+    #[msg("Sell exceeds the per-window limit for this wallet")]
+    SellLimitExceeded,
+    
+    #[msg("Trading has not opened yet")]
+    TradingNotStarted,
+    
+    #[msg("Invalid refundable launch parameters")]
+    InvalidRefundPolicy,
+    
+    #[msg("Launch missed its graduation deadline; only refunds are possible")]
+    LaunchFailed,
+    
+    #[msg("Refunds are not enabled for this token")]
+    RefundsNotEnabled,
+    
+    #[msg("Refunds are only available after a missed graduation deadline")]
+    RefundsNotAvailable,
+    
+    #[msg("Invalid Dutch auction parameters")]
+    InvalidAuction,
+    
+    #[msg("Dutch auction is not accepting bids")]
+    AuctionNotActive,
+    
+    #[msg("Dutch auction has not ended")]
+    AuctionNotEnded,
+    
+    #[msg("Curve trading is closed while the initial auction runs")]
+    AuctionInProgress,
+    
+    #[msg("Voucher is not signed by the token creator")]
+    InvalidVoucherSignature,
+    
+    #[msg("Voucher has expired")]
+    VoucherExpired,
+    
+    #[msg("Invalid or duplicate token tag")]
+    InvalidTag,
+    
+    #[msg("Token already has the maximum number of tags")]
+    TooManyTags,
+    
+    #[msg("Index page is full; use the next page")]
+    IndexPageFull,
+    
+    #[msg("Transfer exceeds the token's daily bridge cap")]
+    BridgeCapExceeded,
+    
+    #[msg("Address is zero or not a valid address for the chain")]
+    InvalidUniversalAddress,
+    
+    #[msg("Invalid cross-chain message payload")]
+    InvalidMessagePayload,
+    
+    #[msg("Unknown cross-chain message type")]
+    UnknownMessageType,
+    
+    #[msg("Message was not emitted by the token's registered emitter")]
+    UntrustedEmitter,
+    
+    #[msg("Recipient does not match the transfer payload")]
+    InvalidRecipient,
+    
+    #[msg("Invalid batch sync accounts")]
+    InvalidBatch,
+    
+    #[msg("Scheduled sync is not configured")]
+    SyncNotConfigured,
+    
+    #[msg("Token was synced too recently")]
+    SyncNotDue,
+    
+    #[msg("Transaction combines conflicting instructions for the same mint")]
+    ConflictingInstructions,
+    
+    #[msg("Signer is not the factory verifier")]
+    InvalidVerifier,
+    
+    #[msg("Symbol must be 1-10 uppercase letters or digits")]
+    InvalidSymbol,
+    
+    #[msg("Symbol is not claimed by the creator or is already in use")]
+    SymbolNotClaimed,
+    
+    #[msg("Token metadata is frozen")]
+    MetadataFrozen,
+    
+    #[msg("Metadata does not fit the token account")]
+    InvalidMetadata,
+    
+    #[msg("No reserve withdrawal is pending or its timelock has not passed")]
+    WithdrawalNotReady,
+    
+    #[msg("Withdrawal exceeds the reserve above what the curve owes")]
+    InsufficientExcessReserve,
+    
+    #[msg("Account is not a TokenData account")]
+    InvalidAccountLayout,
+    
+    #[msg("Account is already at the current layout version")]
+    AlreadyMigrated,
+    
+    #[msg("Invalid lookup table or address list")]
+    InvalidLookupTable,
+    
+    #[msg("Invalid bridge mode or bridge mode already set")]
+    InvalidBridgeMode,
+    
+    #[msg("Lock-mode token requires its custody vault")]
+    CustodyNotInitialized,
+    
+    #[msg("Chain supply account missing, mismatched, or short of the transfer")]
+    InvalidChainSupply,
+    
+    #[msg("Identical state was already published within the de-duplication window")]
+    DuplicateSync,
+    
+    #[msg("Maximum staleness must exceed the minimum source confirmation time")]
+    InvalidFinalityBounds,
+    
+    #[msg("Chain finality account does not match the factory or source chain")]
+    InvalidChainFinality,
+    
+    #[msg("Remote update is newer than the source chain's minimum confirmation time")]
+    UpdateNotFinal,
+    
+    #[msg("Remote update is older than the allowed staleness or already superseded")]
+    StaleUpdate,
+    
+    #[msg("No price attestor is configured")]
+    AttestorNotConfigured,
+    
+    #[msg("Missing or invalid ed25519 attestation of the price update")]
+    InvalidAttestation,
+    
+    #[msg("Emitter policy account does not match the message's emitter")]
+    InvalidEmitterPolicy,
+    
+    #[msg("Emitter is not allowed to send this message type")]
+    MessageTypeNotAllowed,
+    
+    #[msg("Wormhole message payload is empty")]
+    EmptyPayload,
+    
+    #[msg("Wormhole message or VAA has an unsupported version")]
+    UnknownVersion,
+    
+    #[msg("Wormhole message payload is shorter than its declared length")]
+    TruncatedPayload,
+    
+    #[msg("Wormhole message payload failed to deserialize")]
+    DeserializeFailed,
+    
+    #[msg("Wormhole message payload has bytes past its declared length")]
+    TrailingBytes,
+    
+    #[msg("Wormhole message payload field exceeds its maximum length")]
+    PayloadFieldTooLong,
+    
+    #[msg("Delegation needs known capabilities and an expiry in the future")]
+    InvalidDelegation,
+    
+    #[msg("Session is expired, for another key or mint, or has invalid limits")]
+    InvalidSession,
+    
+    #[msg("Trade exceeds the session's spend or sell limit")]
+    SessionLimitExceeded,
+    
+    #[msg("Token has not reached its graduation threshold")]
+    NotGraduated,
+    
+    #[msg("LP tokens must be of the mint already locked for this token")]
+    InvalidLpMint,
+    
+    #[msg("Basket components, vaults or component accounts are invalid")]
+    InvalidBasket,
+    
+    #[msg("Lending price feed has not been updated recently enough")]
+    PriceFeedStale,
+    
+    #[msg("Lending price feed is warming up, halted, or spot deviates too far from the TWAP")]
+    PriceFeedUnreliable,
+    
+    #[msg("Token factory is already initialized")]
+    FactoryAlreadyInitialized,
+    
+    #[msg("Wormhole Core Bridge this build targets is not deployed on this cluster")]
+    BridgeNotDeployed,
+    
+    #[msg("Account is still active and cannot be closed yet")]
+    AccountNotStale,
+    
+    #[msg("Payout split needs 1 to 8 recipients with positive shares summing to 10000 bps")]
+    InvalidPayoutSplit,
+    
+    #[msg("Fees of a vault with a payout splitter are claimed through claim_split_creator_fees")]
+    PayoutSplitterAttached,
+    
+    #[msg("Trade history does not belong to this wallet and token")]
+    InvalidTradeHistory,
+    
+    #[msg("Launch certificates are not enabled")]
+    LaunchCertificatesDisabled,
+    
+    #[msg("Creator does not hold an NFT of the required collection")]
+    NotCollectionMember,
+    
+    #[msg("Invalid secondary quote configuration")]
+    InvalidSecondaryQuote,
+    
+    #[msg("Secondary quote trading is disabled or its rate is stale")]
+    SecondaryQuoteUnavailable,
+    
+    #[msg("Remote quote vault does not hold the bridged quote amount")]
+    RemoteQuoteUnfunded,
+    
+    #[msg("Remote trading is not available for this token")]
+    RemoteTradeUnavailable,
+    
+    #[msg("This feature is disabled by the factory authority")]
+    FeatureDisabled,
+    
+    #[msg("Unknown feature bits")]
+    InvalidFeatures,
+    
+    #[msg("Bootstrap chain accounts do not match the requested chains")]
+    InvalidBootstrapAccounts,
+    
+    #[msg("Shard does not match the creator")]
+    InvalidFactoryShard,
+    
+    #[msg("Token mints its curve supply and has no supply vault")]
+    SupplyVaultNotUsed,
+    
+    #[msg("Supply vault does not hold enough tokens for this buy")]
+    SupplyVaultExhausted,
+    
+    #[msg("Quote mint is not on the factory allowlist")]
+    QuoteMintNotAllowed,
+    
+    #[msg("Quote mint is itself a Crossify curve token")]
+    QuoteMintIsCurveToken,
+    
+    #[msg("Quote mint decimals differ from the allowlisted value")]
+    QuoteMintDecimalsMismatch,
+    
+    #[msg("Invalid price guard parameters")]
+    InvalidPriceGuard,
+    
+    #[msg("Price update moves too far from the mirrored price")]
+    PriceDeviationTooLarge,
+    
+    #[msg("Invalid referral code")]
+    InvalidReferralCode,
+    
+    #[msg("Curve parameters are frozen while a reserve withdrawal is pending")]
+    ReserveWithdrawalPending,
+    
+    #[msg("Secondary reserve cannot cover the sale proceeds")]
+    SecondaryReserveShortfall,
+    
+    #[msg("Vesting schedule must end after it starts, with cliffs only in cliff mode")]
+    InvalidVestingSchedule,
+    
+    #[msg("Instruction must be called directly, not through another program")]
+    CpiNotAllowed,
+    
+    #[msg("Voting on this proposal is still open")]
+    VotingNotClosed,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    
+    fn curve(curve_type: u8, base_price: u64, slope: u64, reserve_ratio: u16) -> BondingCurve {
+        BondingCurve {
+            configured: true,
+            curve_type,
+            base_price,
+            slope,
+            reserve_ratio,
+            ..Default::default()
+        }
+    }
+
+    // Curve type, base price, slope, reserve ratio and decimals, kept small enough that most quotes
+    // fit in u64; the rest are skipped
+    fn any_curve() -> impl Strategy<Value = (u8, u64, u64, u16, u8)> {
+        (0u8..=2, 0u64..=1_000_000_000, 0u64..=1_000_000, 0u16..=1000, 0u8..=9)
+    }
+
+    proptest! {
+        #[test]
+        fn buy_then_sell_never_profits(
+            (curve_type, base_price, slope, reserve_ratio, decimals) in any_curve(),
+            supply in 0u64..=1_000_000_000_000,
+            amount in 1u64..=1_000_000_000_000,
+            pieces in prop::collection::vec(1u64..=u64::MAX, 1..8),
+            sell_tax_bps in 0u16..=MAX_SELL_TAX_BPS,
+        ) {
+            let bonding_curve = curve(curve_type, base_price, slope, reserve_ratio);
+            let cost = quote_bonding_curve(&bonding_curve, decimals, supply, amount);
+            prop_assume!(cost.is_ok());
+            let cost = cost.unwrap();
+            
+            // Sell the position back in arbitrary pieces, top of the curve first
+            let mut remaining = amount;
+            let mut proceeds = 0u64;
+            for piece in pieces {
+                let piece = piece % remaining + 1;
+                let gross = quote_bonding_curve(&bonding_curve, decimals, supply + remaining - piece, piece).unwrap();
+                proceeds += gross - bps_of(gross, sell_tax_bps).unwrap();
+                remaining -= piece;
+                if remaining == 0 {
+                    break;
+                }
+            }
+            if remaining > 0 {
+                let gross = quote_bonding_curve(&bonding_curve, decimals, supply, remaining).unwrap();
+                proceeds += gross - bps_of(gross, sell_tax_bps).unwrap();
+            }
+            prop_assert!(proceeds <= cost, "cost={} proceeds={}", cost, proceeds);
+        }
+
+        #[test]
+        fn cost_is_monotonic_in_amount(
+            (curve_type, base_price, slope, reserve_ratio, decimals) in any_curve(),
+            supply in 0u64..=1_000_000_000_000,
+            amount in 0u64..=1_000_000_000_000,
+            extra in 0u64..=1_000_000_000_000,
+        ) {
+            let bonding_curve = curve(curve_type, base_price, slope, reserve_ratio);
+            let larger = quote_bonding_curve(&bonding_curve, decimals, supply, amount + extra);
+            prop_assume!(larger.is_ok());
+            let smaller = quote_bonding_curve(&bonding_curve, decimals, supply, amount).unwrap();
+            prop_assert!(smaller <= larger.unwrap());
+        }
+
+        #[test]
+        fn pricing_is_path_independent(
+            (curve_type, base_price, slope, reserve_ratio, decimals) in any_curve(),
+            supply in 0u64..=1_000_000_000_000,
+            first in 0u64..=1_000_000_000_000,
+            second in 0u64..=1_000_000_000_000,
+        ) {
+            let bonding_curve = curve(curve_type, base_price, slope, reserve_ratio);
+            let whole = quote_bonding_curve(&bonding_curve, decimals, supply, first + second);
+            prop_assume!(whole.is_ok());
+            let split = quote_bonding_curve(&bonding_curve, decimals, supply, first).unwrap()
+                + quote_bonding_curve(&bonding_curve, decimals, supply + first, second).unwrap();
+            prop_assert_eq!(whole.unwrap(), split);
+        }
+    }
+
+    #[test]
+    fn whole_tokens_cost_their_spot_price() {
+        // Linear: token k costs base_price + slope * k
+        let linear = curve(0, 100, 10, 0);
+        assert_eq!(quote_bonding_curve(&linear, 0, 0, 3).unwrap(), 100 + 110 + 120);
+        assert_eq!(quote_bonding_curve(&linear, 6, 2_000_000, 500_000).unwrap(), 60);
+        
+        // Exponential: slope is growth per token in parts per million of base_price
+        let exponential = curve(1, 1_000_000, 10_000, 0);
+        assert_eq!(quote_bonding_curve(&exponential, 0, 0, 2).unwrap(), 1_000_000 + 1_010_000);
+        
+        // Bancor with a reserve ratio stays at base_price
+        let bancor = curve(2, 7, 0, 500);
+        assert_eq!(quote_bonding_curve(&bancor, 0, 5_000, 10).unwrap(), 70);
+        
+        assert!(quote_bonding_curve(&curve(3, 1, 1, 0), 0, 0, 1).is_err());
+    }
+
+    // The IDL clients generate types from, parsed from the program source as `anchor build` does
+    fn program_idl() -> anchor_syn::idl::Idl {
+        let lib = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src/lib.rs");
+        anchor_syn::idl::file::parse(lib, "0.1.0".to_string(), false, false, false)
+            .unwrap()
+            .expect("program module")
+    }
+
+    #[test]
+    fn idl_round_trips_with_payloads_and_new_accounts() {
+        let idl = program_idl();
+        let json = serde_json::to_string(&idl).unwrap();
+        assert_eq!(serde_json::from_str::<anchor_syn::idl::Idl>(&json).unwrap(), idl);
+        
+        let type_names: Vec<&str> = idl.types.iter().map(|ty| ty.name.as_str()).collect();
+        for payload in [
+            "TokenCreationPayload",
+            "PriceUpdatePayload",
+            "LiquidityUpdatePayload",
+            "SupplyUpdatePayload",
+            "TokenTransferPayload",
+            "RemoteBuyPayload",
+            "RemoteSellPayload",
+            "QuoteTransferPayload",
+            "RefundPayload",
+            "UniversalAddress",
+        ] {
+            assert!(type_names.contains(&payload), "IDL is missing type {}", payload);
+        }
+
+        let account_names: Vec<&str> = idl.accounts.iter().map(|account| account.name.as_str()).collect();
+        for account in [
+            "ReferralCode",
+            "ReferralStats",
+            "SecondaryCurve",
+            "LendingPriceFeed",
+            "BuyerRecord",
+        ] {
+            assert!(account_names.contains(&account), "IDL is missing account {}", account);
+        }
+
+        let constant_names: Vec<&str> = idl.constants.iter().map(|constant| constant.name.as_str()).collect();
+        assert!(constant_names.contains(&"MSG_TYPE_PRICE_UPDATE"));
+    }
+
+    #[test]
+    fn idl_payload_fields_follow_wire_order() {
+        let idl = program_idl();
+        let price_update = idl.types.iter().find(|ty| ty.name == "PriceUpdatePayload").unwrap();
+        let fields: Vec<&str> = match &price_update.ty {
+            anchor_syn::idl::IdlTypeDefinitionTy::Struct { fields } => {
+                fields.iter().map(|field| field.name.as_str()).collect()
+            }
+            _ => panic!("PriceUpdatePayload is not a struct"),
+        };
+        // camelCase, as clients see them
+        assert_eq!(
+            fields,
+            [
+                "factory",
+                "tokenId",
+                "nonce",
+                "currentPrice",
+                "currentSupply",
+                "timestamp"
+            ]
+        );
+    }
+
+    const GOVERNANCE_FIXTURES_PATH: &str = "../../../../integration/fixtures/spl_governance_accounts.json";
+    
+    struct GovernanceFixture {
+        name: String,
+        account_type: u8,
+        data: Vec<u8>,
+        // Set for governance accounts only
+        address: Option<Pubkey>,
+        native_treasury: Option<Pubkey>,
+        realm: Option<Pubkey>,
+    }
+
+    fn governance_fixtures() -> Vec<GovernanceFixture> {
+        let hex = |value: &serde_json::Value| -> Vec<u8> {
+            let hex = value.as_str().unwrap();
+            (0..hex.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).expect("hex byte"))
+                .collect()
+        };
+        let key = |value: &serde_json::Value| value.as_str().map(|_| Pubkey::try_from(hex(value).as_slice()).unwrap());
+        
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(GOVERNANCE_FIXTURES_PATH);
+        let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(json["version"], 1);
+        assert_eq!(json["program_id"].as_str().unwrap(), spl_governance::ID.to_string());
+        json["accounts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|account| GovernanceFixture {
+                name: account["name"].as_str().unwrap().to_string(),
+                account_type: account["account_type"].as_u64().unwrap() as u8,
+                data: hex(&account["data"]),
+                address: key(&account["address"]),
+                native_treasury: key(&account["native_treasury"]),
+                realm: key(&account["realm"]),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn governance_realm_matches_spl_governance_accounts() {
+        let fixtures = governance_fixtures();
+        let governance_types: Vec<u8> = fixtures
+            .iter()
+            .filter(|fixture| fixture.realm.is_some())
+            .map(|fixture| fixture.account_type)
+            .collect();
+        assert_eq!(governance_types, SPL_GOVERNANCE_ACCOUNT_TYPES);
+        
+        for fixture in fixtures {
+            assert_eq!(fixture.data[0], fixture.account_type, "{}", fixture.name);
+            assert_eq!(governance_realm(&fixture.data), fixture.realm, "{}", fixture.name);
+        }
+        assert_eq!(governance_realm(&[SPL_GOVERNANCE_ACCOUNT_TYPES[0]; 32]), None);
+        assert_eq!(governance_realm(&[]), None);
+    }
+
+    #[test]
+    fn native_treasury_matches_spl_governance() {
+        for fixture in governance_fixtures() {
+            if let (Some(address), Some(native_treasury)) = (fixture.address, fixture.native_treasury) {
+                let (derived, _) =
+                    Pubkey::find_program_address(&[b"native-treasury", address.as_ref()], &spl_governance::ID);
+                assert_eq!(derived, native_treasury, "{}", fixture.name);
+            }
+        }
+    }
 }
@@ -0,0 +1,152 @@
+// Liquidity lock module for Crossify Token Factory
+// This file contains the permanent lock or burn of a graduated token's pool LP tokens
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::{TokenData, TokenFactoryError};
+
+#[derive(Accounts)]
+pub struct LockLiquidity<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(mut)]
+    pub lp_mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA owning the LP lock vault; no instruction ever signs for it, so nothing leaves
+    #[account(seeds = [b"lp_lock_authority", token_data.mint.as_ref()], bump)]
+    pub lp_lock_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"lp_lock", token_data.mint.as_ref()],
+        bump,
+        token::mint = lp_mint,
+        token::authority = lp_lock_authority,
+    )]
+    pub lp_lock_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = lp_mint, token::authority = authority)]
+    pub source_lp_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> LockLiquidity<'info> {
+    // Further deposits may follow, but always of the same LP mint
+    pub fn process(&mut self, amount: u64, burn: bool) -> Result<()> {
+        let token_data = &self.token_data;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=lock_liquidity token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            self.authority.key(),
+            token_data.authority
+        );
+        require_with_context!(
+            token_data.bonding_curve.threshold_reached,
+            TokenFactoryError::NotGraduated,
+            "instruction=lock_liquidity token_id={}",
+            token_data.token_id
+        );
+        require_with_context!(
+            amount > 0,
+            TokenFactoryError::InvalidAmount,
+            "instruction=lock_liquidity token_id={} amount={}",
+            token_data.token_id,
+            amount
+        );
+        require_with_context!(
+            !token_data.liquidity_locked || token_data.liquidity_lock.lp_mint == self.lp_mint.key(),
+            TokenFactoryError::InvalidLpMint,
+            "instruction=lock_liquidity token_id={} lp_mint={} locked_lp_mint={}",
+            token_data.token_id,
+            self.lp_mint.key(),
+            token_data.liquidity_lock.lp_mint
+        );
+        
+        if burn {
+            token::burn(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    token::Burn {
+                        mint: self.lp_mint.to_account_info(),
+                        from: self.source_lp_account.to_account_info(),
+                        authority: self.authority.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+        } else {
+            token::transfer(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    token::Transfer {
+                        from: self.source_lp_account.to_account_info(),
+                        to: self.lp_lock_vault.to_account_info(),
+                        authority: self.authority.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let (token_id, mint, was_locked) = (token_data.token_id, token_data.mint, token_data.liquidity_locked);
+        self.token_data.liquidity_locked = true;
+        let lock = &mut self.token_data.liquidity_lock;
+        if !was_locked {
+            lock.lp_mint = self.lp_mint.key();
+            lock.locked_at = now;
+        }
+        if burn {
+            lock.burned = lock.burned.checked_add(amount).ok_or(TokenFactoryError::MathOverflow)?;
+        } else {
+            lock.locked = lock.locked.checked_add(amount).ok_or(TokenFactoryError::MathOverflow)?;
+        }
+
+        emit!(LiquidityLockedEvent {
+            token_id,
+            mint,
+            lp_mint: lock.lp_mint,
+            amount,
+            burned: burn,
+            total_locked: lock.locked,
+            total_burned: lock.burned,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+// The program cannot tell which pool an LP mint belongs to; buyers check `lp_mint` against the pool
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct LiquidityLock {
+    pub lp_mint: Pubkey,
+    pub locked: u64, // LP tokens held in the lp_lock vault
+    pub burned: u64,
+    pub locked_at: i64, // First lock or burn
+}
+
+#[event]
+pub struct LiquidityLockedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub lp_mint: Pubkey,
+    pub amount: u64,
+    pub burned: bool,
+    pub total_locked: u64,
+    pub total_burned: u64,
+    pub timestamp: i64,
+}
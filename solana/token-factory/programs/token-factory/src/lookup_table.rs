@@ -0,0 +1,263 @@
+// Lookup table module for Crossify Token Factory
+// This file contains the factory's address lookup table, used by clients to build v0 transactions
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::solana_program::sysvar;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::Token;
+
+use crate::{wormhole_core_bridge, TokenFactory, TokenFactoryError};
+
+// Extra addresses per extend_lookup_table call, keeping the transaction under its size limit
+pub const MAX_LOOKUP_TABLE_EXTENSION: usize = 20;
+
+// Address Lookup Table program. The solana-program release anchor 0.28 builds against has no
+// builders for it, so the two instructions used here are encoded by hand below
+pub mod address_lookup_table {
+    use anchor_lang::prelude::*;
+    
+    declare_id!("AddressLookupTab1e1111111111111111111111111");
+}
+
+// Bincode tags of the lookup table program's instruction enum
+const ALT_IX_CREATE_LOOKUP_TABLE: u32 = 0;
+const ALT_IX_EXTEND_LOOKUP_TABLE: u32 = 2;
+
+#[derive(Accounts)]
+pub struct CreateLookupTable<'info> {
+    #[account(mut, seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    /// CHECK: PDA authority of the factory lookup table; it holds no data
+    #[account(seeds = [b"lookup_table_authority", token_factory.key().as_ref()], bump)]
+    pub lookup_table_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: Created by the lookup table program; address checked against the derived one
+    #[account(mut)]
+    pub lookup_table: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// CHECK: Address lookup table program
+    #[account(address = address_lookup_table::ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateLookupTable<'info> {
+    pub fn process(&mut self, authority_bump: u8, recent_slot: u64) -> Result<()> {
+        let token_factory = &self.token_factory;
+        
+        // Verify authority
+        require_with_context!(
+            token_factory.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=create_lookup_table signer={} expected_authority={}",
+            self.authority.key(),
+            token_factory.authority
+        );
+        
+        let (create_ix, lookup_table) = create_lookup_table_signed(
+            self.lookup_table_authority.key(),
+            self.authority.key(),
+            recent_slot,
+        );
+        require_with_context!(
+            self.lookup_table.key() == lookup_table,
+            TokenFactoryError::InvalidLookupTable,
+            "instruction=create_lookup_table lookup_table={} expected={}",
+            self.lookup_table.key(),
+            lookup_table
+        );
+        
+        let factory_key = token_factory.key();
+        let bump = [authority_bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"lookup_table_authority", factory_key.as_ref(), &bump]];
+        let cpi_accounts = [
+            self.lookup_table.to_account_info(),
+            self.lookup_table_authority.to_account_info(),
+            self.authority.to_account_info(),
+            self.system_program.to_account_info(),
+        ];
+        invoke_signed(&create_ix, &cpi_accounts, signer_seeds)?;
+        
+        // Seed the table with the addresses nearly every factory instruction touches
+        let addresses = common_addresses(token_factory);
+        let extend_ix = extend_lookup_table(
+            lookup_table,
+            self.lookup_table_authority.key(),
+            Some(self.authority.key()),
+            addresses.clone(),
+        );
+        invoke_signed(&extend_ix, &cpi_accounts, signer_seeds)?;
+        
+        self.token_factory.lookup_table = lookup_table;
+        
+        emit!(LookupTableCreatedEvent {
+            lookup_table,
+            addresses,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ExtendLookupTable<'info> {
+    #[account(seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    /// CHECK: PDA authority of the factory lookup table; it holds no data
+    #[account(seeds = [b"lookup_table_authority", token_factory.key().as_ref()], bump)]
+    pub lookup_table_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: The factory's lookup table, owned by the lookup table program
+    #[account(mut, address = token_factory.lookup_table @ TokenFactoryError::InvalidLookupTable)]
+    pub lookup_table: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// CHECK: Address lookup table program
+    #[account(address = address_lookup_table::ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ExtendLookupTable<'info> {
+    pub fn process(&mut self, authority_bump: u8, addresses: Vec<Pubkey>) -> Result<()> {
+        let token_factory = &self.token_factory;
+        
+        // Verify authority
+        require_with_context!(
+            token_factory.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=extend_lookup_table signer={} expected_authority={}",
+            self.authority.key(),
+            token_factory.authority
+        );
+        require_with_context!(
+            !addresses.is_empty() && addresses.len() <= MAX_LOOKUP_TABLE_EXTENSION,
+            TokenFactoryError::InvalidLookupTable,
+            "instruction=extend_lookup_table addresses={} max={}",
+            addresses.len(),
+            MAX_LOOKUP_TABLE_EXTENSION
+        );
+        
+        let extend_ix = extend_lookup_table(
+            self.lookup_table.key(),
+            self.lookup_table_authority.key(),
+            Some(self.authority.key()),
+            addresses.clone(),
+        );
+        let factory_key = token_factory.key();
+        let bump = [authority_bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"lookup_table_authority", factory_key.as_ref(), &bump]];
+        invoke_signed(
+            &extend_ix,
+            &[
+                self.lookup_table.to_account_info(),
+                self.lookup_table_authority.to_account_info(),
+                self.authority.to_account_info(),
+                self.system_program.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+        
+        emit!(LookupTableExtendedEvent {
+            lookup_table: self.lookup_table.key(),
+            addresses,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+// CreateLookupTable with the authority signing, so the table address cannot be front-run;
+// returns the instruction and the table it creates
+fn create_lookup_table_signed(authority: Pubkey, payer: Pubkey, recent_slot: u64) -> (Instruction, Pubkey) {
+    let (lookup_table, bump_seed) = Pubkey::find_program_address(
+        &[authority.as_ref(), &recent_slot.to_le_bytes()],
+        &address_lookup_table::ID,
+    );
+    let mut data = ALT_IX_CREATE_LOOKUP_TABLE.to_le_bytes().to_vec();
+    data.extend_from_slice(&recent_slot.to_le_bytes());
+    data.push(bump_seed);
+    let ix = Instruction {
+        program_id: address_lookup_table::ID,
+        accounts: vec![
+            AccountMeta::new(lookup_table, false),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(System::id(), false),
+        ],
+        data,
+    };
+    (ix, lookup_table)
+}
+
+// ExtendLookupTable; `payer` tops up the table's rent for the added addresses
+fn extend_lookup_table(
+    lookup_table: Pubkey,
+    authority: Pubkey,
+    payer: Option<Pubkey>,
+    new_addresses: Vec<Pubkey>,
+) -> Instruction {
+    let mut data = ALT_IX_EXTEND_LOOKUP_TABLE.to_le_bytes().to_vec();
+    data.extend_from_slice(&(new_addresses.len() as u64).to_le_bytes());
+    for address in &new_addresses {
+        data.extend_from_slice(address.as_ref());
+    }
+    let mut accounts = vec![
+        AccountMeta::new(lookup_table, false),
+        AccountMeta::new_readonly(authority, true),
+    ];
+    if let Some(payer) = payer {
+        accounts.push(AccountMeta::new(payer, true));
+        accounts.push(AccountMeta::new_readonly(System::id(), false));
+    }
+    Instruction {
+        program_id: address_lookup_table::ID,
+        accounts,
+        data,
+    }
+}
+
+// Programs, sysvars and factory-wide accounts shared by trades, bridging and batch syncs
+fn common_addresses(token_factory: &Account<TokenFactory>) -> Vec<Pubkey> {
+    let factory_key = token_factory.key();
+    let (keeper_vault, _) = Pubkey::find_program_address(&[b"keeper_vault", factory_key.as_ref()], &crate::ID);
+    vec![
+        crate::ID,
+        Token::id(),
+        AssociatedToken::id(),
+        System::id(),
+        sysvar::instructions::ID,
+        sysvar::rent::ID,
+        wormhole_core_bridge::ID,
+        factory_key,
+        token_factory.treasury,
+        keeper_vault,
+    ]
+}
+
+#[event]
+pub struct LookupTableCreatedEvent {
+    pub lookup_table: Pubkey,
+    pub addresses: Vec<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LookupTableExtendedEvent {
+    pub lookup_table: Pubkey,
+    pub addresses: Vec<Pubkey>,
+    pub timestamp: i64,
+}
@@ -0,0 +1,105 @@
+// Migration module for Crossify Token Factory
+// This file contains upgrades of accounts created under older layouts to the current schema
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+
+use crate::{TokenData, TokenFactoryError};
+
+// Layout versions written at creation; bump one whenever its account gains fields
+pub const TOKEN_FACTORY_VERSION: u8 = 5;
+pub const TOKEN_DATA_VERSION: u8 = 6;
+pub const TOKEN_STATS_VERSION: u8 = 5;
+
+// Layout version written to every other account type at creation. Those accounts end in
+// ACCOUNT_RESERVED_LEN zeroed bytes: a field added later takes its bytes from the reserve instead
+// of growing the account, so existing accounts keep their size and read the new field as zero.
+// A type that changes layout gets its own version constant starting at 2
+pub const ACCOUNT_VERSION: u8 = 1;
+pub const ACCOUNT_RESERVED_LEN: usize = 32;
+
+#[derive(Accounts)]
+pub struct MigrateTokenData<'info> {
+    /// CHECK: May not deserialize under the current layout until it is grown; owner and
+    /// discriminator are checked in process
+    #[account(mut, owner = crate::ID)]
+    pub token_data: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateTokenData<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let info = self.token_data.to_account_info();
+        let old_len = info.data_len();
+        require_with_context!(
+            old_len >= 8 && info.try_borrow_data()?[..8] == TokenData::DISCRIMINATOR,
+            TokenFactoryError::InvalidAccountLayout,
+            "instruction=migrate_token_data account={} data_len={}",
+            info.key(),
+            old_len
+        );
+        
+        // Grow first: fields appended since the account was created read as zero once it fits
+        if old_len < TokenData::SPACE {
+            let rent_due = Rent::get()?
+                .minimum_balance(TokenData::SPACE)
+                .saturating_sub(info.lamports());
+            if rent_due > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        self.system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: self.payer.to_account_info(),
+                            to: info.clone(),
+                        },
+                    ),
+                    rent_due,
+                )?;
+            }
+            info.realloc(TokenData::SPACE, true)?;
+        }
+
+        let mut token_data: Account<TokenData> = Account::try_from(&info)?;
+        let from_version = token_data.version;
+        require_with_context!(
+            from_version < TOKEN_DATA_VERSION,
+            TokenFactoryError::AlreadyMigrated,
+            "instruction=migrate_token_data token_id={} version={}",
+            token_data.token_id,
+            from_version
+        );
+        
+        // Version 0 accounts predate the version field; their zeroed tail is already the
+        // correct default for every field added since
+        token_data.version = TOKEN_DATA_VERSION;
+        token_data.exit(&crate::ID)?;
+        
+        emit!(TokenDataMigratedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            from_version,
+            to_version: TOKEN_DATA_VERSION,
+            old_len: old_len as u32,
+            new_len: info.data_len() as u32,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[event]
+pub struct TokenDataMigratedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub old_len: u32,
+    pub new_len: u32,
+    pub timestamp: i64,
+}
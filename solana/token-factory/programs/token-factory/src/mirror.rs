@@ -0,0 +1,575 @@
+// Mirror module for Crossify Token Factory
+// This file contains the local copy of a token's curve state on remote chains, fed by their
+// price and liquidity updates
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as ix_sysvar;
+use std::mem::size_of;
+
+use crate::wormhole::{
+    deserialize_wormhole_message, parse_liquidity_update_message, parse_posted_vaa, parse_price_update_message,
+    serialize_price_update_message, wormhole, PriceUpdatePayload,
+};
+use crate::admin_log::{admin_value, record_admin_action, ADMIN_ACTION_PRICE_RESET};
+use crate::{
+    previous_ed25519_signs, require_message_type_allowed, wormhole_core_bridge, ChainFinality, PriceGuard, TokenData,
+    TokenFactory, TokenFactoryError, ACCOUNT_RESERVED_LEN, ACCOUNT_VERSION,
+};
+
+// Where a mirrored update came from; attestor updates rest on a single key, not the guardian set
+#[constant]
+pub const CURVE_SOURCE_WORMHOLE: u8 = 0;
+#[constant]
+pub const CURVE_SOURCE_ATTESTOR: u8 = 1;
+
+// Longest a price reset approval stays usable (one day)
+pub const MAX_PRICE_RESET_VALIDITY: i64 = 86_400;
+
+#[derive(Accounts)]
+pub struct ReceiveCurveUpdate<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    /// CHECK: Verified VAA posted by the Core Bridge; parsed and checked in `process`
+    #[account(owner = wormhole_core_bridge::ID)]
+    pub posted_vaa: UncheckedAccount<'info>,
+    
+    // Finality bounds of the VAA's emitter chain; checked against it in `process`
+    pub chain_finality: Account<'info, ChainFinality>,
+    
+    /// CHECK: EmitterPolicy PDA of the VAA's emitter, possibly uninitialized; checked in `process`
+    pub emitter_policy: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + size_of::<MirroredCurveState>(),
+        seeds = [b"mirror", token_data.mint.as_ref(), &chain_finality.chain_id.to_le_bytes()],
+        bump,
+    )]
+    pub mirrored_curve: Account<'info, MirroredCurveState>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + size_of::<PriceUpdateNonce>(),
+        seeds = [b"price_nonce", token_data.mint.as_ref(), &chain_finality.chain_id.to_le_bytes()],
+        bump,
+    )]
+    pub price_nonce: Account<'info, PriceUpdateNonce>,
+    
+    /// CHECK: PriceGuard PDA of the source chain, possibly uninitialized; checked in `process`
+    #[account(seeds = [b"price_guard", token_data.factory.as_ref(), &chain_finality.chain_id.to_le_bytes()], bump)]
+    pub price_guard: UncheckedAccount<'info>,
+    
+    /// CHECK: PriceResetApproval PDA of the token and chain, possibly uninitialized; checked in `process`
+    #[account(mut, seeds = [b"price_reset", token_data.mint.as_ref(), &chain_finality.chain_id.to_le_bytes()], bump)]
+    pub price_reset: UncheckedAccount<'info>,
+}
+
+impl<'info> ReceiveCurveUpdate<'info> {
+    pub fn process(&mut self, bump: u8, nonce_bump: u8) -> Result<()> {
+        let token_data = &self.token_data;
+        let vaa = parse_posted_vaa(&self.posted_vaa.try_borrow_data()?)?;
+        
+        require_with_context!(
+            token_data.cross_chain_enabled,
+            TokenFactoryError::CrossChainNotEnabled,
+            "instruction=receive_curve_update token_id={} emitter_chain={}",
+            token_data.token_id,
+            vaa.emitter_chain
+        );
+        require_with_context!(
+            token_data.cross_chain_info.supported_chains.contains(vaa.emitter_chain)
+                && vaa.emitter_address == token_data.cross_chain_info.wormhole_emitter,
+            TokenFactoryError::UntrustedEmitter,
+            "instruction=receive_curve_update token_id={} emitter_chain={} emitter_address={:?}",
+            token_data.token_id,
+            vaa.emitter_chain,
+            vaa.emitter_address.bytes
+        );
+        let chain_finality = &self.chain_finality;
+        require_with_context!(
+            chain_finality.factory == token_data.factory && chain_finality.chain_id == vaa.emitter_chain,
+            TokenFactoryError::InvalidChainFinality,
+            "instruction=receive_curve_update token_id={} chain_finality={} emitter_chain={}",
+            token_data.token_id,
+            chain_finality.key(),
+            vaa.emitter_chain
+        );
+        
+        // Age is measured from the guardian-signed source block time, not the payload's own timestamp
+        let now = Clock::get()?.unix_timestamp;
+        chain_finality.check_update_age(vaa.timestamp as i64, now)?;
+        
+        // Sequences only grow per emitter, so replays and out-of-order deliveries are rejected
+        let mirrored_curve = &mut self.mirrored_curve;
+        let is_new = mirrored_curve.mint == Pubkey::default();
+        require_with_context!(
+            is_new || vaa.sequence > mirrored_curve.last_sequence,
+            TokenFactoryError::StaleUpdate,
+            "instruction=receive_curve_update token_id={} sequence={} last_sequence={}",
+            token_data.token_id,
+            vaa.sequence,
+            mirrored_curve.last_sequence
+        );
+        
+        let (message_type, payload) = deserialize_wormhole_message(&vaa.payload)?;
+        require_message_type_allowed(
+            &self.emitter_policy,
+            &token_data.factory,
+            vaa.emitter_chain,
+            &vaa.emitter_address,
+            message_type,
+            "receive_curve_update",
+        )?;
+        let (payload_factory, payload_token_id) = match message_type {
+            wormhole::MSG_TYPE_PRICE_UPDATE => {
+                let update = parse_price_update_message(&payload)?;
+                self.price_nonce.apply(
+                    token_data.mint,
+                    vaa.emitter_chain,
+                    update.nonce,
+                    nonce_bump,
+                    "receive_curve_update",
+                )?;
+                let (reference_price, reference_timestamp) = if is_new {
+                    (update.current_price, vaa.timestamp as i64)
+                } else {
+                    check_price_guard(
+                        &self.price_guard,
+                        &self.price_reset,
+                        mirrored_curve,
+                        update.current_price,
+                        vaa.timestamp as i64,
+                        now,
+                    )?
+                };
+                mirrored_curve.reference_price = reference_price;
+                mirrored_curve.reference_timestamp = reference_timestamp;
+                mirrored_curve.current_price = update.current_price;
+                mirrored_curve.current_supply = update.current_supply;
+                (update.factory, update.token_id)
+            }
+            wormhole::MSG_TYPE_LIQUIDITY_UPDATE => {
+                let update = parse_liquidity_update_message(&payload)?;
+                mirrored_curve.current_liquidity = update.current_liquidity;
+                (update.factory, update.token_id)
+            }
+            _ => {
+                msg!(
+                    "instruction=receive_curve_update token_id={} message_type={}",
+                    token_data.token_id,
+                    message_type
+                );
+                return Err(TokenFactoryError::UnknownMessageType.into());
+            }
+        };
+        require_with_context!(
+            payload_factory == token_data.factory && payload_token_id == token_data.token_id,
+            TokenFactoryError::InvalidMessagePayload,
+            "instruction=receive_curve_update token_id={} payload_factory={} payload_token_id={}",
+            token_data.token_id,
+            payload_factory,
+            payload_token_id
+        );
+        
+        mirrored_curve.mint = token_data.mint;
+        mirrored_curve.chain_id = vaa.emitter_chain;
+        mirrored_curve.last_sequence = vaa.sequence;
+        mirrored_curve.source_timestamp = vaa.timestamp as i64;
+        mirrored_curve.updated_at = now;
+        mirrored_curve.bump = bump;
+        mirrored_curve.account_version = ACCOUNT_VERSION;
+        
+        emit!(CurveMirroredEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            source_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+            message_type,
+            source: CURVE_SOURCE_WORMHOLE,
+            current_price: mirrored_curve.current_price,
+            current_supply: mirrored_curve.current_supply,
+            current_liquidity: mirrored_curve.current_liquidity,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id: u16)]
+pub struct SubmitAttestedPrice<'info> {
+    #[account(address = token_data.factory, seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        seeds = [b"chain_finality", token_factory.key().as_ref(), &chain_id.to_le_bytes()],
+        bump = chain_finality.bump,
+    )]
+    pub chain_finality: Account<'info, ChainFinality>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + size_of::<MirroredCurveState>(),
+        seeds = [b"mirror", token_data.mint.as_ref(), &chain_id.to_le_bytes()],
+        bump,
+    )]
+    pub mirrored_curve: Account<'info, MirroredCurveState>,
+    
+    /// CHECK: Instructions sysvar, read for the preceding Ed25519 signature check
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + size_of::<PriceUpdateNonce>(),
+        seeds = [b"price_nonce", token_data.mint.as_ref(), &chain_id.to_le_bytes()],
+        bump,
+    )]
+    pub price_nonce: Account<'info, PriceUpdateNonce>,
+    
+    /// CHECK: PriceGuard PDA of the source chain, possibly uninitialized; checked in `process`
+    #[account(seeds = [b"price_guard", token_data.factory.as_ref(), &chain_id.to_le_bytes()], bump)]
+    pub price_guard: UncheckedAccount<'info>,
+    
+    /// CHECK: PriceResetApproval PDA of the token and chain, possibly uninitialized; checked in `process`
+    #[account(mut, seeds = [b"price_reset", token_data.mint.as_ref(), &chain_id.to_le_bytes()], bump)]
+    pub price_reset: UncheckedAccount<'info>,
+}
+
+impl<'info> SubmitAttestedPrice<'info> {
+    // Expects an Ed25519 program instruction right before this one, signing `attested_price_message`
+    pub fn process(&mut self, bump: u8, nonce_bump: u8, chain_id: u16, update: PriceUpdatePayload) -> Result<()> {
+        let token_factory = &self.token_factory;
+        let token_data = &self.token_data;
+        require_with_context!(
+            token_factory.price_attestor != Pubkey::default(),
+            TokenFactoryError::AttestorNotConfigured,
+            "instruction=submit_attested_price token_id={} chain_id={}",
+            token_data.token_id,
+            chain_id
+        );
+        require_with_context!(
+            token_data.cross_chain_enabled && token_data.cross_chain_info.supported_chains.contains(chain_id),
+            TokenFactoryError::CrossChainNotEnabled,
+            "instruction=submit_attested_price token_id={} chain_id={}",
+            token_data.token_id,
+            chain_id
+        );
+        require_with_context!(
+            update.factory == token_data.factory && update.token_id == token_data.token_id,
+            TokenFactoryError::InvalidMessagePayload,
+            "instruction=submit_attested_price token_id={} payload_factory={} payload_token_id={}",
+            token_data.token_id,
+            update.factory,
+            update.token_id
+        );
+        
+        let message = attested_price_message(&token_factory.key(), chain_id, &update);
+        require_with_context!(
+            previous_ed25519_signs(&self.instructions, &token_factory.price_attestor, &message)?,
+            TokenFactoryError::InvalidAttestation,
+            "instruction=submit_attested_price token_id={} chain_id={} attestor={}",
+            token_data.token_id,
+            chain_id,
+            token_factory.price_attestor
+        );
+        
+        // The attestor's own timestamp is all there is to age the update by
+        let now = Clock::get()?.unix_timestamp;
+        self.chain_finality.check_update_age(update.timestamp, now)?;
+        let mirrored_curve = &mut self.mirrored_curve;
+        let is_new = mirrored_curve.mint == Pubkey::default();
+        require_with_context!(
+            is_new || update.timestamp > mirrored_curve.source_timestamp,
+            TokenFactoryError::StaleUpdate,
+            "instruction=submit_attested_price token_id={} timestamp={} last_timestamp={}",
+            token_data.token_id,
+            update.timestamp,
+            mirrored_curve.source_timestamp
+        );
+        self.price_nonce
+            .apply(token_data.mint, chain_id, update.nonce, nonce_bump, "submit_attested_price")?;
+        let (reference_price, reference_timestamp) = if is_new {
+            (update.current_price, update.timestamp)
+        } else {
+            check_price_guard(
+                &self.price_guard,
+                &self.price_reset,
+                mirrored_curve,
+                update.current_price,
+                update.timestamp,
+                now,
+            )?
+        };
+        
+        mirrored_curve.mint = token_data.mint;
+        mirrored_curve.chain_id = chain_id;
+        mirrored_curve.reference_price = reference_price;
+        mirrored_curve.reference_timestamp = reference_timestamp;
+        mirrored_curve.current_price = update.current_price;
+        mirrored_curve.current_supply = update.current_supply;
+        mirrored_curve.source_timestamp = update.timestamp;
+        mirrored_curve.updated_at = now;
+        mirrored_curve.bump = bump;
+        mirrored_curve.account_version = ACCOUNT_VERSION;
+        
+        emit!(CurveMirroredEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            source_chain: chain_id,
+            sequence: mirrored_curve.last_sequence,
+            message_type: wormhole::MSG_TYPE_PRICE_UPDATE,
+            source: CURVE_SOURCE_ATTESTOR,
+            current_price: mirrored_curve.current_price,
+            current_supply: mirrored_curve.current_supply,
+            current_liquidity: mirrored_curve.current_liquidity,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id: u16)]
+pub struct ApprovePriceReset<'info> {
+    #[account(address = token_data.factory, seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + size_of::<PriceResetApproval>(),
+        seeds = [b"price_reset", token_data.mint.as_ref(), &chain_id.to_le_bytes()],
+        bump,
+    )]
+    pub price_reset: Account<'info, PriceResetApproval>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// CHECK: Factory admin log, checked against its derived address; skipped until opened
+    #[account(mut, seeds = [b"admin_log", token_factory.key().as_ref()], bump)]
+    pub admin_log: UncheckedAccount<'info>,
+}
+
+impl<'info> ApprovePriceReset<'info> {
+    // Lets the next price update from the chain through the price guard once, e.g. after a
+    // remote migration or a governance-approved repricing; a zero validity revokes it
+    pub fn process(&mut self, bump: u8, chain_id: u16, valid_for: i64) -> Result<()> {
+        let token_data = &self.token_data;
+        
+        // Verify authority
+        require_with_context!(
+            self.token_factory.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=approve_price_reset token_id={} chain_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            chain_id,
+            self.authority.key(),
+            self.token_factory.authority
+        );
+        require_with_context!(
+            (0..=MAX_PRICE_RESET_VALIDITY).contains(&valid_for),
+            TokenFactoryError::InvalidPriceGuard,
+            "instruction=approve_price_reset token_id={} chain_id={} valid_for={} max={}",
+            token_data.token_id,
+            chain_id,
+            valid_for,
+            MAX_PRICE_RESET_VALIDITY
+        );
+        
+        let now = Clock::get()?.unix_timestamp;
+        let expires_at = if valid_for == 0 { 0 } else { now + valid_for };
+        record_admin_action(
+            &self.admin_log,
+            self.authority.key(),
+            ADMIN_ACTION_PRICE_RESET,
+            chain_id,
+            token_data.mint,
+            admin_value(&[self.price_reset.expires_at as u64]),
+            admin_value(&[expires_at as u64]),
+        )?;
+        
+        let price_reset = &mut self.price_reset;
+        price_reset.mint = token_data.mint;
+        price_reset.chain_id = chain_id;
+        price_reset.expires_at = expires_at;
+        price_reset.bump = bump;
+        price_reset.account_version = ACCOUNT_VERSION;
+        
+        emit!(PriceResetApprovedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            chain_id,
+            expires_at,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+// Applies the chain's price guard to a price update, unless an unexpired reset approval is
+// present, which this consumes. Both accounts must already be checked against their derived
+// addresses. Returns the guard's reference price and source time to record with the update; a
+// reset opens a new window at the new price
+fn check_price_guard(
+    price_guard: &AccountInfo,
+    price_reset: &AccountInfo,
+    mirrored_curve: &MirroredCurveState,
+    new_price: u64,
+    source_ts: i64,
+    now: i64,
+) -> Result<(u64, i64)> {
+    if price_guard.data_is_empty() {
+        return Ok((new_price, source_ts));
+    }
+    let guard: Account<PriceGuard> = Account::try_from(price_guard)?;
+    let result = guard.check(mirrored_curve, new_price, source_ts);
+    if result.is_ok() || price_reset.data_is_empty() {
+        return result;
+    }
+    let mut approval: Account<PriceResetApproval> = Account::try_from(price_reset)?;
+    if approval.expires_at < now {
+        return result;
+    }
+    approval.expires_at = 0;
+    approval.exit(&crate::ID)?;
+    
+    emit!(PriceResetAppliedEvent {
+        mint: mirrored_curve.mint,
+        chain_id: mirrored_curve.chain_id,
+        previous_price: mirrored_curve.current_price,
+        new_price,
+        timestamp: now,
+    });
+    
+    Ok((new_price, source_ts))
+}
+
+// Bytes the attestor signs: the factory and source chain bind the price message to one
+// deployment and chain, so a signature cannot be replayed elsewhere
+pub fn attested_price_message(factory: &Pubkey, chain_id: u16, update: &PriceUpdatePayload) -> Vec<u8> {
+    let mut message = Vec::new();
+    message.extend_from_slice(factory.as_ref());
+    message.extend_from_slice(&chain_id.to_le_bytes());
+    message.extend_from_slice(&serialize_price_update_message(update));
+    message
+}
+
+/// Latest curve state of a token on one remote chain, as reported by that chain
+#[account]
+pub struct MirroredCurveState {
+    pub mint: Pubkey,
+    pub chain_id: u16,
+    pub current_price: u64,
+    pub current_supply: u64,
+    pub current_liquidity: u64,
+    pub last_sequence: u64,
+    pub source_timestamp: i64, // Source block time of the last applied update
+    pub updated_at: i64,
+    pub reference_price: u64, // Price the price guard's current window opened at; 0 until the first update
+    pub reference_timestamp: i64, // Source block time the current window opened at
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; 16], // ACCOUNT_RESERVED_LEN, less the two reference fields
+}
+
+/// Nonce of the last price update applied for a token from one remote chain. Shared by the
+/// Wormhole and attestor paths, so neither can roll back a price the other applied
+#[account]
+pub struct PriceUpdateNonce {
+    pub mint: Pubkey,
+    pub chain_id: u16,
+    pub last_nonce: u64,
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+impl PriceUpdateNonce {
+    // Rejects a price update that is not newer than the last one applied
+    pub fn apply(&mut self, mint: Pubkey, chain_id: u16, nonce: u64, bump: u8, instruction: &str) -> Result<()> {
+        require_with_context!(
+            nonce > self.last_nonce,
+            TokenFactoryError::StaleUpdate,
+            "instruction={} mint={} chain_id={} nonce={} last_nonce={}",
+            instruction,
+            mint,
+            chain_id,
+            nonce,
+            self.last_nonce
+        );
+        self.mint = mint;
+        self.chain_id = chain_id;
+        self.last_nonce = nonce;
+        self.bump = bump;
+        self.account_version = ACCOUNT_VERSION;
+        Ok(())
+    }
+}
+
+/// One-shot pass through the price guard for a token's updates from one chain
+#[account]
+pub struct PriceResetApproval {
+    pub mint: Pubkey,
+    pub chain_id: u16,
+    pub expires_at: i64, // 0 once used or revoked
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+#[event]
+pub struct PriceResetApprovedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub chain_id: u16,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PriceResetAppliedEvent {
+    pub mint: Pubkey,
+    pub chain_id: u16,
+    pub previous_price: u64,
+    pub new_price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CurveMirroredEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub source_chain: u16,
+    pub sequence: u64,
+    pub message_type: u8,
+    pub source: u8, // CURVE_SOURCE_*
+    pub current_price: u64,
+    pub current_supply: u64,
+    pub current_liquidity: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,49 @@
+// Notification module for Crossify Token Factory
+// This file contains the compact digest event wallet push-notification providers parse instead
+// of the full event set
+
+use anchor_lang::prelude::*;
+
+// Bumped whenever NotificationDigestEvent changes shape; providers skip versions they do not know
+#[constant]
+pub const DIGEST_VERSION: u8 = 1;
+
+// NotificationDigestEvent action codes; new codes are only ever appended
+#[constant]
+pub const DIGEST_ACTION_CREATED: u8 = 0;
+#[constant]
+pub const DIGEST_ACTION_BOUGHT: u8 = 1;
+#[constant]
+pub const DIGEST_ACTION_SOLD: u8 = 2;
+#[constant]
+pub const DIGEST_ACTION_GRADUATED: u8 = 3;
+#[constant]
+pub const DIGEST_ACTION_FEES_CLAIMED: u8 = 4;
+#[constant]
+pub const DIGEST_ACTION_BRIDGED_OUT: u8 = 5;
+#[constant]
+pub const DIGEST_ACTION_BRIDGED_IN: u8 = 6;
+#[constant]
+pub const DIGEST_ACTION_REFUNDED: u8 = 7;
+
+// Emitted right after the rich event it summarizes
+pub fn emit_digest(action: u8, mint: Pubkey, wallet: Pubkey, amount: u64, timestamp: i64) {
+    emit!(NotificationDigestEvent {
+        version: DIGEST_VERSION,
+        action,
+        mint,
+        wallet,
+        amount,
+        timestamp,
+    });
+}
+
+#[event]
+pub struct NotificationDigestEvent {
+    pub version: u8,    // DIGEST_VERSION
+    pub action: u8,     // DIGEST_ACTION_*
+    pub mint: Pubkey,
+    pub wallet: Pubkey, // Who the notification is for: trader, creator or bridge recipient
+    pub amount: u64,    // Token base units, or quote base units for graduation and fee claims
+    pub timestamp: i64,
+}
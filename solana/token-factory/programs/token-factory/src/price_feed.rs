@@ -0,0 +1,188 @@
+// Price feed module for Crossify Token Factory
+// This file contains the conservative curve price lending protocols value collateral at, kept
+// apart from the spot quotes trading and the UI use
+
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+use crate::{
+    bps_of, decimals_unit, quote_bonding_curve, TokenData, TokenFactoryError, TokenStats, ACCOUNT_RESERVED_LEN,
+    ACCOUNT_VERSION,
+};
+
+// Seconds the TWAP averages over; a feed younger than this is still warming up
+#[constant]
+pub const PRICE_FEED_WINDOW: i64 = 30 * 60;
+
+// Spot may sit this far from the TWAP, in basis points, before the feed is marked suspect
+#[constant]
+pub const MAX_SPOT_DEVIATION_BPS: u16 = 1_000;
+
+// LendingPriceFeed status values; only PRICE_STATUS_TRADING prices should back loans
+#[constant]
+pub const PRICE_STATUS_TRADING: u8 = 0;
+#[constant]
+pub const PRICE_STATUS_WARMING_UP: u8 = 1;
+#[constant]
+pub const PRICE_STATUS_SUSPECT: u8 = 2;
+#[constant]
+pub const PRICE_STATUS_HALTED: u8 = 3;
+
+#[derive(Accounts)]
+pub struct UpdatePriceFeed<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        seeds = [b"token_stats", token_data.mint.as_ref()],
+        bump = token_stats.bump,
+    )]
+    pub token_stats: Account<'info, TokenStats>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + size_of::<LendingPriceFeed>(),
+        seeds = [b"price_feed", token_data.mint.as_ref()],
+        bump,
+    )]
+    pub price_feed: Account<'info, LendingPriceFeed>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UpdatePriceFeed<'info> {
+    // Permissionless crank
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        let token_data = &self.token_data;
+        require_with_context!(
+            token_data.bonding_curve.configured,
+            TokenFactoryError::CurveNotConfigured,
+            "instruction=update_price_feed token_id={}",
+            token_data.token_id
+        );
+        
+        let one_token = u64::try_from(decimals_unit(token_data.decimals)).unwrap_or(u64::MAX);
+        let spot = quote_bonding_curve(
+            &token_data.bonding_curve,
+            token_data.decimals,
+            self.token_stats.curve_supply,
+            one_token,
+        )?;
+        let now = Clock::get()?.unix_timestamp;
+        
+        let price_feed = &mut self.price_feed;
+        if price_feed.mint == Pubkey::default() {
+            price_feed.mint = token_data.mint;
+            price_feed.quote_mint = token_data.bonding_curve.quote_mint;
+            price_feed.decimals = token_data.decimals;
+            price_feed.twap = spot;
+            price_feed.first_update = now;
+            price_feed.bump = bump;
+            price_feed.account_version = ACCOUNT_VERSION;
+        } else {
+            // The elapsed time is weighted at the previous spot, not this one, so moving the curve
+            // right before an update shifts the TWAP only once time has passed at that price
+            let elapsed = now.saturating_sub(price_feed.updated_at).clamp(0, PRICE_FEED_WINDOW) as u128;
+            let window = PRICE_FEED_WINDOW as u128;
+            let twap = (price_feed.twap as u128 * (window - elapsed) + price_feed.spot as u128 * elapsed) / window;
+            price_feed.twap = twap as u64;
+        }
+        price_feed.spot = spot;
+        price_feed.price = spot.min(price_feed.twap);
+        price_feed.updated_at = now;
+        price_feed.status = if !token_data.bonding_curve.enabled {
+            PRICE_STATUS_HALTED
+        } else if now.saturating_sub(price_feed.first_update) < PRICE_FEED_WINDOW {
+            PRICE_STATUS_WARMING_UP
+        } else if spot.abs_diff(price_feed.twap) > bps_of(price_feed.twap, MAX_SPOT_DEVIATION_BPS).unwrap_or(u64::MAX) {
+            PRICE_STATUS_SUSPECT
+        } else {
+            PRICE_STATUS_TRADING
+        };
+        
+        emit!(PriceFeedUpdatedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            price: price_feed.price,
+            twap: price_feed.twap,
+            spot,
+            status: price_feed.status,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct GetLendingPrice<'info> {
+    #[account(
+        seeds = [b"price_feed", price_feed.mint.as_ref()],
+        bump = price_feed.bump,
+    )]
+    pub price_feed: Account<'info, LendingPriceFeed>,
+}
+
+impl<'info> GetLendingPrice<'info> {
+    pub fn process(&self, max_age: i64) -> Result<u64> {
+        let now = Clock::get()?.unix_timestamp;
+        self.price_feed.lending_price(now, max_age)
+    }
+}
+
+/// Conservative price of one whole token in quote base units, for lending protocols.
+/// Fields are only ever appended, so readers can decode it at fixed offsets.
+#[account]
+pub struct LendingPriceFeed {
+    pub mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub decimals: u8,     // Of `mint`; prices are per 10^decimals base units
+    pub price: u64,       // min(twap, spot)
+    pub twap: u64,        // Over PRICE_FEED_WINDOW
+    pub spot: u64,        // Curve price at the last update
+    pub status: u8,       // PRICE_STATUS_*
+    pub updated_at: i64,
+    pub first_update: i64,
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+impl LendingPriceFeed {
+    // The conservative price, if it was refreshed within `max_age` seconds and looks sound
+    pub fn lending_price(&self, now: i64, max_age: i64) -> Result<u64> {
+        let age = now.saturating_sub(self.updated_at);
+        require_with_context!(
+            age <= max_age,
+            TokenFactoryError::PriceFeedStale,
+            "mint={} age={} max_age={}",
+            self.mint,
+            age,
+            max_age
+        );
+        require_with_context!(
+            self.status == PRICE_STATUS_TRADING,
+            TokenFactoryError::PriceFeedUnreliable,
+            "mint={} status={} spot={} twap={}",
+            self.mint,
+            self.status,
+            self.spot,
+            self.twap
+        );
+        Ok(self.price)
+    }
+}
+
+#[event]
+pub struct PriceFeedUpdatedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub price: u64,
+    pub twap: u64,
+    pub spot: u64,
+    pub status: u8,
+    pub timestamp: i64,
+}
@@ -0,0 +1,163 @@
+// Quote allowlist module for Crossify Token Factory
+// This file contains the admin-maintained list of assets a curve may be priced in, with the
+// decimals each is expected to have
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use std::mem::size_of;
+
+use crate::admin_log::{admin_value, record_admin_action, ADMIN_ACTION_QUOTE_MINT};
+use crate::{TokenFactory, TokenFactoryError, ACCOUNT_RESERVED_LEN, ACCOUNT_VERSION};
+
+#[derive(Accounts)]
+pub struct ConfigureQuoteMint<'info> {
+    #[account(seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    pub quote_mint: Account<'info, Mint>,
+    
+    /// CHECK: TokenStats PDA of the quote mint; must be empty, so curve tokens cannot price each other
+    #[account(seeds = [b"token_stats", quote_mint.key().as_ref()], bump)]
+    pub quote_token_stats: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + size_of::<AllowedQuoteMint>(),
+        seeds = [b"quote_mint", token_factory.key().as_ref(), quote_mint.key().as_ref()],
+        bump,
+    )]
+    pub allowed_quote_mint: Account<'info, AllowedQuoteMint>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// CHECK: Factory admin log, checked against its derived address; skipped until opened
+    #[account(mut, seeds = [b"admin_log", token_factory.key().as_ref()], bump)]
+    pub admin_log: UncheckedAccount<'info>,
+}
+
+impl<'info> ConfigureQuoteMint<'info> {
+    // Delisting only affects reserves initialized afterwards; existing curves keep their quote asset
+    pub fn process(&mut self, bump: u8, expected_decimals: u8, allowed: bool) -> Result<()> {
+        let quote_mint = self.quote_mint.key();
+        
+        // Verify authority
+        require_with_context!(
+            self.token_factory.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_quote_mint quote_mint={} signer={} expected_authority={}",
+            quote_mint,
+            self.authority.key(),
+            self.token_factory.authority
+        );
+        if allowed {
+            require_valid_quote_mint(
+                &self.quote_mint,
+                expected_decimals,
+                &self.quote_token_stats,
+                "configure_quote_mint",
+            )?;
+        }
+
+        let allowed_quote_mint = &mut self.allowed_quote_mint;
+        record_admin_action(
+            &self.admin_log,
+            self.authority.key(),
+            ADMIN_ACTION_QUOTE_MINT,
+            0,
+            quote_mint,
+            admin_value(&[allowed_quote_mint.allowed as u64, allowed_quote_mint.decimals as u64]),
+            admin_value(&[allowed as u64, expected_decimals as u64]),
+        )?;
+        allowed_quote_mint.factory = self.token_factory.key();
+        allowed_quote_mint.quote_mint = quote_mint;
+        allowed_quote_mint.decimals = expected_decimals;
+        allowed_quote_mint.allowed = allowed;
+        allowed_quote_mint.bump = bump;
+        allowed_quote_mint.account_version = ACCOUNT_VERSION;
+        
+        emit!(QuoteMintConfiguredEvent {
+            quote_mint,
+            decimals: expected_decimals,
+            allowed,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+/// A quote asset the factory authority has allowed curves to be priced in
+#[account]
+pub struct AllowedQuoteMint {
+    pub factory: Pubkey,
+    pub quote_mint: Pubkey,
+    pub decimals: u8, // Checked against the mint whenever a curve adopts it
+    pub allowed: bool,
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+// Rejects a quote mint that is not listed, was delisted, or no longer passes the listing checks.
+// Both accounts must already be checked against their derived addresses
+pub fn require_allowed_quote_mint(
+    quote_mint: &Account<Mint>,
+    allowed_quote_mint: &AccountInfo,
+    quote_token_stats: &AccountInfo,
+    instruction: &str,
+) -> Result<()> {
+    require_with_context!(
+        !allowed_quote_mint.data_is_empty(),
+        TokenFactoryError::QuoteMintNotAllowed,
+        "instruction={} quote_mint={}",
+        instruction,
+        quote_mint.key()
+    );
+    let entry: Account<AllowedQuoteMint> = Account::try_from(allowed_quote_mint)?;
+    require_with_context!(
+        entry.allowed,
+        TokenFactoryError::QuoteMintNotAllowed,
+        "instruction={} quote_mint={} delisted",
+        instruction,
+        quote_mint.key()
+    );
+    // A listed asset may have been registered as a curve token since
+    require_valid_quote_mint(quote_mint, entry.decimals, quote_token_stats, instruction)
+}
+
+fn require_valid_quote_mint(
+    quote_mint: &Account<Mint>,
+    expected_decimals: u8,
+    quote_token_stats: &AccountInfo,
+    instruction: &str,
+) -> Result<()> {
+    require_with_context!(
+        quote_token_stats.data_is_empty(),
+        TokenFactoryError::QuoteMintIsCurveToken,
+        "instruction={} quote_mint={}",
+        instruction,
+        quote_mint.key()
+    );
+    require_with_context!(
+        quote_mint.decimals == expected_decimals,
+        TokenFactoryError::QuoteMintDecimalsMismatch,
+        "instruction={} quote_mint={} decimals={} expected_decimals={}",
+        instruction,
+        quote_mint.key(),
+        quote_mint.decimals,
+        expected_decimals
+    );
+    Ok(())
+}
+
+#[event]
+pub struct QuoteMintConfiguredEvent {
+    pub quote_mint: Pubkey,
+    pub decimals: u8,
+    pub allowed: bool,
+    pub timestamp: i64,
+}
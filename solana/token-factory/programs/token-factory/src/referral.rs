@@ -0,0 +1,230 @@
+// Referral module for Crossify Token Factory
+// This file contains attribution codes that frontends register and pass to trades, so the volume
+// and fees each third-party UI routes are tallied on-chain for revenue sharing
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{TokenData, TokenFactory, TokenFactoryError, ACCOUNT_RESERVED_LEN, ACCOUNT_VERSION};
+
+// Codes are 3-16 lowercase ASCII letters, digits or hyphens, which also keeps them a valid seed
+pub const MIN_REFERRAL_CODE_LEN: usize = 3;
+pub const MAX_REFERRAL_CODE_LEN: usize = 16;
+
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct RegisterReferralCode<'info> {
+    #[account(seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    // Fails if the code is already registered
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + ReferralCode::LEN,
+        seeds = [b"referral_code", token_factory.key().as_ref(), code.as_bytes()],
+        bump,
+    )]
+    pub referral_code: Account<'info, ReferralCode>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RegisterReferralCode<'info> {
+    pub fn process(&mut self, bump: u8, code: String) -> Result<()> {
+        require_with_context!(
+            is_valid_referral_code(&code),
+            TokenFactoryError::InvalidReferralCode,
+            "instruction=register_referral_code code={} min_len={} max_len={}",
+            code,
+            MIN_REFERRAL_CODE_LEN,
+            MAX_REFERRAL_CODE_LEN
+        );
+        
+        let now = Clock::get()?.unix_timestamp;
+        let referral_code = &mut self.referral_code;
+        referral_code.factory = self.token_factory.key();
+        referral_code.code = code.clone();
+        referral_code.owner = self.owner.key();
+        referral_code.registered_at = now;
+        referral_code.bump = bump;
+        referral_code.account_version = ACCOUNT_VERSION;
+        
+        emit!(ReferralCodeRegisteredEvent {
+            referral_code: referral_code.key(),
+            code,
+            owner: self.owner.key(),
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct OpenReferralStats<'info> {
+    pub referral_code: Account<'info, ReferralCode>,
+    
+    pub quote_mint: Account<'info, Mint>,
+    
+    // Volumes in different quote assets cannot be summed, so each gets its own tally
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ReferralStats::LEN,
+        seeds = [b"referral_stats", referral_code.key().as_ref(), quote_mint.key().as_ref()],
+        bump,
+    )]
+    pub referral_stats: Account<'info, ReferralStats>,
+    
+    // Anyone may open a tally; the code's owner usually does
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> OpenReferralStats<'info> {
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        let referral_stats = &mut self.referral_stats;
+        referral_stats.referral_code = self.referral_code.key();
+        referral_stats.factory = self.referral_code.factory;
+        referral_stats.quote_mint = self.quote_mint.key();
+        referral_stats.bump = bump;
+        referral_stats.account_version = ACCOUNT_VERSION;
+        
+        emit!(ReferralStatsOpenedEvent {
+            referral_code: self.referral_code.key(),
+            referral_stats: referral_stats.key(),
+            quote_mint: self.quote_mint.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+pub fn is_valid_referral_code(code: &str) -> bool {
+    (MIN_REFERRAL_CODE_LEN..=MAX_REFERRAL_CODE_LEN).contains(&code.len())
+        && code
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+}
+
+// Credits a curve trade to the code the frontend passed, if any, in the tally for the trade's
+// quote mint. `volume` and `fees` are in `quote_mint` base units. Remote trades are not credited:
+// their payloads carry no code, and the relayer submitting them is not the frontend that routed them
+pub fn record_referral(
+    referral_stats: Option<&mut Account<ReferralStats>>,
+    token_data: &TokenData,
+    quote_mint: Pubkey,
+    volume: u64,
+    fees: u64,
+    now: i64,
+    instruction: &str,
+) -> Result<()> {
+    let referral_stats = match referral_stats {
+        Some(referral_stats) => referral_stats,
+        None => return Ok(()),
+    };
+    require_with_context!(
+        referral_stats.factory == token_data.factory,
+        TokenFactoryError::InvalidReferralCode,
+        "instruction={} token_id={} referral_stats={} factory={} expected_factory={}",
+        instruction,
+        token_data.token_id,
+        referral_stats.key(),
+        referral_stats.factory,
+        token_data.factory
+    );
+    require_with_context!(
+        referral_stats.quote_mint == quote_mint,
+        TokenFactoryError::InvalidReferralCode,
+        "instruction={} token_id={} referral_stats={} quote_mint={} expected_quote_mint={}",
+        instruction,
+        token_data.token_id,
+        referral_stats.key(),
+        referral_stats.quote_mint,
+        quote_mint
+    );
+    
+    referral_stats.trades = referral_stats.trades.saturating_add(1);
+    referral_stats.volume_routed = referral_stats.volume_routed.saturating_add(volume);
+    referral_stats.fees_generated = referral_stats.fees_generated.saturating_add(fees);
+    
+    emit!(ReferralTradeEvent {
+        referral_code: referral_stats.referral_code,
+        token_id: token_data.token_id,
+        mint: token_data.mint,
+        quote_mint,
+        volume,
+        fees,
+        timestamp: now,
+    });
+    
+    Ok(())
+}
+
+/// A frontend's attribution code
+#[account]
+pub struct ReferralCode {
+    pub factory: Pubkey,
+    pub code: String,
+    pub owner: Pubkey,
+    pub registered_at: i64,
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+impl ReferralCode {
+    pub const LEN: usize = 32 + 4 + MAX_REFERRAL_CODE_LEN + 32 + 8 + 1 + 1 + ACCOUNT_RESERVED_LEN;
+}
+
+/// What a referral code has routed through the factory's curves in one quote asset
+#[account]
+pub struct ReferralStats {
+    pub referral_code: Pubkey,
+    pub factory: Pubkey,
+    pub quote_mint: Pubkey,
+    pub trades: u64,
+    pub volume_routed: u64,  // Quote base units traded
+    pub fees_generated: u64, // Sell tax paid on those trades, in the same units
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+impl ReferralStats {
+    pub const LEN: usize = 32 + 32 + 32 + 8 + 8 + 8 + 1 + 1 + ACCOUNT_RESERVED_LEN;
+}
+
+#[event]
+pub struct ReferralCodeRegisteredEvent {
+    pub referral_code: Pubkey,
+    pub code: String,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralStatsOpenedEvent {
+    pub referral_code: Pubkey,
+    pub referral_stats: Pubkey,
+    pub quote_mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralTradeEvent {
+    pub referral_code: Pubkey,
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub volume: u64,
+    pub fees: u64,
+    pub timestamp: i64,
+}
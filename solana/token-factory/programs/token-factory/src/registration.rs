@@ -0,0 +1,443 @@
+// Registration module for Crossify Token Factory
+// This file contains the TokenData setup shared by new tokens and imported existing mints
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use std::mem::size_of;
+
+use crate::{
+    bind_symbol_registry, emit_digest, require_collection_member, BondingCurve, BridgeLimits, CrossChainInfo,
+    EmissionSchedule, GovernanceConfig, LiquidityLock, PendingReserveWithdrawal, RefundPolicy, SellLimits,
+    SymbolRegistry, TokenCreatedEvent, TokenData, TokenFactory, TokenFactoryError, TokenIndexPage, TokenStats,
+    ACCOUNT_VERSION, DIGEST_ACTION_CREATED, MAX_TOKEN_TAGS, TOKEN_DATA_VERSION, TOKEN_INDEX_PAGE_SIZE,
+    TOKEN_STATS_VERSION,
+};
+
+#[derive(Accounts)]
+#[instruction(name: String, symbol: String, metadata_uri: String)]
+pub struct RegisterExistingToken<'info> {
+    #[account(mut, seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = TokenData::space_for(name.len(), symbol.len(), metadata_uri.len()),
+    )]
+    pub token_data: Account<'info, TokenData>,
+    
+    // Only the mint authority can bring a mint into the factory. A mint whose authority was
+    // revoked has nobody to ask; its registrant becomes the creator and funds the supply vault
+    #[account(
+        constraint = mint.mint_authority == COption::Some(authority.key()) || mint.mint_authority.is_none()
+            @ TokenFactoryError::InvalidAuthority,
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    // One registration per mint, shared with tokens created here
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + size_of::<TokenStats>(),
+        seeds = [b"token_stats", mint.key().as_ref()],
+        bump,
+    )]
+    pub token_stats: Account<'info, TokenStats>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + TokenIndexPage::LEN,
+        seeds = [
+            b"token_index",
+            token_factory.key().as_ref(),
+            &(token_factory.token_count / TOKEN_INDEX_PAGE_SIZE).to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub token_index_page: Account<'info, TokenIndexPage>,
+    
+    // Creator's claim on the symbol; mandatory when the factory requires unique symbols
+    #[account(mut)]
+    pub symbol_registry: Option<Account<'info, SymbolRegistry>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    // An NFT of the factory's required collection held by the creator, and its Metaplex metadata;
+    // mandatory while creation is gated
+    pub collection_nft_account: Option<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Address, owner and contents are checked against collection_nft_account in process
+    pub collection_nft_metadata: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> RegisterExistingToken<'info> {
+    pub fn process(
+        &mut self,
+        token_stats_bump: u8,
+        token_index_page_bump: u8,
+        name: String,
+        symbol: String,
+        metadata_uri: String,
+    ) -> Result<()> {
+        let token_factory = &mut self.token_factory;
+        let token_data = &mut self.token_data;
+        let mint = &self.mint;
+        let authority = self.authority.key();
+        
+        // Existing holders keep their tokens; the supply at import stands in for the initial supply
+        init_token_data(
+            token_data,
+            token_factory.key(),
+            mint.key(),
+            token_factory.token_count,
+            name,
+            symbol,
+            mint.decimals,
+            metadata_uri,
+            authority,
+            mint.supply,
+            Clock::get()?.slot,
+        );
+        token_data.imported = true;
+        token_data.supply_from_vault = mint.mint_authority.is_none();
+        init_token_stats(&mut self.token_stats, mint.key(), token_data.token_id, token_stats_bump);
+        list_token(
+            &mut self.token_index_page,
+            token_factory.key(),
+            token_data.token_id,
+            token_data.key(),
+            token_index_page_bump,
+        );
+        bind_symbol_registry(
+            &token_factory.key(),
+            self.symbol_registry.as_mut(),
+            token_factory.require_unique_symbols,
+            &token_data.symbol,
+            &authority,
+            &mint.key(),
+        )?;
+        require_collection_member(
+            &token_factory.required_collection,
+            self.collection_nft_account.as_ref(),
+            self.collection_nft_metadata.as_ref().map(|metadata| metadata.as_ref()),
+            &authority,
+        )?;
+        
+        token_factory.token_count = token_factory
+            .token_count
+            .checked_add(1)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        
+        emit!(TokenImportedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            authority,
+            name: token_data.name.clone(),
+            symbol: token_data.symbol.clone(),
+            decimals: token_data.decimals,
+            supply: token_data.initial_supply,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+// Derived ids have the top bit set, so they never collide with sequential ones
+pub const DERIVED_TOKEN_ID_FLAG: u64 = 1 << 63;
+
+#[derive(Accounts)]
+#[instruction(name: String, symbol: String, decimals: u8, metadata_uri: String)]
+pub struct CreateTokenWithDerivedId<'info> {
+    // Read-only, so creations in the same slot do not serialize on the factory
+    #[account(seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = TokenData::space_for(name.len(), symbol.len(), metadata_uri.len()),
+    )]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = decimals,
+        mint::authority = authority.key(),
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + size_of::<TokenStats>(),
+        seeds = [b"token_stats", mint.key().as_ref()],
+        bump,
+    )]
+    pub token_stats: Account<'info, TokenStats>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = authority,
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+    
+    // Creator's claim on the symbol; mandatory when the factory requires unique symbols
+    #[account(mut)]
+    pub symbol_registry: Option<Account<'info, SymbolRegistry>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    
+    // An NFT of the factory's required collection held by the creator, and its Metaplex metadata;
+    // mandatory while creation is gated
+    pub collection_nft_account: Option<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Address, owner and contents are checked against collection_nft_account in process
+    pub collection_nft_metadata: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> CreateTokenWithDerivedId<'info> {
+    // Same as create_token, except the id comes from the mint and the token is not added to the
+    // paged index, which would need the factory's token_count; indexers pick it up from the event
+    pub fn process(
+        &mut self,
+        token_stats_bump: u8,
+        name: String,
+        symbol: String,
+        decimals: u8,
+        metadata_uri: String,
+        initial_supply: u64,
+    ) -> Result<()> {
+        let token_factory = &self.token_factory;
+        let token_data = &mut self.token_data;
+        let mint = self.mint.key();
+        let authority = self.authority.key();
+        
+        init_token_data(
+            token_data,
+            token_factory.key(),
+            mint,
+            derive_token_id(&mint),
+            name,
+            symbol,
+            decimals,
+            metadata_uri,
+            authority,
+            initial_supply,
+            Clock::get()?.slot,
+        );
+        init_token_stats(&mut self.token_stats, mint, token_data.token_id, token_stats_bump);
+        bind_symbol_registry(
+            &token_factory.key(),
+            self.symbol_registry.as_mut(),
+            token_factory.require_unique_symbols,
+            &token_data.symbol,
+            &authority,
+            &mint,
+        )?;
+        require_collection_member(
+            &token_factory.required_collection,
+            self.collection_nft_account.as_ref(),
+            self.collection_nft_metadata.as_ref().map(|metadata| metadata.as_ref()),
+            &authority,
+        )?;
+        
+        token::mint_to(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::MintTo {
+                    mint: self.mint.to_account_info(),
+                    to: self.token_account.to_account_info(),
+                    authority: self.authority.to_account_info(),
+                },
+            ),
+            initial_supply,
+        )?;
+        
+        let now = Clock::get()?.unix_timestamp;
+        emit!(TokenCreatedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            name: token_data.name.clone(),
+            symbol: token_data.symbol.clone(),
+            decimals: token_data.decimals,
+            initial_supply: token_data.initial_supply,
+            timestamp: now,
+        });
+        emit_digest(DIGEST_ACTION_CREATED, token_data.mint, authority, token_data.initial_supply, now);
+        
+        Ok(())
+    }
+}
+
+// Token id fixed by the mint alone, so it is the same in every deployment of the factory:
+// the first 8 bytes of keccak("token_id" || mint), little-endian, with the top bit set
+pub fn derive_token_id(mint: &Pubkey) -> u64 {
+    let hash = keccak::hashv(&[b"token_id", mint.as_ref()]).0;
+    let mut id = [0u8; 8];
+    id.copy_from_slice(&hash[..8]);
+    u64::from_le_bytes(id) | DERIVED_TOKEN_ID_FLAG
+}
+
+// Fresh TokenData with every feature off; callers set anything mode-specific afterwards
+#[allow(clippy::too_many_arguments)]
+pub fn init_token_data(
+    token_data: &mut TokenData,
+    factory: Pubkey,
+    mint: Pubkey,
+    token_id: u64,
+    name: String,
+    symbol: String,
+    decimals: u8,
+    metadata_uri: String,
+    authority: Pubkey,
+    initial_supply: u64,
+    created_slot: u64,
+) {
+    token_data.factory = factory;
+    token_data.mint = mint;
+    token_data.name = name;
+    token_data.symbol = symbol;
+    token_data.decimals = decimals;
+    token_data.metadata_uri = metadata_uri;
+    token_data.authority = authority;
+    token_data.initial_supply = initial_supply;
+    token_data.cross_chain_enabled = false;
+    token_data.cross_chain_info = CrossChainInfo::default();
+    token_data.token_id = token_id;
+    token_data.bonding_curve = BondingCurve::default();
+    token_data.mint_authority_bump = 0;
+    token_data.emission_schedule = EmissionSchedule::default();
+    token_data.governance = GovernanceConfig::default();
+    token_data.sell_limits = SellLimits::default();
+    token_data.refund_policy = RefundPolicy::default();
+    token_data.tags = [0; MAX_TOKEN_TAGS];
+    token_data.bridge_limits = BridgeLimits::default();
+    token_data.block_round_trips = false;
+    token_data.creator_verified = false;
+    token_data.creator_verified_at = 0;
+    token_data.metadata_frozen = false;
+    token_data.keep_metadata_mutable = false;
+    token_data.pending_reserve_withdrawal = PendingReserveWithdrawal::default();
+    token_data.version = TOKEN_DATA_VERSION;
+    token_data.imported = false;
+    token_data.bridge_mode = 0;
+    token_data.liquidity_locked = false;
+    token_data.liquidity_lock = LiquidityLock::default();
+    token_data.created_slot = created_slot;
+    token_data.supply_from_vault = false;
+}
+
+pub fn init_token_stats(token_stats: &mut TokenStats, mint: Pubkey, token_id: u64, bump: u8) {
+    token_stats.mint = mint;
+    token_stats.token_id = token_id;
+    token_stats.total_burned = 0;
+    token_stats.snapshot_count = 0;
+    token_stats.proposal_count = 0;
+    token_stats.curve_supply = 0;
+    token_stats.total_volume = 0;
+    token_stats.bridge_window_start_ts = 0;
+    token_stats.bridged_in_window = 0;
+    token_stats.total_bridged_out = 0;
+    token_stats.total_bridged_in = 0;
+    token_stats.last_synced_reserve = 0;
+    token_stats.last_sync_ts = 0;
+    token_stats.total_boosted = 0;
+    token_stats.bump = bump;
+    token_stats.version = TOKEN_STATS_VERSION;
+    token_stats.curve_holders = 0;
+    token_stats.last_sync_hash = [0; 32];
+    token_stats.total_creator_fees = 0;
+    token_stats.price_update_nonce = 0;
+}
+
+// Lists the token in the factory index so UIs can page without scanning
+pub fn list_token(
+    token_index_page: &mut TokenIndexPage,
+    factory: Pubkey,
+    token_id: u64,
+    token_data: Pubkey,
+    bump: u8,
+) {
+    if token_index_page.entries.is_empty() {
+        token_index_page.factory = factory;
+        token_index_page.page = token_id / TOKEN_INDEX_PAGE_SIZE;
+        token_index_page.bump = bump;
+        token_index_page.account_version = ACCOUNT_VERSION;
+    }
+    token_index_page.entries.push(token_data);
+}
+
+#[derive(Accounts)]
+pub struct EstimateCreateTokenCost<'info> {
+    #[account(seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+}
+
+impl<'info> EstimateCreateTokenCost<'info> {
+    // Rent create_token's payer funds for strings of these lengths; transaction fees are not included
+    pub fn process(&self, name_len: u32, symbol_len: u32, metadata_uri_len: u32) -> Result<CreateTokenCost> {
+        let rent = Rent::get()?;
+        
+        // Only the first token of a page creates the page
+        let opens_index_page = self.token_factory.token_count % TOKEN_INDEX_PAGE_SIZE == 0;
+        let token_data = rent.minimum_balance(TokenData::space_for(
+            name_len as usize,
+            symbol_len as usize,
+            metadata_uri_len as usize,
+        ));
+        let mint = rent.minimum_balance(Mint::LEN);
+        let token_stats = rent.minimum_balance(8 + size_of::<TokenStats>());
+        let token_account = rent.minimum_balance(TokenAccount::LEN);
+        let token_index_page = if opens_index_page {
+            rent.minimum_balance(8 + TokenIndexPage::LEN)
+        } else {
+            0
+        };
+        
+        Ok(CreateTokenCost {
+            token_data,
+            mint,
+            token_stats,
+            token_account,
+            token_index_page,
+            total: token_data + mint + token_stats + token_account + token_index_page,
+        })
+    }
+}
+
+/// Lamports create_token moves out of the payer, per account it creates
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateTokenCost {
+    pub token_data: u64,
+    pub mint: u64,
+    pub token_stats: u64,
+    pub token_account: u64,
+    pub token_index_page: u64, // 0 unless this token opens a new index page
+    pub total: u64,
+}
+
+#[event]
+pub struct TokenImportedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub authority: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub supply: u64,
+    pub timestamp: i64,
+}
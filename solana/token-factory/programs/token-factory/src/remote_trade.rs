@@ -0,0 +1,1187 @@
+// Remote trade module for Crossify Token Factory
+// This file contains curve trades requested from other chains through Wormhole, settled against
+// the token's remote quote vault
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use std::mem::size_of;
+
+use crate::wormhole::{
+    deserialize_wormhole_message, parse_posted_vaa, parse_remote_buy_message, parse_remote_sell_message,
+    serialize_quote_transfer_message, serialize_refund_message, serialize_token_transfer_message, wormhole, PostedVaa,
+    QuoteTransferPayload, RefundPayload, RemoteBuyPayload, RemoteSellPayload, TokenTransferPayload, UniversalAddress,
+};
+use crate::{
+    bps_of, bridged_volume_within_cap, curve_error, emit_digest, quote_bonding_curve, record_buy_milestones,
+    record_curve_sell, release_from_supply_vault, require_feature_enabled, require_message_type_allowed,
+    require_top_level, require_trading_enabled, secondary_supply, wormhole_core_bridge, wormhole_token_bridge,
+    BondingCurve, BuyerRecord, ChainSupply, CrossChainMessageSentEvent, ReceivedMessage, TokenData, TokenFactory,
+    TokenFactoryError, TokenStats, ACCOUNT_VERSION, BRIDGE_MODE_LOCK, DIGEST_ACTION_BOUGHT, FEATURE_BRIDGING,
+    FEATURE_TRADING,
+};
+
+// Token Bridge instruction index of TransferNative
+const TOKEN_BRIDGE_TRANSFER_NATIVE: u8 = 5;
+
+// Why a remote trade was refunded rather than settled
+#[constant]
+pub const REFUND_REASON_TRADING_CLOSED: u8 = 1; // Curve closed to remote trades, or the bridge cap is reached
+#[constant]
+pub const REFUND_REASON_SLIPPAGE: u8 = 2;
+#[constant]
+pub const REFUND_REASON_SOLD_OUT: u8 = 3; // Not enough curve supply to sell into, or supply vault balance to buy from
+#[constant]
+pub const REFUND_REASON_SELL_LIMIT: u8 = 4;
+
+#[derive(Accounts)]
+pub struct ExecuteRemoteBuy<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_stats", token_data.mint.as_ref()],
+        bump = token_stats.bump,
+    )]
+    pub token_stats: Account<'info, TokenStats>,
+    
+    #[account(mut, address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(address = token_data.bonding_curve.quote_mint)]
+    pub quote_mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA mint authority, signs the mint CPI
+    #[account(
+        seeds = [b"mint_authority", mint.key().as_ref()],
+        bump = token_data.mint_authority_bump,
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: PDA that owns the reserve and remote quote vaults; it holds no data
+    #[account(
+        seeds = [b"reserve_authority", mint.key().as_ref()],
+        bump = token_data.bonding_curve.reserve_authority_bump,
+    )]
+    pub reserve_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"reserve", mint.key().as_ref()],
+        bump,
+    )]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    
+    // Quote bridged in for remote buys; the Token Bridge pays transfers into it
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"remote_quote", mint.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = reserve_authority,
+    )]
+    pub remote_quote_vault: Account<'info, TokenAccount>,
+    
+    /// CHECK: Verified VAA posted by the Core Bridge; parsed and checked in `process`
+    #[account(owner = wormhole_core_bridge::ID)]
+    pub posted_vaa: UncheckedAccount<'info>,
+    
+    /// CHECK: EmitterPolicy PDA of the VAA's emitter, possibly uninitialized; checked in `process`
+    pub emitter_policy: UncheckedAccount<'info>,
+    
+    // Exists once a VAA has been executed, so every remote buy settles at most once
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + size_of::<ReceivedMessage>(),
+        seeds = [b"received", posted_vaa.key().as_ref()],
+        bump,
+    )]
+    pub received_message: Account<'info, ReceivedMessage>,
+    
+    // Required when the tokens are held on Solana; owned by the payload recipient
+    #[account(mut, token::mint = mint)]
+    pub recipient_token_account: Option<Account<'info, TokenAccount>>,
+    
+    // Required when lock-mode tokens are bridged on
+    #[account(
+        mut,
+        seeds = [b"custody", mint.key().as_ref()],
+        bump,
+    )]
+    pub custody_vault: Option<Account<'info, TokenAccount>>,
+    
+    // Per-chain accounting for the recipient chain; required when the tokens are bridged on
+    #[account(mut)]
+    pub chain_supply: Option<Account<'info, ChainSupply>>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    
+    // Read for the factory's feature switches
+    #[account(address = token_data.factory, seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    // Required for tokens whose curve supply comes from the supply vault
+    #[account(
+        mut,
+        seeds = [b"supply_vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub supply_vault: Option<Account<'info, TokenAccount>>,
+}
+
+impl<'info> ExecuteRemoteBuy<'info> {
+    // Permissionless, so any relayer can deliver the message
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        require_feature_enabled(&self.token_factory, FEATURE_TRADING, "execute_remote_buy")?;
+        require_feature_enabled(&self.token_factory, FEATURE_BRIDGING, "execute_remote_buy")?;
+        require_top_level("execute_remote_buy")?;
+        let token_data = &self.token_data;
+        let (vaa, payload) = read_trusted_message(
+            token_data,
+            &self.posted_vaa,
+            &self.emitter_policy,
+            wormhole::MSG_TYPE_REMOTE_BUY,
+            "execute_remote_buy",
+        )?;
+        let remote_buy = parse_remote_buy_message(&payload)?;
+        let to_solana = remote_buy.recipient_chain == wormhole::CHAIN_ID_SOLANA;
+        require_with_context!(
+            remote_buy.factory == token_data.factory
+                && remote_buy.token_id == token_data.token_id
+                && remote_buy.quote_amount > 0
+                && remote_buy.recipient.is_valid_for_chain(remote_buy.recipient_chain)
+                && (to_solana || token_data.cross_chain_info.supported_chains.contains(remote_buy.recipient_chain)),
+            TokenFactoryError::InvalidMessagePayload,
+            "instruction=execute_remote_buy token_id={} payload_factory={} payload_token_id={} quote_amount={} recipient_chain={}",
+            token_data.token_id,
+            remote_buy.factory,
+            remote_buy.token_id,
+            remote_buy.quote_amount,
+            remote_buy.recipient_chain
+        );
+        
+        // The VAA is consumed whether the buy settles or is refunded
+        let recipient = if to_solana { remote_buy.recipient.to_pubkey() } else { Pubkey::default() };
+        let received_message = &mut self.received_message;
+        received_message.posted_vaa = self.posted_vaa.key();
+        received_message.emitter_chain = vaa.emitter_chain;
+        received_message.sequence = vaa.sequence;
+        received_message.mint = token_data.mint;
+        received_message.recipient = recipient;
+        received_message.escrowed_amount = 0;
+        received_message.bump = bump;
+        received_message.account_version = ACCOUNT_VERSION;
+        
+        let now = Clock::get()?.unix_timestamp;
+        match self.settle(&vaa, &remote_buy, now) {
+            Err(error) => match refund_reason(&error) {
+                Some(reason) => {
+                    publish_refund(
+                        &self.token_data,
+                        &vaa,
+                        wormhole::MSG_TYPE_REMOTE_BUY,
+                        reason,
+                        remote_buy.quote_amount,
+                        remote_buy.buyer,
+                        now,
+                    );
+                    Ok(())
+                }
+                None => Err(error),
+            },
+            settled => settled,
+        }
+    }
+
+    // Every refundable check runs before the first transfer, so a refund leaves no trace of the buy
+    fn settle(&mut self, vaa: &PostedVaa, remote_buy: &RemoteBuyPayload, now: i64) -> Result<()> {
+        let token_data = &self.token_data;
+        let to_solana = remote_buy.recipient_chain == wormhole::CHAIN_ID_SOLANA;
+        require_trading_enabled(token_data, "execute_remote_buy", remote_buy.quote_amount)?;
+        require_with_context!(
+            now >= token_data.bonding_curve.trading_start_ts,
+            TokenFactoryError::TradingNotStarted,
+            "instruction=execute_remote_buy token_id={} now={} trading_start_ts={}",
+            token_data.token_id,
+            now,
+            token_data.bonding_curve.trading_start_ts
+        );
+        // Launch refunds are paid against buyer records, which remote buyers do not have
+        require_with_context!(
+            !token_data.refund_policy.enabled,
+            TokenFactoryError::RemoteTradeUnavailable,
+            "instruction=execute_remote_buy token_id={} refundable launch",
+            token_data.token_id
+        );
+        require_with_context!(
+            self.remote_quote_vault.amount >= remote_buy.quote_amount,
+            TokenFactoryError::RemoteQuoteUnfunded,
+            "instruction=execute_remote_buy token_id={} quote_amount={} remote_quote_balance={}",
+            token_data.token_id,
+            remote_buy.quote_amount,
+            self.remote_quote_vault.amount
+        );
+        
+        // The whole quote amount goes to the reserve; the rounding dust left over after the
+        // largest affordable buy stays there too
+        let curve_supply = self.token_stats.curve_supply;
+        let amount = tokens_for_quote(
+            &token_data.bonding_curve,
+            token_data.decimals,
+            curve_supply,
+            remote_buy.quote_amount,
+        )?;
+        require_with_context!(
+            amount > 0 && amount >= remote_buy.min_tokens,
+            TokenFactoryError::SlippageExceeded,
+            "instruction=execute_remote_buy token_id={} quote_amount={} amount={} min_tokens={}",
+            token_data.token_id,
+            remote_buy.quote_amount,
+            amount,
+            remote_buy.min_tokens
+        );
+        
+        // A fixed supply is handed out of the supply vault, which has nothing to give tokens that
+        // are never held on Solana
+        let is_lock_mode = token_data.bridge_mode == BRIDGE_MODE_LOCK;
+        if token_data.supply_from_vault {
+            require_with_context!(
+                to_solana || is_lock_mode,
+                TokenFactoryError::RemoteTradeUnavailable,
+                "instruction=execute_remote_buy token_id={} recipient_chain={} fixed supply bridges in lock mode only",
+                token_data.token_id,
+                remote_buy.recipient_chain
+            );
+            let vault_balance = self.supply_vault.as_ref().map_or(0, |supply_vault| supply_vault.amount);
+            require_with_context!(
+                vault_balance >= amount,
+                TokenFactoryError::SupplyVaultExhausted,
+                "instruction=execute_remote_buy token_id={} amount={} vault_balance={}",
+                token_data.token_id,
+                amount,
+                vault_balance
+            );
+        }
+
+        // Tokens bought for another chain are a bridge out, and count against the same daily cap
+        let bridged_in_window = if to_solana {
+            None
+        } else {
+            Some(bridged_volume_within_cap(token_data, &self.token_stats, amount, now, "execute_remote_buy")?)
+        };
+        
+        let mint_key = token_data.mint;
+        let reserve_bump = [token_data.bonding_curve.reserve_authority_bump];
+        let reserve_seeds: &[&[&[u8]]] = &[&[b"reserve_authority", mint_key.as_ref(), &reserve_bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.remote_quote_vault.to_account_info(),
+                    to: self.reserve_vault.to_account_info(),
+                    authority: self.reserve_authority.to_account_info(),
+                },
+                reserve_seeds,
+            ),
+            remote_buy.quote_amount,
+        )?;
+        
+        // Held here for a Solana recipient; otherwise the tokens leave for the recipient chain
+        // the way bridge_out would send them: locked in custody, or never minted here at all.
+        // A fixed supply is released from the supply vault rather than minted
+        let destination = if to_solana {
+            let recipient_token_account = self
+                .recipient_token_account
+                .as_ref()
+                .ok_or(TokenFactoryError::InvalidRecipient)?;
+            require_with_context!(
+                remote_buy.recipient == UniversalAddress::from_pubkey(&recipient_token_account.owner),
+                TokenFactoryError::InvalidRecipient,
+                "instruction=execute_remote_buy token_id={} recipient_token_account={} owner={}",
+                token_data.token_id,
+                recipient_token_account.key(),
+                recipient_token_account.owner
+            );
+            Some(recipient_token_account.to_account_info())
+        } else if is_lock_mode {
+            let custody_vault = self
+                .custody_vault
+                .as_ref()
+                .ok_or(TokenFactoryError::CustodyNotInitialized)?;
+            Some(custody_vault.to_account_info())
+        } else {
+            None
+        };
+        match destination {
+            Some(destination) if token_data.supply_from_vault => release_from_supply_vault(
+                token_data,
+                &self.token_program,
+                self.supply_vault.as_ref(),
+                self.mint_authority.to_account_info(),
+                destination,
+                amount,
+                "execute_remote_buy",
+            )?,
+            Some(destination) => {
+                let mint_bump = [token_data.mint_authority_bump];
+                let mint_seeds: &[&[&[u8]]] = &[&[b"mint_authority", mint_key.as_ref(), &mint_bump]];
+                token::mint_to(
+                    CpiContext::new_with_signer(
+                        self.token_program.to_account_info(),
+                        token::MintTo {
+                            mint: self.mint.to_account_info(),
+                            to: destination,
+                            authority: self.mint_authority.to_account_info(),
+                        },
+                        mint_seeds,
+                    ),
+                    amount,
+                )?;
+            }
+            None => {}
+        }
+        if !to_solana {
+            let chain_supply = self
+                .chain_supply
+                .as_mut()
+                .ok_or(TokenFactoryError::InvalidChainSupply)?;
+            require_with_context!(
+                chain_supply.mint == mint_key && chain_supply.chain_id == remote_buy.recipient_chain,
+                TokenFactoryError::InvalidChainSupply,
+                "instruction=execute_remote_buy token_id={} chain_supply={} recipient_chain={}",
+                token_data.token_id,
+                chain_supply.key(),
+                remote_buy.recipient_chain
+            );
+            chain_supply.bridged_out = chain_supply
+                .bridged_out
+                .checked_add(amount)
+                .ok_or(TokenFactoryError::MathOverflow)?;
+            self.token_stats.total_bridged_out = self
+                .token_stats
+                .total_bridged_out
+                .checked_add(amount)
+                .ok_or(TokenFactoryError::MathOverflow)?;
+            if let Some(bridged_in_window) = bridged_in_window {
+                self.token_stats.bridged_in_window = bridged_in_window;
+                self.token_stats.bridge_window_start_ts = now;
+            }
+            
+            let payload = serialize_token_transfer_message(&TokenTransferPayload {
+                factory: token_data.factory,
+                token_id: token_data.token_id,
+                amount,
+                sender: self.mint_authority.key(), // The curve, on behalf of the remote buyer
+                recipient_chain: remote_buy.recipient_chain,
+                recipient: remote_buy.recipient,
+                timestamp: now,
+            });
+            emit!(CrossChainMessageSentEvent {
+                token_id: token_data.token_id,
+                mint: mint_key,
+                target_chain: remote_buy.recipient_chain,
+                payload,
+                timestamp: now,
+            });
+        }
+
+        let token_stats = &mut self.token_stats;
+        token_stats.curve_supply = curve_supply
+            .checked_add(amount)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        token_stats.total_volume = token_stats
+            .total_volume
+            .checked_add(remote_buy.quote_amount)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        self.reserve_vault.reload()?;
+        let recipient = self.received_message.recipient;
+        record_buy_milestones(&mut self.token_data, self.reserve_vault.amount, recipient, now);
+        
+        emit!(RemoteBuyExecutedEvent {
+            token_id: self.token_data.token_id,
+            mint: mint_key,
+            source_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+            buyer: remote_buy.buyer,
+            recipient_chain: remote_buy.recipient_chain,
+            recipient: remote_buy.recipient,
+            quote_amount: remote_buy.quote_amount,
+            amount,
+            curve_supply: token_stats.curve_supply,
+            timestamp: now,
+        });
+        if to_solana {
+            emit_digest(DIGEST_ACTION_BOUGHT, mint_key, recipient, amount, now);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(seller: Pubkey)]
+pub struct ExecuteRemoteSell<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_stats", token_data.mint.as_ref()],
+        bump = token_stats.bump,
+    )]
+    pub token_stats: Account<'info, TokenStats>,
+    
+    #[account(mut, address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(address = token_data.bonding_curve.quote_mint)]
+    pub quote_mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA holding the delegated mint authority, which signs for custody; it holds no data
+    #[account(
+        seeds = [b"mint_authority", mint.key().as_ref()],
+        bump = token_data.mint_authority_bump,
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: PDA that owns the reserve and creator fee vaults and the proceeds escrow; it holds no data
+    #[account(
+        seeds = [b"reserve_authority", mint.key().as_ref()],
+        bump = token_data.bonding_curve.reserve_authority_bump,
+    )]
+    pub reserve_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"reserve", mint.key().as_ref()],
+        bump,
+    )]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"creator_fee_vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub creator_fee_vault: Account<'info, TokenAccount>,
+    
+    /// CHECK: Verified VAA posted by the Core Bridge; parsed and checked in `process`
+    #[account(owner = wormhole_core_bridge::ID)]
+    pub posted_vaa: UncheckedAccount<'info>,
+    
+    /// CHECK: EmitterPolicy PDA of the VAA's emitter, possibly uninitialized; checked in `process`
+    pub emitter_policy: UncheckedAccount<'info>,
+    
+    // Exists once a VAA has been executed, so every remote sell settles at most once
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + size_of::<ReceivedMessage>(),
+        seeds = [b"received", posted_vaa.key().as_ref()],
+        bump,
+    )]
+    pub received_message: Account<'info, ReceivedMessage>,
+    
+    // Holds this sell's proceeds until bridge_remote_proceeds sends them to the seller, apart from
+    // the remote quote vault that funds remote buys
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"remote_proceeds", received_message.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = reserve_authority,
+    )]
+    pub proceeds_escrow: Account<'info, TokenAccount>,
+    
+    // Keyed by the seller's Wormhole address, so the token's sell limits apply to remote sellers too
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + size_of::<BuyerRecord>(),
+        seeds = [b"buyer_record", mint.key().as_ref(), seller.as_ref()],
+        bump,
+    )]
+    pub buyer_record: Account<'info, BuyerRecord>,
+    
+    /// CHECK: SecondaryCurve PDA of the mint, possibly uninitialized; checked in `process`
+    #[account(seeds = [b"secondary_curve", mint.key().as_ref()], bump)]
+    pub secondary_curve: UncheckedAccount<'info>,
+    
+    // Required for lock-mode tokens, whose sold tokens leave custody
+    #[account(
+        mut,
+        seeds = [b"custody", mint.key().as_ref()],
+        bump,
+    )]
+    pub custody_vault: Option<Account<'info, TokenAccount>>,
+    
+    // Per-chain accounting for the source chain; required for lock-mode tokens
+    #[account(mut)]
+    pub chain_supply: Option<Account<'info, ChainSupply>>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    
+    // Read for the factory's feature switches
+    #[account(address = token_data.factory, seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    // Required for tokens whose curve supply comes from the supply vault
+    #[account(
+        mut,
+        seeds = [b"supply_vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub supply_vault: Option<Account<'info, TokenAccount>>,
+}
+
+impl<'info> ExecuteRemoteSell<'info> {
+    // Permissionless like execute_remote_buy; `seller` is the payload's seller as a key, naming
+    // its buyer record
+    pub fn process(&mut self, bump: u8, seller: Pubkey) -> Result<()> {
+        require_feature_enabled(&self.token_factory, FEATURE_TRADING, "execute_remote_sell")?;
+        require_feature_enabled(&self.token_factory, FEATURE_BRIDGING, "execute_remote_sell")?;
+        require_top_level("execute_remote_sell")?;
+        let token_data = &self.token_data;
+        let (vaa, payload) = read_trusted_message(
+            token_data,
+            &self.posted_vaa,
+            &self.emitter_policy,
+            wormhole::MSG_TYPE_REMOTE_SELL,
+            "execute_remote_sell",
+        )?;
+        let remote_sell = parse_remote_sell_message(&payload)?;
+        require_with_context!(
+            remote_sell.factory == token_data.factory
+                && remote_sell.token_id == token_data.token_id
+                && remote_sell.amount > 0
+                && remote_sell.seller.is_valid_for_chain(vaa.emitter_chain)
+                && remote_sell.seller.to_pubkey() == seller,
+            TokenFactoryError::InvalidMessagePayload,
+            "instruction=execute_remote_sell token_id={} payload_factory={} payload_token_id={} amount={} seller={}",
+            token_data.token_id,
+            remote_sell.factory,
+            remote_sell.token_id,
+            remote_sell.amount,
+            seller
+        );
+        
+        // The VAA is consumed whether the sell settles or is refunded
+        let received_message = &mut self.received_message;
+        received_message.posted_vaa = self.posted_vaa.key();
+        received_message.emitter_chain = vaa.emitter_chain;
+        received_message.sequence = vaa.sequence;
+        received_message.mint = token_data.mint;
+        received_message.recipient = seller;
+        received_message.escrowed_amount = 0;
+        received_message.bump = bump;
+        received_message.account_version = ACCOUNT_VERSION;
+        
+        let now = Clock::get()?.unix_timestamp;
+        match self.settle(&vaa, &remote_sell, seller, now) {
+            Err(error) => match refund_reason(&error) {
+                Some(reason) => {
+                    publish_refund(
+                        &self.token_data,
+                        &vaa,
+                        wormhole::MSG_TYPE_REMOTE_SELL,
+                        reason,
+                        remote_sell.amount,
+                        remote_sell.seller,
+                        now,
+                    );
+                    Ok(())
+                }
+                None => Err(error),
+            },
+            settled => settled,
+        }
+    }
+
+    // Every refundable check runs before the first transfer, so a refund leaves the curve untouched
+    fn settle(&mut self, vaa: &PostedVaa, remote_sell: &RemoteSellPayload, seller: Pubkey, now: i64) -> Result<()> {
+        let token_data = &self.token_data;
+        let amount = remote_sell.amount;
+        let curve_supply = self.token_stats.curve_supply;
+        require_trading_enabled(token_data, "execute_remote_sell", amount)?;
+        let secondary_supply = secondary_supply(&self.secondary_curve)?;
+        require_with_context!(
+            amount <= curve_supply.saturating_sub(secondary_supply),
+            TokenFactoryError::InsufficientCurveSupply,
+            "instruction=execute_remote_sell token_id={} amount={} curve_supply={} secondary_supply={}",
+            token_data.token_id,
+            amount,
+            curve_supply,
+            secondary_supply
+        );
+        
+        // The seller's tokens left circulation on the source chain before the message was sent;
+        // lock-mode tokens still have their Solana side in custody, which is burned here, or put
+        // back into the supply vault for a fixed supply
+        let is_lock_mode = token_data.bridge_mode == BRIDGE_MODE_LOCK;
+        require_with_context!(
+            !token_data.supply_from_vault || (is_lock_mode && self.supply_vault.is_some()),
+            TokenFactoryError::RemoteTradeUnavailable,
+            "instruction=execute_remote_sell token_id={} fixed supply sells need lock mode and the supply vault",
+            token_data.token_id
+        );
+        if let Some(chain_supply) = self.chain_supply.as_ref() {
+            require_with_context!(
+                chain_supply.mint == token_data.mint && chain_supply.chain_id == vaa.emitter_chain,
+                TokenFactoryError::InvalidChainSupply,
+                "instruction=execute_remote_sell token_id={} chain_supply={} emitter_chain={}",
+                token_data.token_id,
+                chain_supply.key(),
+                vaa.emitter_chain
+            );
+            require_with_context!(
+                !is_lock_mode || amount <= chain_supply.outstanding(),
+                TokenFactoryError::InvalidChainSupply,
+                "instruction=execute_remote_sell token_id={} amount={} outstanding={}",
+                token_data.token_id,
+                amount,
+                chain_supply.outstanding()
+            );
+        } else {
+            require_with_context!(
+                !is_lock_mode,
+                TokenFactoryError::InvalidChainSupply,
+                "instruction=execute_remote_sell token_id={} chain_supply account missing",
+                token_data.token_id
+            );
+        }
+
+        let remaining_supply = curve_supply - amount;
+        let gross_proceeds = quote_bonding_curve(
+            &token_data.bonding_curve,
+            token_data.decimals,
+            remaining_supply,
+            amount,
+        )?;
+        let sell_tax = bps_of(gross_proceeds, token_data.bonding_curve.sell_tax_bps)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        let proceeds = gross_proceeds - sell_tax;
+        require_with_context!(
+            proceeds >= remote_sell.min_proceeds,
+            TokenFactoryError::SlippageExceeded,
+            "instruction=execute_remote_sell token_id={} amount={} proceeds={} min_proceeds={}",
+            token_data.token_id,
+            amount,
+            proceeds,
+            remote_sell.min_proceeds
+        );
+        
+        let closes_position =
+            record_curve_sell(token_data, &mut self.buyer_record, seller, amount, now, "execute_remote_sell")?;
+        
+        let mint_key = token_data.mint;
+        if is_lock_mode {
+            let custody_vault = self
+                .custody_vault
+                .as_ref()
+                .ok_or(TokenFactoryError::CustodyNotInitialized)?;
+            let mint_bump = [token_data.mint_authority_bump];
+            let mint_seeds: &[&[&[u8]]] = &[&[b"mint_authority", mint_key.as_ref(), &mint_bump]];
+            if let Some(supply_vault) = self.supply_vault.as_ref().filter(|_| token_data.supply_from_vault) {
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        self.token_program.to_account_info(),
+                        token::Transfer {
+                            from: custody_vault.to_account_info(),
+                            to: supply_vault.to_account_info(),
+                            authority: self.mint_authority.to_account_info(),
+                        },
+                        mint_seeds,
+                    ),
+                    amount,
+                )?;
+            } else {
+                token::burn(
+                    CpiContext::new_with_signer(
+                        self.token_program.to_account_info(),
+                        token::Burn {
+                            mint: self.mint.to_account_info(),
+                            from: custody_vault.to_account_info(),
+                            authority: self.mint_authority.to_account_info(),
+                        },
+                        mint_seeds,
+                    ),
+                    amount,
+                )?;
+            }
+        }
+
+        // Proceeds wait in this sell's escrow for the transfer out; the tax goes to the fee vault
+        let reserve_bump = [token_data.bonding_curve.reserve_authority_bump];
+        let reserve_seeds: &[&[&[u8]]] = &[&[b"reserve_authority", mint_key.as_ref(), &reserve_bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.reserve_vault.to_account_info(),
+                    to: self.proceeds_escrow.to_account_info(),
+                    authority: self.reserve_authority.to_account_info(),
+                },
+                reserve_seeds,
+            ),
+            proceeds,
+        )?;
+        if sell_tax > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    token::Transfer {
+                        from: self.reserve_vault.to_account_info(),
+                        to: self.creator_fee_vault.to_account_info(),
+                        authority: self.reserve_authority.to_account_info(),
+                    },
+                    reserve_seeds,
+                ),
+                sell_tax,
+            )?;
+        }
+
+        let payload = serialize_quote_transfer_message(&QuoteTransferPayload {
+            factory: token_data.factory,
+            token_id: token_data.token_id,
+            quote_mint: token_data.bonding_curve.quote_mint,
+            amount: proceeds,
+            recipient_chain: vaa.emitter_chain,
+            recipient: remote_sell.seller,
+            timestamp: now,
+        });
+        emit!(CrossChainMessageSentEvent {
+            token_id: token_data.token_id,
+            mint: mint_key,
+            target_chain: vaa.emitter_chain,
+            payload,
+            timestamp: now,
+        });
+        
+        if let Some(chain_supply) = self.chain_supply.as_mut() {
+            chain_supply.bridged_in = chain_supply
+                .bridged_in
+                .checked_add(amount)
+                .ok_or(TokenFactoryError::MathOverflow)?;
+        }
+        let token_stats = &mut self.token_stats;
+        token_stats.total_bridged_in = token_stats
+            .total_bridged_in
+            .checked_add(amount)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        token_stats.curve_supply = remaining_supply;
+        if closes_position {
+            token_stats.curve_holders = token_stats.curve_holders.saturating_sub(1);
+        }
+        token_stats.total_volume = token_stats
+            .total_volume
+            .checked_add(gross_proceeds)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        token_stats.total_creator_fees = token_stats
+            .total_creator_fees
+            .checked_add(sell_tax)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        self.token_data.bonding_curve.trading_started = true;
+        
+        emit!(RemoteSellExecutedEvent {
+            token_id: self.token_data.token_id,
+            mint: mint_key,
+            source_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+            seller: remote_sell.seller,
+            amount,
+            proceeds,
+            sell_tax,
+            curve_supply: remaining_supply,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct BridgeRemoteProceeds<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(mut, address = token_data.bonding_curve.quote_mint)]
+    pub quote_mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA that owns the proceeds escrow; it holds no data
+    #[account(
+        seeds = [b"reserve_authority", token_data.mint.as_ref()],
+        bump = token_data.bonding_curve.reserve_authority_bump,
+    )]
+    pub reserve_authority: UncheckedAccount<'info>,
+    
+    // The executed remote sell, naming the seller and the chain the proceeds go to
+    #[account(constraint = received_message.mint == token_data.mint @ TokenFactoryError::InvalidMessagePayload)]
+    pub received_message: Account<'info, ReceivedMessage>,
+    
+    #[account(
+        mut,
+        seeds = [b"remote_proceeds", received_message.key().as_ref()],
+        bump,
+    )]
+    pub proceeds_escrow: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    // The Token Bridge validates its own and the Core Bridge's accounts below
+    
+    /// CHECK: Token Bridge config PDA
+    pub token_bridge_config: UncheckedAccount<'info>,
+    
+    /// CHECK: Token Bridge custody account for the quote mint
+    #[account(mut)]
+    pub token_bridge_custody: UncheckedAccount<'info>,
+    
+    /// CHECK: Token Bridge authority signer PDA, approved to move the proceeds
+    pub token_bridge_authority_signer: UncheckedAccount<'info>,
+    
+    /// CHECK: Token Bridge custody signer PDA
+    pub token_bridge_custody_signer: UncheckedAccount<'info>,
+    
+    /// CHECK: Core Bridge config PDA
+    #[account(mut)]
+    pub core_bridge_config: UncheckedAccount<'info>,
+    
+    /// CHECK: Fresh keypair for the transfer message
+    #[account(mut)]
+    pub core_message: Signer<'info>,
+    
+    /// CHECK: Token Bridge emitter PDA
+    pub core_emitter: UncheckedAccount<'info>,
+    
+    /// CHECK: Core Bridge sequence PDA of the Token Bridge emitter
+    #[account(mut)]
+    pub core_emitter_sequence: UncheckedAccount<'info>,
+    
+    /// CHECK: Core Bridge fee collector; the message fee must already be paid into it
+    #[account(mut)]
+    pub core_fee_collector: UncheckedAccount<'info>,
+    
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    
+    /// CHECK: Wormhole Core Bridge program
+    #[account(address = wormhole_core_bridge::ID)]
+    pub core_bridge_program: UncheckedAccount<'info>,
+    
+    /// CHECK: Wormhole Token Bridge program
+    #[account(address = wormhole_token_bridge::ID)]
+    pub token_bridge_program: UncheckedAccount<'info>,
+    
+    // Read for the factory's feature switches
+    #[account(address = token_data.factory, seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+}
+
+impl<'info> BridgeRemoteProceeds<'info> {
+    // Permissionless: the escrow can only ever be sent to the seller recorded at settlement. The
+    // escrow's rent goes to whoever pays for the transfer
+    pub fn process(&mut self, nonce: u32) -> Result<()> {
+        require_feature_enabled(&self.token_factory, FEATURE_BRIDGING, "bridge_remote_proceeds")?;
+        let token_data = &self.token_data;
+        let amount = self.proceeds_escrow.amount;
+        require_with_context!(
+            amount > 0,
+            TokenFactoryError::NothingToClaim,
+            "instruction=bridge_remote_proceeds token_id={} sequence={}",
+            token_data.token_id,
+            self.received_message.sequence
+        );
+        
+        let mint_key = token_data.mint;
+        let reserve_bump = [token_data.bonding_curve.reserve_authority_bump];
+        let reserve_seeds: &[&[&[u8]]] = &[&[b"reserve_authority", mint_key.as_ref(), &reserve_bump]];
+        token::approve(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token::Approve {
+                    to: self.proceeds_escrow.to_account_info(),
+                    delegate: self.token_bridge_authority_signer.to_account_info(),
+                    authority: self.reserve_authority.to_account_info(),
+                },
+                reserve_seeds,
+            ),
+            amount,
+        )?;
+        
+        let received_message = &self.received_message;
+        let mut data = Vec::with_capacity(55);
+        data.push(TOKEN_BRIDGE_TRANSFER_NATIVE);
+        data.extend_from_slice(&nonce.to_le_bytes());
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.extend_from_slice(&0u64.to_le_bytes()); // No relayer fee
+        data.extend_from_slice(&received_message.recipient.to_bytes());
+        data.extend_from_slice(&received_message.emitter_chain.to_le_bytes());
+        let transfer_ix = Instruction {
+            program_id: wormhole_token_bridge::ID,
+            accounts: vec![
+                AccountMeta::new(self.payer.key(), true),
+                AccountMeta::new_readonly(self.token_bridge_config.key(), false),
+                AccountMeta::new(self.proceeds_escrow.key(), false),
+                AccountMeta::new(self.quote_mint.key(), false),
+                AccountMeta::new(self.token_bridge_custody.key(), false),
+                AccountMeta::new_readonly(self.token_bridge_authority_signer.key(), false),
+                AccountMeta::new_readonly(self.token_bridge_custody_signer.key(), false),
+                AccountMeta::new(self.core_bridge_config.key(), false),
+                AccountMeta::new(self.core_message.key(), true),
+                AccountMeta::new_readonly(self.core_emitter.key(), false),
+                AccountMeta::new(self.core_emitter_sequence.key(), false),
+                AccountMeta::new(self.core_fee_collector.key(), false),
+                AccountMeta::new_readonly(self.clock.key(), false),
+                AccountMeta::new_readonly(self.rent.key(), false),
+                AccountMeta::new_readonly(self.system_program.key(), false),
+                AccountMeta::new_readonly(self.core_bridge_program.key(), false),
+                AccountMeta::new_readonly(self.token_program.key(), false),
+            ],
+            data,
+        };
+        invoke(
+            &transfer_ix,
+            &[
+                self.payer.to_account_info(),
+                self.token_bridge_config.to_account_info(),
+                self.proceeds_escrow.to_account_info(),
+                self.quote_mint.to_account_info(),
+                self.token_bridge_custody.to_account_info(),
+                self.token_bridge_authority_signer.to_account_info(),
+                self.token_bridge_custody_signer.to_account_info(),
+                self.core_bridge_config.to_account_info(),
+                self.core_message.to_account_info(),
+                self.core_emitter.to_account_info(),
+                self.core_emitter_sequence.to_account_info(),
+                self.core_fee_collector.to_account_info(),
+                self.clock.to_account_info(),
+                self.rent.to_account_info(),
+                self.system_program.to_account_info(),
+                self.core_bridge_program.to_account_info(),
+                self.token_program.to_account_info(),
+                self.token_bridge_program.to_account_info(),
+            ],
+        )?;
+        
+        token::close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            token::CloseAccount {
+                account: self.proceeds_escrow.to_account_info(),
+                destination: self.payer.to_account_info(),
+                authority: self.reserve_authority.to_account_info(),
+            },
+            reserve_seeds,
+        ))?;
+        
+        emit!(RemoteProceedsBridgedEvent {
+            token_id: token_data.token_id,
+            mint: mint_key,
+            target_chain: received_message.emitter_chain,
+            sequence: received_message.sequence,
+            seller: UniversalAddress::from_pubkey(&received_message.recipient),
+            amount,
+            message: self.core_message.key(),
+            nonce,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+// Parses a posted VAA from the token's trusted emitter and returns its body, which must be of
+// `message_type` and allowed by the emitter's policy
+pub fn read_trusted_message(
+    token_data: &TokenData,
+    posted_vaa: &AccountInfo,
+    emitter_policy: &AccountInfo,
+    message_type: u8,
+    instruction: &str,
+) -> Result<(PostedVaa, Vec<u8>)> {
+    let vaa = parse_posted_vaa(&posted_vaa.try_borrow_data()?)?;
+    require_with_context!(
+        token_data.cross_chain_enabled,
+        TokenFactoryError::CrossChainNotEnabled,
+        "instruction={} token_id={} emitter_chain={}",
+        instruction,
+        token_data.token_id,
+        vaa.emitter_chain
+    );
+    require_with_context!(
+        token_data.cross_chain_info.supported_chains.contains(vaa.emitter_chain)
+            && vaa.emitter_address == token_data.cross_chain_info.wormhole_emitter,
+        TokenFactoryError::UntrustedEmitter,
+        "instruction={} token_id={} emitter_chain={} emitter_address={:?}",
+        instruction,
+        token_data.token_id,
+        vaa.emitter_chain,
+        vaa.emitter_address.bytes
+    );
+    
+    let (received_type, payload) = deserialize_wormhole_message(&vaa.payload)?;
+    require_message_type_allowed(
+        emitter_policy,
+        &token_data.factory,
+        vaa.emitter_chain,
+        &vaa.emitter_address,
+        received_type,
+        instruction,
+    )?;
+    require_with_context!(
+        received_type == message_type,
+        TokenFactoryError::UnknownMessageType,
+        "instruction={} token_id={} message_type={}",
+        instruction,
+        token_data.token_id,
+        received_type
+    );
+    Ok((vaa, payload))
+}
+
+// Refund reason for a remote trade rejected with `error`, or None if the error must fail the
+// transaction instead, leaving the VAA to be retried
+fn refund_reason(error: &Error) -> Option<u8> {
+    let code = match error {
+        Error::AnchorError(error) => error.error_code_number,
+        Error::ProgramError(_) => return None,
+    };
+    let is = |variant: TokenFactoryError| code == u32::from(variant);
+    if is(TokenFactoryError::SlippageExceeded) {
+        Some(REFUND_REASON_SLIPPAGE)
+    } else if is(TokenFactoryError::InsufficientCurveSupply) || is(TokenFactoryError::SupplyVaultExhausted) {
+        Some(REFUND_REASON_SOLD_OUT)
+    } else if is(TokenFactoryError::SellCooldownActive) || is(TokenFactoryError::SellLimitExceeded) {
+        Some(REFUND_REASON_SELL_LIMIT)
+    } else if is(TokenFactoryError::CurveNotConfigured)
+        || is(TokenFactoryError::CurveDisabled)
+        || is(TokenFactoryError::ReserveNotInitialized)
+        || is(TokenFactoryError::AuctionInProgress)
+        || is(TokenFactoryError::LaunchFailed)
+        || is(TokenFactoryError::TradingNotStarted)
+        || is(TokenFactoryError::RemoteTradeUnavailable)
+        || is(TokenFactoryError::BridgeCapExceeded)
+    {
+        Some(REFUND_REASON_TRADING_CLOSED)
+    } else {
+        None
+    }
+}
+
+// Tells the source chain to release what it escrowed for a rejected remote trade: the quote
+// paid for a buy, or the tokens of a sell
+fn publish_refund(
+    token_data: &TokenData,
+    vaa: &PostedVaa,
+    message_type: u8,
+    reason: u8,
+    amount: u64,
+    recipient: UniversalAddress,
+    now: i64,
+) {
+    let payload = serialize_refund_message(&RefundPayload {
+        factory: token_data.factory,
+        token_id: token_data.token_id,
+        sequence: vaa.sequence,
+        message_type,
+        reason,
+        amount,
+        recipient,
+        timestamp: now,
+    });
+    emit!(CrossChainMessageSentEvent {
+        token_id: token_data.token_id,
+        mint: token_data.mint,
+        target_chain: vaa.emitter_chain,
+        payload,
+        timestamp: now,
+    });
+    emit!(RemoteTradeRefundedEvent {
+        token_id: token_data.token_id,
+        mint: token_data.mint,
+        source_chain: vaa.emitter_chain,
+        sequence: vaa.sequence,
+        message_type,
+        reason,
+        amount,
+        recipient,
+        timestamp: now,
+    });
+}
+
+// Largest buy, in base units, that `quote_amount` pays for at `supply`
+pub fn tokens_for_quote(bonding_curve: &BondingCurve, decimals: u8, supply: u64, quote_amount: u64) -> Result<u64> {
+    crossify_common::tokens_for_quote(&bonding_curve.params(), decimals, supply, quote_amount)
+        .map_err(curve_error)
+}
+
+#[event]
+pub struct RemoteBuyExecutedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub source_chain: u16,
+    pub sequence: u64,
+    pub buyer: UniversalAddress,
+    pub recipient_chain: u16,
+    pub recipient: UniversalAddress,
+    pub quote_amount: u64,
+    pub amount: u64,
+    pub curve_supply: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RemoteSellExecutedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub source_chain: u16,
+    pub sequence: u64,
+    pub seller: UniversalAddress,
+    pub amount: u64,
+    pub proceeds: u64, // Owed to the seller on the source chain
+    pub sell_tax: u64,
+    pub curve_supply: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RemoteProceedsBridgedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub target_chain: u16,
+    pub sequence: u64, // Of the remote sell VAA the proceeds came from
+    pub seller: UniversalAddress,
+    pub amount: u64,
+    pub message: Pubkey,
+    pub nonce: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RemoteTradeRefundedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub source_chain: u16,
+    pub sequence: u64,
+    pub message_type: u8, // MSG_TYPE_REMOTE_BUY or MSG_TYPE_REMOTE_SELL
+    pub reason: u8,       // REFUND_REASON_*
+    pub amount: u64,      // Quote for a buy, tokens for a sell
+    pub recipient: UniversalAddress,
+    pub timestamp: i64,
+}
@@ -0,0 +1,306 @@
+// Session module for Crossify Token Factory
+// This file contains short-lived session keys that trade on a wallet's behalf within spend limits
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+use std::mem::size_of;
+
+use crate::{TokenFactoryError, ACCOUNT_RESERVED_LEN, ACCOUNT_VERSION};
+
+// Longest session a wallet can authorize in one signature
+pub const MAX_SESSION_DURATION: i64 = 7 * 86_400;
+
+#[derive(Accounts)]
+#[instruction(session_key: Pubkey)]
+pub struct CreateSession<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + size_of::<TradingSession>(),
+        seeds = [b"session", owner.key().as_ref(), session_key.as_ref()],
+        bump,
+    )]
+    pub session: Account<'info, TradingSession>,
+    
+    // The session PDA is approved as delegate for up to the spend limit
+    #[account(mut, token::authority = owner)]
+    pub owner_quote_account: Account<'info, TokenAccount>,
+    
+    // Approved for up to the sell limit, when one is set
+    #[account(mut, token::authority = owner)]
+    pub owner_token_account: Option<Account<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateSession<'info> {
+    pub fn process(
+        &mut self,
+        bump: u8,
+        session_key: Pubkey,
+        mint: Pubkey,
+        max_quote_spend: u64,
+        max_tokens_sold: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require_with_context!(
+            expires_at > now && expires_at <= now.saturating_add(MAX_SESSION_DURATION),
+            TokenFactoryError::InvalidSession,
+            "instruction=create_session owner={} expires_at={} now={} max_duration={}",
+            self.owner.key(),
+            expires_at,
+            now,
+            MAX_SESSION_DURATION
+        );
+        require_with_context!(
+            self.owner_token_account.is_some() || max_tokens_sold == 0,
+            TokenFactoryError::InvalidSession,
+            "instruction=create_session owner={} max_tokens_sold={} owner_token_account missing",
+            self.owner.key(),
+            max_tokens_sold
+        );
+        
+        // SPL delegation caps what the session can move even before the limits below apply
+        token::approve(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Approve {
+                    to: self.owner_quote_account.to_account_info(),
+                    delegate: self.session.to_account_info(),
+                    authority: self.owner.to_account_info(),
+                },
+            ),
+            max_quote_spend,
+        )?;
+        if let Some(owner_token_account) = self.owner_token_account.as_ref() {
+            token::approve(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    token::Approve {
+                        to: owner_token_account.to_account_info(),
+                        delegate: self.session.to_account_info(),
+                        authority: self.owner.to_account_info(),
+                    },
+                ),
+                max_tokens_sold,
+            )?;
+        }
+
+        let session = &mut self.session;
+        session.owner = self.owner.key();
+        session.session_key = session_key;
+        session.mint = mint;
+        session.max_quote_spend = max_quote_spend;
+        session.quote_spent = 0;
+        session.max_tokens_sold = max_tokens_sold;
+        session.tokens_sold = 0;
+        session.expires_at = expires_at;
+        session.bump = bump;
+        session.account_version = ACCOUNT_VERSION;
+        
+        emit!(SessionCreatedEvent {
+            owner: session.owner,
+            session_key,
+            mint,
+            max_quote_spend,
+            max_tokens_sold,
+            expires_at,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CloseSession<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"session", owner.key().as_ref(), session.session_key.as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, TradingSession>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+impl<'info> CloseSession<'info> {
+    // Remaining SPL approvals are left for the owner to revoke; without the session account
+    // the program no longer signs for them
+    pub fn process(&mut self) -> Result<()> {
+        emit!(SessionClosedEvent {
+            owner: self.owner.key(),
+            session_key: self.session.session_key,
+            quote_spent: self.session.quote_spent,
+            tokens_sold: self.session.tokens_sold,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+/// Authorization for `session_key` to trade for `owner` until `expires_at`
+#[account]
+pub struct TradingSession {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub mint: Pubkey, // Only this mint can be traded; default = any mint
+    pub max_quote_spend: u64,
+    pub quote_spent: u64,
+    pub max_tokens_sold: u64,
+    pub tokens_sold: u64,
+    pub expires_at: i64,
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+impl TradingSession {
+    // Checks `signer` may trade `mint` under this session now and records the spend or sale
+    pub fn charge(
+        &mut self,
+        signer: &Pubkey,
+        mint: &Pubkey,
+        quote_spent: u64,
+        tokens_sold: u64,
+        instruction: &str,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require_with_context!(
+            self.session_key == *signer
+                && now < self.expires_at
+                && (self.mint == Pubkey::default() || self.mint == *mint),
+            TokenFactoryError::InvalidSession,
+            "instruction={} session_key={} signer={} expires_at={} now={}",
+            instruction,
+            self.session_key,
+            signer,
+            self.expires_at,
+            now
+        );
+        let total_spent = self
+            .quote_spent
+            .checked_add(quote_spent)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        let total_sold = self
+            .tokens_sold
+            .checked_add(tokens_sold)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        require_with_context!(
+            total_spent <= self.max_quote_spend && total_sold <= self.max_tokens_sold,
+            TokenFactoryError::SessionLimitExceeded,
+            "instruction={} session_key={} quote_spent={} max_quote_spend={} tokens_sold={} max_tokens_sold={}",
+            instruction,
+            self.session_key,
+            total_spent,
+            self.max_quote_spend,
+            total_sold,
+            self.max_tokens_sold
+        );
+        self.quote_spent = total_spent;
+        self.tokens_sold = total_sold;
+        Ok(())
+    }
+}
+
+// Moves `amount` out of a trader's token account, signed by the trader or by their session PDA
+pub fn trader_transfer<'info>(
+    token_program: &Program<'info, Token>,
+    from: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    signer: AccountInfo<'info>,
+    session: Option<&Account<'info, TradingSession>>,
+    amount: u64,
+) -> Result<()> {
+    match session {
+        Some(session) => {
+            let bump = [session.bump];
+            let signer_seeds: &[&[&[u8]]] =
+                &[&[b"session", session.owner.as_ref(), session.session_key.as_ref(), &bump]];
+            token::transfer(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    token::Transfer {
+                        from,
+                        to,
+                        authority: session.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+            )
+        }
+        None => token::transfer(
+            CpiContext::new(
+                token_program.to_account_info(),
+                token::Transfer { from, to, authority: signer },
+            ),
+            amount,
+        ),
+    }
+}
+
+// Burns `amount` from a trader's token account, signed by the trader or by their session PDA
+pub fn trader_burn<'info>(
+    token_program: &Program<'info, Token>,
+    mint: AccountInfo<'info>,
+    from: AccountInfo<'info>,
+    signer: AccountInfo<'info>,
+    session: Option<&Account<'info, TradingSession>>,
+    amount: u64,
+) -> Result<()> {
+    match session {
+        Some(session) => {
+            let bump = [session.bump];
+            let signer_seeds: &[&[&[u8]]] =
+                &[&[b"session", session.owner.as_ref(), session.session_key.as_ref(), &bump]];
+            token::burn(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    token::Burn {
+                        mint,
+                        from,
+                        authority: session.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+            )
+        }
+        None => token::burn(
+            CpiContext::new(
+                token_program.to_account_info(),
+                token::Burn { mint, from, authority: signer },
+            ),
+            amount,
+        ),
+    }
+}
+
+#[event]
+pub struct SessionCreatedEvent {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub mint: Pubkey,
+    pub max_quote_spend: u64,
+    pub max_tokens_sold: u64,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SessionClosedEvent {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub quote_spent: u64,
+    pub tokens_sold: u64,
+    pub timestamp: i64,
+}
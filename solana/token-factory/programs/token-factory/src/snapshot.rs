@@ -0,0 +1,299 @@
+// Snapshot module for Crossify Token Factory
+// This file contains holder-balance checkpoints and pro-rata distributions keyed by snapshot id
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use std::mem::size_of;
+
+use crate::{TokenData, TokenFactoryError, TokenStats, ACCOUNT_RESERVED_LEN, ACCOUNT_VERSION};
+
+#[derive(Accounts)]
+pub struct TakeSnapshot<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_stats", token_data.mint.as_ref()],
+        bump = token_stats.bump,
+    )]
+    pub token_stats: Account<'info, TokenStats>,
+    
+    #[account(address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + size_of::<Snapshot>(),
+        seeds = [b"snapshot", mint.key().as_ref(), &token_stats.snapshot_count.to_le_bytes()],
+        bump,
+    )]
+    pub snapshot: Account<'info, Snapshot>,
+    
+    pub distribution_mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"snapshot_vault", snapshot.key().as_ref()],
+        bump,
+        token::mint = distribution_mint,
+        token::authority = snapshot,
+    )]
+    pub distribution_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = distribution_mint,
+        token::authority = authority,
+    )]
+    pub funding_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> TakeSnapshot<'info> {
+    pub fn process(&mut self, bump: u8, balances_root: [u8; 32], distribution_amount: u64) -> Result<()> {
+        // Verify authority
+        require_with_context!(
+            self.token_data.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=take_snapshot token_id={} signer={} expected_authority={}",
+            self.token_data.token_id,
+            self.authority.key(),
+            self.token_data.authority
+        );
+        
+        // Fund the distribution reserve for this snapshot
+        if distribution_amount > 0 {
+            token::transfer(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    token::Transfer {
+                        from: self.funding_account.to_account_info(),
+                        to: self.distribution_vault.to_account_info(),
+                        authority: self.authority.to_account_info(),
+                    },
+                ),
+                distribution_amount,
+            )?;
+        }
+
+        let clock = Clock::get()?;
+        let snapshot = &mut self.snapshot;
+        snapshot.mint = self.mint.key();
+        snapshot.token_id = self.token_data.token_id;
+        snapshot.snapshot_id = self.token_stats.snapshot_count;
+        snapshot.slot = clock.slot;
+        snapshot.timestamp = clock.unix_timestamp;
+        snapshot.total_supply = self.mint.supply;
+        snapshot.reserve_balance = distribution_amount;
+        snapshot.balances_root = balances_root;
+        snapshot.distribution_mint = self.distribution_mint.key();
+        snapshot.distribution_vault = self.distribution_vault.key();
+        snapshot.total_claimed = 0;
+        snapshot.bump = bump;
+        snapshot.account_version = ACCOUNT_VERSION;
+        
+        self.token_stats.snapshot_count = self
+            .token_stats
+            .snapshot_count
+            .checked_add(1)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        
+        emit!(SnapshotTakenEvent {
+            token_id: snapshot.token_id,
+            mint: snapshot.mint,
+            snapshot_id: snapshot.snapshot_id,
+            slot: snapshot.slot,
+            total_supply: snapshot.total_supply,
+            reserve_balance: snapshot.reserve_balance,
+            balances_root,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ClaimSnapshotDistribution<'info> {
+    #[account(mut)]
+    pub snapshot: Account<'info, Snapshot>,
+    
+    #[account(
+        init,
+        payer = claimant,
+        space = 8 + size_of::<SnapshotClaim>(),
+        seeds = [b"snapshot_claim", snapshot.key().as_ref(), claimant.key().as_ref()],
+        bump,
+    )]
+    pub claim_record: Account<'info, SnapshotClaim>,
+    
+    #[account(mut, address = snapshot.distribution_vault)]
+    pub distribution_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = snapshot.distribution_mint,
+        token::authority = claimant,
+    )]
+    pub claimant_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimSnapshotDistribution<'info> {
+    pub fn process(&mut self, balance: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        let snapshot = &self.snapshot;
+        let claimant = self.claimant.key();
+        
+        // Verify the claimed balance against the snapshot's balances root
+        require_with_context!(
+            verify_balance_proof(&snapshot.balances_root, &claimant, balance, &proof),
+            TokenFactoryError::InvalidSnapshotProof,
+            "instruction=claim_snapshot_distribution token_id={} snapshot_id={} claimant={} balance={}",
+            snapshot.token_id,
+            snapshot.snapshot_id,
+            claimant,
+            balance
+        );
+        
+        let amount = pro_rata_share(balance, snapshot.reserve_balance, snapshot.total_supply)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        require_with_context!(
+            amount > 0,
+            TokenFactoryError::NothingToClaim,
+            "instruction=claim_snapshot_distribution token_id={} snapshot_id={} claimant={} balance={}",
+            snapshot.token_id,
+            snapshot.snapshot_id,
+            claimant,
+            balance
+        );
+        
+        let mint_key = snapshot.mint;
+        let snapshot_id = snapshot.snapshot_id.to_le_bytes();
+        let bump = [snapshot.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"snapshot", mint_key.as_ref(), &snapshot_id, &bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.distribution_vault.to_account_info(),
+                    to: self.claimant_token_account.to_account_info(),
+                    authority: self.snapshot.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+        
+        let claim_record = &mut self.claim_record;
+        claim_record.snapshot = self.snapshot.key();
+        claim_record.claimant = claimant;
+        claim_record.account_version = ACCOUNT_VERSION;
+        claim_record.balance = balance;
+        claim_record.amount = amount;
+        
+        let snapshot = &mut self.snapshot;
+        snapshot.total_claimed = snapshot
+            .total_claimed
+            .checked_add(amount)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        
+        emit!(SnapshotDistributionClaimedEvent {
+            token_id: snapshot.token_id,
+            snapshot_id: snapshot.snapshot_id,
+            claimant,
+            balance,
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+// Amount of the reserve owed to a holder of `balance` out of `total_supply`
+pub fn pro_rata_share(balance: u64, reserve_balance: u64, total_supply: u64) -> Option<u64> {
+    if total_supply == 0 {
+        return Some(0);
+    }
+    let share = (balance as u128)
+        .checked_mul(reserve_balance as u128)?
+        .checked_div(total_supply as u128)?;
+    u64::try_from(share).ok()
+}
+
+// Leaves are keccak(holder || balance), nodes are keccak of the sorted child pair
+pub fn verify_balance_proof(root: &[u8; 32], holder: &Pubkey, balance: u64, proof: &[[u8; 32]]) -> bool {
+    let mut node = keccak::hashv(&[holder.as_ref(), &balance.to_le_bytes()]).0;
+    for sibling in proof.iter() {
+        node = if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).0
+        } else {
+            keccak::hashv(&[sibling, &node]).0
+        };
+    }
+    node == *root
+}
+
+#[account]
+pub struct Snapshot {
+    pub mint: Pubkey,
+    pub token_id: u64,
+    pub snapshot_id: u64,
+    pub slot: u64,
+    pub timestamp: i64,
+    pub total_supply: u64,
+    pub reserve_balance: u64,
+    pub balances_root: [u8; 32], // Merkle root of holder balances at `slot`
+    pub distribution_mint: Pubkey,
+    pub distribution_vault: Pubkey,
+    pub total_claimed: u64,
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+#[account]
+pub struct SnapshotClaim {
+    pub snapshot: Pubkey,
+    pub claimant: Pubkey,
+    pub balance: u64,
+    pub amount: u64,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+#[event]
+pub struct SnapshotTakenEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub snapshot_id: u64,
+    pub slot: u64,
+    pub total_supply: u64,
+    pub reserve_balance: u64,
+    pub balances_root: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SnapshotDistributionClaimedEvent {
+    pub token_id: u64,
+    pub snapshot_id: u64,
+    pub claimant: Pubkey,
+    pub balance: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,388 @@
+// Solvency module for Crossify Token Factory
+// This file contains checks of the reserve vault against what the curve owes its holders
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::{quote_bonding_curve, secondary_supply, SecondaryCurve, TokenData, TokenFactoryError, TokenStats};
+
+// Delay between requesting and executing a withdrawal of excess reserve
+pub const RESERVE_WITHDRAWAL_DELAY: i64 = 86_400;
+
+#[derive(Accounts)]
+pub struct VerifySolvency<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        seeds = [b"token_stats", token_data.mint.as_ref()],
+        bump = token_stats.bump,
+    )]
+    pub token_stats: Account<'info, TokenStats>,
+    
+    #[account(
+        seeds = [b"reserve", token_data.mint.as_ref()],
+        bump,
+    )]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    
+    /// CHECK: SecondaryCurve PDA, read only once a second quote asset is configured
+    #[account(seeds = [b"secondary_curve", token_data.mint.as_ref()], bump)]
+    pub secondary_curve: UncheckedAccount<'info>,
+    
+    // Checked against the supply bought with the secondary asset; required while there is any
+    #[account(
+        seeds = [b"secondary_reserve", token_data.mint.as_ref()],
+        bump,
+    )]
+    pub secondary_reserve_vault: Option<Account<'info, TokenAccount>>,
+}
+
+impl<'info> VerifySolvency<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let token_data = &mut self.token_data;
+        require_with_context!(
+            token_data.bonding_curve.quote_mint != Pubkey::default(),
+            TokenFactoryError::ReserveNotInitialized,
+            "instruction=verify_solvency token_id={}",
+            token_data.token_id
+        );
+        
+        let curve_supply = self.token_stats.curve_supply;
+        let secondary_supply = secondary_supply(&self.secondary_curve)?;
+        let now = Clock::get()?.unix_timestamp;
+        
+        // Each reserve is checked against the supply bought with its own asset
+        let mut reserves = vec![(
+            token_data.bonding_curve.quote_mint,
+            required_reserve(token_data, curve_supply, secondary_supply)?,
+            self.reserve_vault.amount,
+        )];
+        if secondary_supply > 0 {
+            let secondary_curve: Account<SecondaryCurve> = Account::try_from(&self.secondary_curve)?;
+            let secondary_reserve_vault = self
+                .secondary_reserve_vault
+                .as_ref()
+                .ok_or(TokenFactoryError::InvalidSecondaryQuote)?;
+            reserves.push((
+                secondary_curve.quote_mint,
+                secondary_curve.required_reserve(token_data, curve_supply)?,
+                secondary_reserve_vault.amount,
+            ));
+        }
+
+        // Succeeds either way so that the pause and the events are kept
+        let insolvent = reserves.iter().any(|&(_, required, balance)| balance < required);
+        let paused = insolvent && token_data.bonding_curve.pause_on_insolvency && token_data.bonding_curve.enabled;
+        if paused {
+            token_data.bonding_curve.enabled = false;
+        }
+        for (quote_mint, required, balance) in reserves {
+            if balance < required {
+                emit!(InsolvencyDetectedEvent {
+                    token_id: token_data.token_id,
+                    mint: token_data.mint,
+                    quote_mint,
+                    curve_supply,
+                    required_reserve: required,
+                    reserve_balance: balance,
+                    shortfall: required - balance,
+                    paused,
+                    timestamp: now,
+                });
+            } else {
+                emit!(SolvencyVerifiedEvent {
+                    token_id: token_data.token_id,
+                    mint: token_data.mint,
+                    quote_mint,
+                    curve_supply,
+                    required_reserve: required,
+                    reserve_balance: balance,
+                    timestamp: now,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct BoostReserve<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_stats", token_data.mint.as_ref()],
+        bump = token_stats.bump,
+    )]
+    pub token_stats: Account<'info, TokenStats>,
+    
+    #[account(
+        mut,
+        seeds = [b"reserve", token_data.mint.as_ref()],
+        bump,
+    )]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = token_data.bonding_curve.quote_mint)]
+    pub depositor_quote_account: Account<'info, TokenAccount>,
+    
+    pub depositor: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> BoostReserve<'info> {
+    pub fn process(&mut self, amount: u64) -> Result<()> {
+        let token_data = &self.token_data;
+        require_with_context!(
+            token_data.bonding_curve.quote_mint != Pubkey::default(),
+            TokenFactoryError::ReserveNotInitialized,
+            "instruction=boost_reserve token_id={}",
+            token_data.token_id
+        );
+        require_with_context!(
+            amount > 0,
+            TokenFactoryError::InvalidAmount,
+            "instruction=boost_reserve token_id={} amount={}",
+            token_data.token_id,
+            amount
+        );
+        
+        // Backing only: nothing is minted, so the reserve per circulating token rises
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.depositor_quote_account.to_account_info(),
+                    to: self.reserve_vault.to_account_info(),
+                    authority: self.depositor.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        let token_stats = &mut self.token_stats;
+        token_stats.total_boosted = token_stats
+            .total_boosted
+            .checked_add(amount)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        
+        self.reserve_vault.reload()?;
+        emit!(ReserveBoostedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            depositor: self.depositor.key(),
+            amount,
+            reserve_balance: self.reserve_vault.amount,
+            total_boosted: token_stats.total_boosted,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct WithdrawExcessReserve<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        seeds = [b"token_stats", token_data.mint.as_ref()],
+        bump = token_stats.bump,
+    )]
+    pub token_stats: Account<'info, TokenStats>,
+    
+    /// CHECK: PDA that owns the reserve and creator fee vaults; it holds no data
+    #[account(
+        seeds = [b"reserve_authority", token_data.mint.as_ref()],
+        bump = token_data.bonding_curve.reserve_authority_bump,
+    )]
+    pub reserve_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"reserve", token_data.mint.as_ref()],
+        bump,
+    )]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = token_data.bonding_curve.quote_mint)]
+    pub destination: Account<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    /// CHECK: SecondaryCurve PDA, read only once a second quote asset is configured
+    #[account(seeds = [b"secondary_curve", token_data.mint.as_ref()], bump)]
+    pub secondary_curve: UncheckedAccount<'info>,
+}
+
+impl<'info> WithdrawExcessReserve<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let token_data = &self.token_data;
+        let now = Clock::get()?.unix_timestamp;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=withdraw_excess_reserve token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            self.authority.key(),
+            token_data.authority
+        );
+        
+        let pending = &token_data.pending_reserve_withdrawal;
+        require_with_context!(
+            pending.amount > 0 && now >= pending.available_at,
+            TokenFactoryError::WithdrawalNotReady,
+            "instruction=withdraw_excess_reserve token_id={} amount={} available_at={} now={}",
+            token_data.token_id,
+            pending.amount,
+            pending.available_at,
+            now
+        );
+        
+        // Only what the curve would not owe holders if they all sold now can leave
+        let amount = pending.amount;
+        let secondary_supply = secondary_supply(&self.secondary_curve)?;
+        let required = required_reserve(token_data, self.token_stats.curve_supply, secondary_supply)?;
+        let excess = self.reserve_vault.amount.saturating_sub(required);
+        require_with_context!(
+            amount <= excess,
+            TokenFactoryError::InsufficientExcessReserve,
+            "instruction=withdraw_excess_reserve token_id={} amount={} reserve_balance={} required_reserve={}",
+            token_data.token_id,
+            amount,
+            self.reserve_vault.amount,
+            required
+        );
+        
+        let mint_key = token_data.mint;
+        let bump = [token_data.bonding_curve.reserve_authority_bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"reserve_authority", mint_key.as_ref(), &bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.reserve_vault.to_account_info(),
+                    to: self.destination.to_account_info(),
+                    authority: self.reserve_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+        
+        // Solvency must still hold after the transfer
+        self.reserve_vault.reload()?;
+        require_with_context!(
+            self.reserve_vault.amount >= required,
+            TokenFactoryError::InsufficientExcessReserve,
+            "instruction=withdraw_excess_reserve token_id={} reserve_balance={} required_reserve={}",
+            self.token_data.token_id,
+            self.reserve_vault.amount,
+            required
+        );
+        
+        self.token_data.pending_reserve_withdrawal = PendingReserveWithdrawal::default();
+        
+        emit!(ExcessReserveWithdrawnEvent {
+            token_id: self.token_data.token_id,
+            mint: mint_key,
+            destination: self.destination.key(),
+            amount,
+            reserve_balance: self.reserve_vault.amount,
+            required_reserve: required,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+// Quote tokens the primary reserve must hold to buy back all supply bought with the primary asset,
+// sold from the current supply down as sell_tokens prices it. Supply bought with a secondary asset
+// is owed by that asset's reserve and left out
+pub fn required_reserve(token_data: &TokenData, curve_supply: u64, secondary_supply: u64) -> Result<u64> {
+    let primary_supply = curve_supply.saturating_sub(secondary_supply);
+    quote_bonding_curve(
+        &token_data.bonding_curve,
+        token_data.decimals,
+        curve_supply - primary_supply,
+        primary_supply,
+    )
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct PendingReserveWithdrawal {
+    pub amount: u64, // 0 when nothing is pending
+    pub available_at: i64,
+}
+
+#[event]
+pub struct InsolvencyDetectedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub quote_mint: Pubkey, // Reserve asset the check covered
+    pub curve_supply: u64,
+    pub required_reserve: u64,
+    pub reserve_balance: u64,
+    pub shortfall: u64,
+    pub paused: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SolvencyVerifiedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub quote_mint: Pubkey, // Reserve asset the check covered
+    pub curve_supply: u64,
+    pub required_reserve: u64,
+    pub reserve_balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReserveBoostedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub reserve_balance: u64,
+    pub total_boosted: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReserveWithdrawalRequestedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub available_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExcessReserveWithdrawnEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub reserve_balance: u64,
+    pub required_reserve: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SolvencyGuardConfiguredEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub pause_on_insolvency: bool,
+    pub timestamp: i64,
+}
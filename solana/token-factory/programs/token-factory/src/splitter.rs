@@ -0,0 +1,270 @@
+// Splitter module for Crossify Token Factory
+// This file contains payout splits that share a fee vault between several recipients
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::{bps_of, TokenData, TokenFactoryError, ACCOUNT_RESERVED_LEN, ACCOUNT_VERSION};
+
+// Recipients one splitter can pay
+pub const MAX_PAYOUT_RECIPIENTS: usize = 8;
+
+#[derive(Accounts)]
+pub struct ConfigurePayoutSplitter<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        seeds = [b"creator_fee_vault", token_data.mint.as_ref()],
+        bump,
+    )]
+    pub creator_fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + PayoutSplitter::LEN,
+        seeds = [b"payout_splitter", creator_fee_vault.key().as_ref()],
+        bump,
+    )]
+    pub payout_splitter: Account<'info, PayoutSplitter>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ConfigurePayoutSplitter<'info> {
+    // Reconfiguring replaces the whole recipient list
+    pub fn process(&mut self, bump: u8, recipients: Vec<PayoutRecipient>) -> Result<()> {
+        let token_data = &self.token_data;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=configure_payout_splitter token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            self.authority.key(),
+            token_data.authority
+        );
+        let total_bps: u32 = recipients.iter().map(|r| r.bps as u32).sum();
+        require_with_context!(
+            !recipients.is_empty()
+                && recipients.len() <= MAX_PAYOUT_RECIPIENTS
+                && total_bps == 10_000
+                && recipients.iter().all(|r| r.bps > 0),
+            TokenFactoryError::InvalidPayoutSplit,
+            "instruction=configure_payout_splitter token_id={} recipients={} total_bps={} max_recipients={}",
+            token_data.token_id,
+            recipients.len(),
+            total_bps,
+            MAX_PAYOUT_RECIPIENTS
+        );
+        
+        let payout_splitter = &mut self.payout_splitter;
+        payout_splitter.vault = self.creator_fee_vault.key();
+        payout_splitter.mint = token_data.mint;
+        payout_splitter.recipients = recipients;
+        payout_splitter.bump = bump;
+        payout_splitter.account_version = ACCOUNT_VERSION;
+        
+        emit!(PayoutSplitterConfiguredEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            vault: payout_splitter.vault,
+            recipients: payout_splitter.recipients.clone(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct RemovePayoutSplitter<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"payout_splitter", payout_splitter.vault.as_ref()],
+        bump = payout_splitter.bump,
+        constraint = payout_splitter.mint == token_data.mint @ TokenFactoryError::InvalidPayoutSplit,
+    )]
+    pub payout_splitter: Account<'info, PayoutSplitter>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> RemovePayoutSplitter<'info> {
+    // Fees go back to being claimed whole through claim_creator_fees
+    pub fn process(&mut self) -> Result<()> {
+        let token_data = &self.token_data;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=remove_payout_splitter token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            self.authority.key(),
+            token_data.authority
+        );
+        
+        emit!(PayoutSplitterConfiguredEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            vault: self.payout_splitter.vault,
+            recipients: Vec::new(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ClaimSplitCreatorFees<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    /// CHECK: PDA that owns the reserve and creator fee vaults; it holds no data
+    #[account(
+        seeds = [b"reserve_authority", token_data.mint.as_ref()],
+        bump = token_data.bonding_curve.reserve_authority_bump,
+    )]
+    pub reserve_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"creator_fee_vault", token_data.mint.as_ref()],
+        bump,
+    )]
+    pub creator_fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [b"payout_splitter", creator_fee_vault.key().as_ref()],
+        bump = payout_splitter.bump,
+    )]
+    pub payout_splitter: Account<'info, PayoutSplitter>,
+    
+    pub payer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClaimSplitCreatorFees<'info> {
+    // Permissionless, since every share goes to a fixed recipient. Remaining accounts: one quote
+    // token account per recipient, in splitter order; the last one also takes the rounding dust
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let token_data = &self.token_data;
+        let recipients = &self.payout_splitter.recipients;
+        require_with_context!(
+            remaining_accounts.len() == recipients.len(),
+            TokenFactoryError::InvalidPayoutSplit,
+            "instruction=claim_split_creator_fees token_id={} accounts={} recipients={}",
+            token_data.token_id,
+            remaining_accounts.len(),
+            recipients.len()
+        );
+        let amount = self.creator_fee_vault.amount;
+        require_with_context!(
+            amount > 0,
+            TokenFactoryError::NothingToClaim,
+            "instruction=claim_split_creator_fees token_id={}",
+            token_data.token_id
+        );
+        
+        let mint_key = token_data.mint;
+        let bump = [token_data.bonding_curve.reserve_authority_bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"reserve_authority", mint_key.as_ref(), &bump]];
+        let mut paid: u64 = 0;
+        for (index, (account, recipient)) in remaining_accounts.iter().zip(recipients.iter()).enumerate() {
+            let destination: Account<TokenAccount> = Account::try_from(account)?;
+            require_with_context!(
+                destination.owner == recipient.wallet && destination.mint == token_data.bonding_curve.quote_mint,
+                TokenFactoryError::InvalidRecipient,
+                "instruction=claim_split_creator_fees token_id={} destination={} owner={} expected_owner={}",
+                token_data.token_id,
+                account.key(),
+                destination.owner,
+                recipient.wallet
+            );
+            let share = if index == recipients.len() - 1 {
+                amount - paid
+            } else {
+                bps_of(amount, recipient.bps).ok_or(TokenFactoryError::MathOverflow)?
+            };
+            if share == 0 {
+                continue;
+            }
+            token::transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    token::Transfer {
+                        from: self.creator_fee_vault.to_account_info(),
+                        to: account.clone(),
+                        authority: self.reserve_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                share,
+            )?;
+            paid += share;
+        }
+
+        emit!(CreatorFeesSplitEvent {
+            token_id: token_data.token_id,
+            mint: mint_key,
+            amount,
+            recipients: recipients.len() as u8,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+/// Shares in which a fee vault is paid out; attaching one disables single-destination claims
+#[account]
+pub struct PayoutSplitter {
+    pub vault: Pubkey,
+    pub mint: Pubkey, // Token whose fees the vault collects
+    pub recipients: Vec<PayoutRecipient>,
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+impl PayoutSplitter {
+    pub const LEN: usize = 32 + 32 + 4 + PayoutRecipient::LEN * MAX_PAYOUT_RECIPIENTS + 1 + 1 + ACCOUNT_RESERVED_LEN;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PayoutRecipient {
+    pub wallet: Pubkey,
+    pub bps: u16, // Share of each claim; all recipients sum to 10_000
+}
+
+impl PayoutRecipient {
+    pub const LEN: usize = 32 + 2;
+}
+
+#[event]
+pub struct PayoutSplitterConfiguredEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub recipients: Vec<PayoutRecipient>, // Empty once the splitter is removed
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreatorFeesSplitEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub recipients: u8,
+    pub timestamp: i64,
+}
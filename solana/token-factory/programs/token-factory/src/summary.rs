@@ -0,0 +1,87 @@
+// Summary module for Crossify Token Factory
+// This file contains the creator dashboard view aggregating stats across a creator's tokens
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::{TokenData, TokenFactoryError, TokenStats};
+
+// Tokens one summary can cover; larger portfolios are summed client-side over several calls
+pub const MAX_SUMMARY_TOKENS: usize = 20;
+
+// Remaining accounts per token: token_data, token_stats, creator_fee_vault
+const SUMMARY_ACCOUNTS_PER_TOKEN: usize = 3;
+
+#[derive(Accounts)]
+pub struct GetCreatorSummary {}
+
+impl GetCreatorSummary {
+    // Remaining accounts: one triple per token of `creator`; a creator fee vault that does not
+    // exist yet counts as nothing pending
+    pub fn process(&self, creator: Pubkey, remaining_accounts: &[AccountInfo]) -> Result<CreatorSummary> {
+        let token_count = remaining_accounts.len() / SUMMARY_ACCOUNTS_PER_TOKEN;
+        require_with_context!(
+            token_count > 0
+                && token_count <= MAX_SUMMARY_TOKENS
+                && remaining_accounts.len() % SUMMARY_ACCOUNTS_PER_TOKEN == 0,
+            TokenFactoryError::InvalidBatch,
+            "instruction=get_creator_summary creator={} accounts={} max_tokens={}",
+            creator,
+            remaining_accounts.len(),
+            MAX_SUMMARY_TOKENS
+        );
+        
+        let mut summary = CreatorSummary {
+            creator,
+            tokens_created: 0,
+            tokens_graduated: 0,
+            total_volume: 0,
+            total_fees_earned: 0,
+            pending_fees: 0,
+        };
+        let mut mints: Vec<Pubkey> = Vec::with_capacity(token_count);
+        for accounts in remaining_accounts.chunks(SUMMARY_ACCOUNTS_PER_TOKEN) {
+            let token_data: Account<TokenData> = Account::try_from(&accounts[0])?;
+            let token_stats: Account<TokenStats> = Account::try_from(&accounts[1])?;
+            let (creator_fee_vault, _) =
+                Pubkey::find_program_address(&[b"creator_fee_vault", token_data.mint.as_ref()], &crate::ID);
+            require_with_context!(
+                token_data.authority == creator
+                    && token_stats.mint == token_data.mint
+                    && accounts[2].key() == creator_fee_vault
+                    && !mints.contains(&token_data.mint),
+                TokenFactoryError::InvalidBatch,
+                "instruction=get_creator_summary creator={} token_id={} authority={}",
+                creator,
+                token_data.token_id,
+                token_data.authority
+            );
+            mints.push(token_data.mint);
+            
+            let pending = if accounts[2].data_is_empty() {
+                0
+            } else {
+                Account::<TokenAccount>::try_from(&accounts[2])?.amount
+            };
+            summary.tokens_created += 1;
+            if token_data.bonding_curve.threshold_reached {
+                summary.tokens_graduated += 1;
+            }
+            summary.total_volume = summary.total_volume.saturating_add(token_stats.total_volume);
+            summary.total_fees_earned = summary.total_fees_earned.saturating_add(token_stats.total_creator_fees);
+            summary.pending_fees = summary.pending_fees.saturating_add(pending);
+        }
+
+        Ok(summary)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreatorSummary {
+    pub creator: Pubkey,
+    pub tokens_created: u32,
+    pub tokens_graduated: u32,
+    pub total_volume: u64,      // Quote tokens traded through the curves
+    pub total_fees_earned: u64, // Sell tax paid into the creator fee vaults, claimed or not
+    pub pending_fees: u64,      // Still in the creator fee vaults
+}
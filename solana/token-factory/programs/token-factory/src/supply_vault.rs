@@ -0,0 +1,144 @@
+// Supply vault module for Crossify Token Factory
+// This file contains the creator-funded token vault that backs curve buys of imported mints whose
+// mint authority was revoked, so fixed-supply tokens can still trade on the curve
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::{TokenData, TokenFactoryError};
+
+#[derive(Accounts)]
+pub struct FundSupplyVault<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    /// CHECK: Program PDA that signs for the supply vault; it holds no data
+    #[account(seeds = [b"mint_authority", mint.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"supply_vault", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = mint_authority,
+    )]
+    pub supply_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = mint, token::authority = authority)]
+    pub creator_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> FundSupplyVault<'info> {
+    // Deposits can be topped up at any time; tokens in the vault are only released by curve buys
+    pub fn process(&mut self, mint_authority_bump: u8, amount: u64) -> Result<()> {
+        let token_data = &mut self.token_data;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=fund_supply_vault token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            self.authority.key(),
+            token_data.authority
+        );
+        require_with_context!(
+            token_data.supply_from_vault,
+            TokenFactoryError::SupplyVaultNotUsed,
+            "instruction=fund_supply_vault token_id={}",
+            token_data.token_id
+        );
+        require_with_context!(
+            amount > 0,
+            TokenFactoryError::InvalidAmount,
+            "instruction=fund_supply_vault token_id={} amount={}",
+            token_data.token_id,
+            amount
+        );
+        
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.creator_token_account.to_account_info(),
+                    to: self.supply_vault.to_account_info(),
+                    authority: self.authority.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        // The mint authority was revoked, but the PDA still signs for the vault
+        token_data.mint_authority_bump = mint_authority_bump;
+        
+        self.supply_vault.reload()?;
+        emit!(SupplyVaultFundedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            amount,
+            vault_balance: self.supply_vault.amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+// Moves `amount` bought tokens out of the supply vault, signed by the mint authority PDA
+pub fn release_from_supply_vault<'info>(
+    token_data: &TokenData,
+    token_program: &Program<'info, Token>,
+    supply_vault: Option<&Account<'info, TokenAccount>>,
+    mint_authority: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    amount: u64,
+    instruction: &str,
+) -> Result<()> {
+    let supply_vault = supply_vault.ok_or(TokenFactoryError::SupplyVaultExhausted)?;
+    require_with_context!(
+        supply_vault.amount >= amount,
+        TokenFactoryError::SupplyVaultExhausted,
+        "instruction={} token_id={} amount={} vault_balance={}",
+        instruction,
+        token_data.token_id,
+        amount,
+        supply_vault.amount
+    );
+    
+    let mint_key = token_data.mint;
+    let bump = [token_data.mint_authority_bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[b"mint_authority", mint_key.as_ref(), &bump]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            token::Transfer {
+                from: supply_vault.to_account_info(),
+                to,
+                authority: mint_authority,
+            },
+            signer_seeds,
+        ),
+        amount,
+    )
+}
+
+#[event]
+pub struct SupplyVaultFundedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub vault_balance: u64,
+    pub timestamp: i64,
+}
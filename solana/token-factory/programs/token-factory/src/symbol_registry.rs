@@ -0,0 +1,162 @@
+// Symbol registry module for Crossify Token Factory
+// This file contains per-factory symbol reservations that create_token can require
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::{TokenFactory, TokenFactoryError, ACCOUNT_RESERVED_LEN, ACCOUNT_VERSION};
+
+// Registered symbols are 1-10 uppercase ASCII letters or digits, which also keeps them a valid seed
+pub const MAX_REGISTERED_SYMBOL_LEN: usize = 10;
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct ClaimSymbol<'info> {
+    #[account(seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    // Fails if the symbol is already claimed
+    #[account(
+        init,
+        payer = claimant,
+        space = 8 + SymbolRegistry::LEN,
+        seeds = [b"symbol", token_factory.key().as_ref(), symbol.as_bytes()],
+        bump,
+    )]
+    pub symbol_registry: Account<'info, SymbolRegistry>,
+    
+    /// CHECK: Receives the claim fee in lamports; checked against the factory treasury
+    #[account(mut, address = token_factory.treasury @ TokenFactoryError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimSymbol<'info> {
+    pub fn process(&mut self, bump: u8, symbol: String) -> Result<()> {
+        require_with_context!(
+            is_registrable_symbol(&symbol),
+            TokenFactoryError::InvalidSymbol,
+            "instruction=claim_symbol symbol={} max_len={}",
+            symbol,
+            MAX_REGISTERED_SYMBOL_LEN
+        );
+        
+        let fee = self.token_factory.symbol_claim_fee_lamports;
+        if fee > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: self.claimant.to_account_info(),
+                        to: self.treasury.to_account_info(),
+                    },
+                ),
+                fee,
+            )?;
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let symbol_registry = &mut self.symbol_registry;
+        symbol_registry.factory = self.token_factory.key();
+        symbol_registry.symbol = symbol.clone();
+        symbol_registry.owner = self.claimant.key();
+        symbol_registry.mint = Pubkey::default();
+        symbol_registry.claimed_at = now;
+        symbol_registry.bump = bump;
+        symbol_registry.account_version = ACCOUNT_VERSION;
+        
+        emit!(SymbolClaimedEvent {
+            symbol,
+            owner: self.claimant.key(),
+            fee,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+pub fn is_registrable_symbol(symbol: &str) -> bool {
+    !symbol.is_empty()
+        && symbol.len() <= MAX_REGISTERED_SYMBOL_LEN
+        && symbol.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+}
+
+// Binds the creator's claimed symbol to a new mint; required when the factory enforces unique symbols
+pub fn bind_symbol_registry(
+    factory: &Pubkey,
+    symbol_registry: Option<&mut Account<SymbolRegistry>>,
+    require_unique_symbols: bool,
+    symbol: &str,
+    creator: &Pubkey,
+    mint: &Pubkey,
+) -> Result<()> {
+    let symbol_registry = match symbol_registry {
+        Some(symbol_registry) => symbol_registry,
+        None => {
+            require_with_context!(
+                !require_unique_symbols,
+                TokenFactoryError::SymbolNotClaimed,
+                "instruction=create_token symbol={} symbol registry account missing",
+                symbol
+            );
+            return Ok(());
+        }
+    };
+    
+    // Lowercase symbols share the uppercase reservation
+    let canonical = symbol.to_ascii_uppercase();
+    let (expected, _) =
+        Pubkey::find_program_address(&[b"symbol", factory.as_ref(), canonical.as_bytes()], &crate::ID);
+    require_with_context!(
+        symbol_registry.key() == expected
+            && symbol_registry.owner == *creator
+            && symbol_registry.mint == Pubkey::default(),
+        TokenFactoryError::SymbolNotClaimed,
+        "instruction=create_token symbol={} symbol_registry={} owner={} mint={}",
+        symbol,
+        symbol_registry.key(),
+        symbol_registry.owner,
+        symbol_registry.mint
+    );
+    
+    symbol_registry.mint = *mint;
+    
+    Ok(())
+}
+
+/// A symbol reserved in a factory; `mint` is set once a token is created under it
+#[account]
+pub struct SymbolRegistry {
+    pub factory: Pubkey,
+    pub symbol: String,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub claimed_at: i64,
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+impl SymbolRegistry {
+    pub const LEN: usize = 32 + 4 + MAX_REGISTERED_SYMBOL_LEN + 32 + 32 + 8 + 1 + 1 + ACCOUNT_RESERVED_LEN;
+}
+
+#[event]
+pub struct SymbolClaimedEvent {
+    pub symbol: String,
+    pub owner: Pubkey,
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SymbolRegistryConfiguredEvent {
+    pub require_unique_symbols: bool,
+    pub symbol_claim_fee_lamports: u64,
+    pub timestamp: i64,
+}
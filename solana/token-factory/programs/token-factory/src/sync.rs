@@ -0,0 +1,298 @@
+// Sync module for Crossify Token Factory
+// This file contains publishing of curve price and reserve liquidity state to remote chains
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use anchor_lang::system_program;
+use anchor_spl::token::TokenAccount;
+
+use crate::wormhole::{
+    serialize_batch_sync_message, serialize_liquidity_update_message, serialize_price_update_message,
+    LiquidityUpdatePayload, PriceUpdatePayload,
+};
+use crate::{
+    collect_bridge_fee, decimals_unit, quote_bonding_curve, require_feature_enabled, TokenData, TokenFactory,
+    TokenFactoryError, TokenStats, FEATURE_BRIDGING,
+};
+
+// Tokens per batch; each one contributes a price and a liquidity update
+pub const MAX_BATCH_SYNC_TOKENS: usize = 10;
+
+// Remaining accounts per token: token_data, token_stats (writable), reserve_vault
+const SYNC_ACCOUNTS_PER_TOKEN: usize = 3;
+
+// Seconds during which republishing an unchanged price, supply and reserve is rejected,
+// so a retried transaction cannot send remote chains the same update twice
+pub const SYNC_DEDUP_WINDOW: i64 = 60;
+
+#[derive(Accounts)]
+pub struct PublishBatchSync<'info> {
+    #[account(seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    /// CHECK: Receives the protocol fee in lamports; checked against the factory treasury
+    #[account(mut, address = token_factory.treasury @ TokenFactoryError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PublishBatchSync<'info> {
+    pub fn process(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        require_feature_enabled(&self.token_factory, FEATURE_BRIDGING, "publish_batch_sync")?;
+        let token_count = remaining_accounts.len() / SYNC_ACCOUNTS_PER_TOKEN;
+        require_with_context!(
+            token_count > 0
+                && token_count <= MAX_BATCH_SYNC_TOKENS
+                && remaining_accounts.len() % SYNC_ACCOUNTS_PER_TOKEN == 0,
+            TokenFactoryError::InvalidBatch,
+            "instruction=publish_batch_sync accounts={} max_tokens={}",
+            remaining_accounts.len(),
+            MAX_BATCH_SYNC_TOKENS
+        );
+        
+        // One message and one protocol fee for the whole batch
+        collect_bridge_fee(
+            &self.token_factory,
+            &self.payer,
+            &self.treasury.to_account_info(),
+            &self.system_program,
+        )?;
+        
+        let now = Clock::get()?.unix_timestamp;
+        publish_sync_batch(&self.token_factory.key(), remaining_accounts, now, 0)?;
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CrankSync<'info> {
+    #[account(seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    /// CHECK: Lamport-only PDA funding keeper rewards; anyone can top it up with a plain transfer
+    #[account(
+        mut,
+        seeds = [b"keeper_vault", token_factory.key().as_ref()],
+        bump,
+    )]
+    pub keeper_vault: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CrankSync<'info> {
+    pub fn process(&mut self, keeper_vault_bump: u8, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let token_factory = &self.token_factory;
+        require_feature_enabled(token_factory, FEATURE_BRIDGING, "crank_sync")?;
+        let token_count = remaining_accounts.len() / SYNC_ACCOUNTS_PER_TOKEN;
+        require_with_context!(
+            token_factory.sync_interval > 0,
+            TokenFactoryError::SyncNotConfigured,
+            "instruction=crank_sync sync_interval={}",
+            token_factory.sync_interval
+        );
+        require_with_context!(
+            token_count > 0
+                && token_count <= MAX_BATCH_SYNC_TOKENS
+                && remaining_accounts.len() % SYNC_ACCOUNTS_PER_TOKEN == 0,
+            TokenFactoryError::InvalidBatch,
+            "instruction=crank_sync accounts={} max_tokens={}",
+            remaining_accounts.len(),
+            MAX_BATCH_SYNC_TOKENS
+        );
+        
+        // Only stale tokens can be cranked, so rewards track useful work
+        let now = Clock::get()?.unix_timestamp;
+        publish_sync_batch(&token_factory.key(), remaining_accounts, now, token_factory.sync_interval)?;
+        
+        // Pay what the vault can afford while keeping it rent-exempt
+        let owed = token_factory
+            .keeper_reward_lamports
+            .saturating_mul(token_count as u64);
+        let available = self
+            .keeper_vault
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0));
+        let reward = owed.min(available);
+        if reward > 0 {
+            let factory_key = token_factory.key();
+            let bump = [keeper_vault_bump];
+            let signer_seeds: &[&[&[u8]]] = &[&[b"keeper_vault", factory_key.as_ref(), &bump]];
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: self.keeper_vault.to_account_info(),
+                        to: self.cranker.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                reward,
+            )?;
+        }
+
+        emit!(SyncCrankedEvent {
+            cranker: self.cranker.key(),
+            tokens_synced: token_count as u8,
+            reward,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+// Publishes one batch message for the given token triples; each must be at least
+// `min_interval` seconds past its last sync
+fn publish_sync_batch<'info>(
+    factory: &Pubkey,
+    remaining_accounts: &[AccountInfo<'info>],
+    now: i64,
+    min_interval: i64,
+) -> Result<()> {
+    let token_count = remaining_accounts.len() / SYNC_ACCOUNTS_PER_TOKEN;
+    let mut token_ids = Vec::with_capacity(token_count);
+    let mut messages = Vec::with_capacity(token_count * 2);
+    for accounts in remaining_accounts.chunks(SYNC_ACCOUNTS_PER_TOKEN) {
+        let (token_id, price_update, liquidity_update) = sync_token_state(factory, accounts, now, min_interval)?;
+        token_ids.push(token_id);
+        messages.push(price_update);
+        messages.push(liquidity_update);
+    }
+
+    // In a real implementation, this would call the Wormhole bridge to send the message
+    // For now, we just emit an event
+    emit!(BatchSyncPublishedEvent {
+        token_ids,
+        payload: serialize_batch_sync_message(&messages),
+        timestamp: now,
+    });
+    
+    Ok(())
+}
+
+// Builds price and liquidity updates for one token and records the sync in its TokenStats
+pub fn sync_token_state<'info>(
+    factory: &Pubkey,
+    accounts: &[AccountInfo<'info>],
+    now: i64,
+    min_interval: i64,
+) -> Result<(u64, Vec<u8>, Vec<u8>)> {
+    let token_data: Account<TokenData> = Account::try_from(&accounts[0])?;
+    let mut token_stats: Account<TokenStats> = Account::try_from(&accounts[1])?;
+    let reserve_vault: Account<TokenAccount> = Account::try_from(&accounts[2])?;
+    
+    let mint = token_data.mint;
+    let (expected_stats, _) = Pubkey::find_program_address(&[b"token_stats", mint.as_ref()], &crate::ID);
+    let (expected_reserve, _) = Pubkey::find_program_address(&[b"reserve", mint.as_ref()], &crate::ID);
+    require_with_context!(
+        token_data.factory == *factory
+            && token_stats.key() == expected_stats
+            && reserve_vault.key() == expected_reserve,
+        TokenFactoryError::InvalidBatch,
+        "instruction=sync token_id={} token_stats={} reserve_vault={}",
+        token_data.token_id,
+        token_stats.key(),
+        reserve_vault.key()
+    );
+    require_with_context!(
+        token_data.cross_chain_enabled,
+        TokenFactoryError::CrossChainNotEnabled,
+        "instruction=sync token_id={}",
+        token_data.token_id
+    );
+    require_with_context!(
+        now >= token_stats.last_sync_ts.saturating_add(min_interval),
+        TokenFactoryError::SyncNotDue,
+        "instruction=sync token_id={} now={} last_sync_ts={} min_interval={}",
+        token_data.token_id,
+        now,
+        token_stats.last_sync_ts,
+        min_interval
+    );
+    
+    // Spot price of one whole token at the current curve supply
+    let one_token = u64::try_from(decimals_unit(token_data.decimals)).unwrap_or(u64::MAX);
+    let current_price = quote_bonding_curve(
+        &token_data.bonding_curve,
+        token_data.decimals,
+        token_stats.curve_supply,
+        one_token,
+    )?;
+    let current_liquidity = reserve_vault.amount;
+    let last_liquidity = token_stats.last_synced_reserve;
+    
+    // The timestamp is left out so a resubmission in a later second still matches
+    let sync_hash = keccak::hashv(&[
+        &token_data.token_id.to_le_bytes(),
+        &current_price.to_le_bytes(),
+        &token_stats.curve_supply.to_le_bytes(),
+        &current_liquidity.to_le_bytes(),
+    ])
+    .0;
+    require_with_context!(
+        sync_hash != token_stats.last_sync_hash
+            || now >= token_stats.last_sync_ts.saturating_add(SYNC_DEDUP_WINDOW),
+        TokenFactoryError::DuplicateSync,
+        "instruction=sync token_id={} now={} last_sync_ts={} window={}",
+        token_data.token_id,
+        now,
+        token_stats.last_sync_ts,
+        SYNC_DEDUP_WINDOW
+    );
+    
+    token_stats.price_update_nonce = token_stats.price_update_nonce.saturating_add(1);
+    let price_update = serialize_price_update_message(&PriceUpdatePayload {
+        factory: token_data.factory,
+        token_id: token_data.token_id,
+        nonce: token_stats.price_update_nonce,
+        current_price,
+        current_supply: token_stats.curve_supply,
+        timestamp: now,
+    });
+    let liquidity_update = serialize_liquidity_update_message(&LiquidityUpdatePayload {
+        factory: token_data.factory,
+        token_id: token_data.token_id,
+        liquidity_added: current_liquidity.saturating_sub(last_liquidity),
+        liquidity_removed: last_liquidity.saturating_sub(current_liquidity),
+        current_liquidity,
+        timestamp: now,
+    });
+    
+    token_stats.last_synced_reserve = current_liquidity;
+    token_stats.last_sync_ts = now;
+    token_stats.last_sync_hash = sync_hash;
+    token_stats.exit(&crate::ID)?;
+    
+    Ok((token_data.token_id, price_update, liquidity_update))
+}
+
+#[event]
+pub struct BatchSyncPublishedEvent {
+    pub token_ids: Vec<u64>,
+    pub payload: Vec<u8>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SyncCrankedEvent {
+    pub cranker: Pubkey,
+    pub tokens_synced: u8,
+    pub reward: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SyncScheduleConfiguredEvent {
+    pub sync_interval: i64,
+    pub keeper_reward_lamports: u64,
+    pub timestamp: i64,
+}
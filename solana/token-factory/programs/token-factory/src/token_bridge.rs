@@ -0,0 +1,223 @@
+// Token Bridge module for Crossify Token Factory
+// This file contains attestation through the canonical Wormhole Token Bridge and the
+// wrapped-asset addresses it produces on other chains
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use anchor_spl::token::Mint;
+use std::mem::size_of;
+
+use crate::wormhole::UniversalAddress;
+use crate::{
+    wormhole_core_bridge, wormhole_token_bridge, TokenData, TokenFactoryError, ACCOUNT_RESERVED_LEN, ACCOUNT_VERSION,
+    MAX_CHAIN_ID,
+};
+
+// Token Bridge instruction index of AttestToken
+const TOKEN_BRIDGE_ATTEST_TOKEN: u8 = 1;
+
+#[derive(Accounts)]
+pub struct AttestToken<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    // The Token Bridge validates its own and the Core Bridge's accounts below
+    
+    /// CHECK: Token Bridge config PDA
+    pub token_bridge_config: UncheckedAccount<'info>,
+    
+    /// CHECK: Token Bridge wrapped-asset meta PDA for this mint
+    pub wrapped_meta: UncheckedAccount<'info>,
+    
+    /// CHECK: Metaplex metadata account of the mint
+    pub spl_metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Core Bridge config PDA
+    #[account(mut)]
+    pub core_bridge_config: UncheckedAccount<'info>,
+    
+    /// CHECK: Fresh keypair for the attestation message
+    #[account(mut)]
+    pub core_message: Signer<'info>,
+    
+    /// CHECK: Token Bridge emitter PDA
+    pub core_emitter: UncheckedAccount<'info>,
+    
+    /// CHECK: Core Bridge sequence PDA of the Token Bridge emitter
+    #[account(mut)]
+    pub core_emitter_sequence: UncheckedAccount<'info>,
+    
+    /// CHECK: Core Bridge fee collector; the message fee must already be paid into it
+    #[account(mut)]
+    pub core_fee_collector: UncheckedAccount<'info>,
+    
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    
+    /// CHECK: Wormhole Core Bridge program
+    #[account(address = wormhole_core_bridge::ID)]
+    pub core_bridge_program: UncheckedAccount<'info>,
+    
+    /// CHECK: Wormhole Token Bridge program
+    #[account(address = wormhole_token_bridge::ID)]
+    pub token_bridge_program: UncheckedAccount<'info>,
+}
+
+impl<'info> AttestToken<'info> {
+    // Attestation is permissionless on the Token Bridge, so anyone may pay for it
+    pub fn process(&mut self, nonce: u32) -> Result<()> {
+        let mut data = Vec::with_capacity(5);
+        data.push(TOKEN_BRIDGE_ATTEST_TOKEN);
+        data.extend_from_slice(&nonce.to_le_bytes());
+        let attest_ix = Instruction {
+            program_id: wormhole_token_bridge::ID,
+            accounts: vec![
+                AccountMeta::new(self.payer.key(), true),
+                AccountMeta::new_readonly(self.token_bridge_config.key(), false),
+                AccountMeta::new_readonly(self.mint.key(), false),
+                AccountMeta::new_readonly(self.wrapped_meta.key(), false),
+                AccountMeta::new_readonly(self.spl_metadata.key(), false),
+                AccountMeta::new(self.core_bridge_config.key(), false),
+                AccountMeta::new(self.core_message.key(), true),
+                AccountMeta::new_readonly(self.core_emitter.key(), false),
+                AccountMeta::new(self.core_emitter_sequence.key(), false),
+                AccountMeta::new(self.core_fee_collector.key(), false),
+                AccountMeta::new_readonly(self.clock.key(), false),
+                AccountMeta::new_readonly(self.rent.key(), false),
+                AccountMeta::new_readonly(self.system_program.key(), false),
+                AccountMeta::new_readonly(self.core_bridge_program.key(), false),
+            ],
+            data,
+        };
+        invoke(
+            &attest_ix,
+            &[
+                self.payer.to_account_info(),
+                self.token_bridge_config.to_account_info(),
+                self.mint.to_account_info(),
+                self.wrapped_meta.to_account_info(),
+                self.spl_metadata.to_account_info(),
+                self.core_bridge_config.to_account_info(),
+                self.core_message.to_account_info(),
+                self.core_emitter.to_account_info(),
+                self.core_emitter_sequence.to_account_info(),
+                self.core_fee_collector.to_account_info(),
+                self.clock.to_account_info(),
+                self.rent.to_account_info(),
+                self.system_program.to_account_info(),
+                self.core_bridge_program.to_account_info(),
+                self.token_bridge_program.to_account_info(),
+            ],
+        )?;
+        
+        emit!(TokenAttestedEvent {
+            token_id: self.token_data.token_id,
+            mint: self.mint.key(),
+            message: self.core_message.key(),
+            nonce,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id: u16)]
+pub struct RecordWrappedAsset<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + size_of::<WrappedAsset>(),
+        seeds = [b"wrapped_asset", token_data.mint.as_ref(), &chain_id.to_le_bytes()],
+        bump,
+    )]
+    pub wrapped_asset: Account<'info, WrappedAsset>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RecordWrappedAsset<'info> {
+    pub fn process(&mut self, bump: u8, chain_id: u16, wrapped_address: UniversalAddress) -> Result<()> {
+        let token_data = &self.token_data;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=record_wrapped_asset token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            self.authority.key(),
+            token_data.authority
+        );
+        require_with_context!(
+            chain_id <= MAX_CHAIN_ID && wrapped_address.is_valid_for_chain(chain_id),
+            TokenFactoryError::InvalidUniversalAddress,
+            "instruction=record_wrapped_asset token_id={} chain_id={} wrapped_address={:?}",
+            token_data.token_id,
+            chain_id,
+            wrapped_address.bytes
+        );
+        
+        // The Token Bridge derives it on the remote chain, so it is recorded as the creator reports it
+        let wrapped_asset = &mut self.wrapped_asset;
+        wrapped_asset.mint = token_data.mint;
+        wrapped_asset.chain_id = chain_id;
+        wrapped_asset.wrapped_address = wrapped_address;
+        wrapped_asset.recorded_at = Clock::get()?.unix_timestamp;
+        wrapped_asset.bump = bump;
+        wrapped_asset.account_version = ACCOUNT_VERSION;
+        
+        emit!(WrappedAssetRecordedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            chain_id,
+            wrapped_address,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+/// Token Bridge wrapped-asset address of a mint on one remote chain
+#[account]
+pub struct WrappedAsset {
+    pub mint: Pubkey,
+    pub chain_id: u16,
+    pub wrapped_address: UniversalAddress,
+    pub recorded_at: i64,
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+#[event]
+pub struct TokenAttestedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub message: Pubkey,
+    pub nonce: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WrappedAssetRecordedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub chain_id: u16,
+    pub wrapped_address: UniversalAddress,
+    pub timestamp: i64,
+}
@@ -0,0 +1,169 @@
+// Trade history module for Crossify Token Factory
+// This file contains the opt-in per-wallet record of recent curve trades, read by the UI for P&L
+// without an indexer
+
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+use crate::{TokenData, TokenFactoryError, ACCOUNT_RESERVED_LEN, ACCOUNT_VERSION};
+
+// Trades a history keeps before the oldest is overwritten
+pub const TRADE_HISTORY_LEN: usize = 32;
+
+// TradeEntry side values
+#[constant]
+pub const TRADE_SIDE_BUY: u8 = 0;
+#[constant]
+pub const TRADE_SIDE_SELL: u8 = 1;
+
+#[derive(Accounts)]
+pub struct OpenTradeHistory<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        init,
+        payer = wallet,
+        space = 8 + size_of::<TradeHistory>(),
+        seeds = [b"trade_history", token_data.mint.as_ref(), wallet.key().as_ref()],
+        bump,
+    )]
+    pub trade_history: AccountLoader<'info, TradeHistory>,
+    
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> OpenTradeHistory<'info> {
+    // Trades are recorded from the next one on; earlier trades are not backfilled
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        let mut trade_history = self.trade_history.load_init()?;
+        trade_history.mint = self.token_data.mint;
+        trade_history.wallet = self.wallet.key();
+        trade_history.bump = bump;
+        trade_history.account_version = ACCOUNT_VERSION;
+        
+        emit!(TradeHistoryOpenedEvent {
+            token_id: self.token_data.token_id,
+            mint: self.token_data.mint,
+            wallet: self.wallet.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CloseTradeHistory<'info> {
+    #[account(
+        mut,
+        close = wallet,
+        has_one = wallet,
+    )]
+    pub trade_history: AccountLoader<'info, TradeHistory>,
+    
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+}
+
+impl<'info> CloseTradeHistory<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let trade_history = self.trade_history.load()?;
+        
+        emit!(TradeHistoryClosedEvent {
+            mint: trade_history.mint,
+            wallet: trade_history.wallet,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+/// Last TRADE_HISTORY_LEN curve trades of one wallet in one token, plus lifetime totals.
+/// Zero-copy so buys and sells can append without deserializing the whole ring.
+#[account(zero_copy)]
+pub struct TradeHistory {
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub total_bought: u64,   // Token base units, since the history was opened
+    pub total_cost: u64,     // Quote paid for them
+    pub total_sold: u64,
+    pub total_proceeds: u64, // Quote received after sell tax
+    pub next_index: u32,     // Slot the next trade is written to
+    pub len: u32,            // Filled slots, up to TRADE_HISTORY_LEN
+    pub bump: u8,
+    pub account_version: u8,
+    pub _padding: [u8; 6],
+    pub entries: [TradeEntry; TRADE_HISTORY_LEN],
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+impl TradeHistory {
+    // Fails when the history belongs to another wallet or token, so it cannot be padded with
+    // someone else's trades
+    pub fn record(
+        &mut self,
+        mint: &Pubkey,
+        wallet: &Pubkey,
+        side: u8,
+        amount: u64,
+        quote_amount: u64,
+        timestamp: i64,
+    ) -> Result<()> {
+        require_with_context!(
+            self.mint == *mint && self.wallet == *wallet,
+            TokenFactoryError::InvalidTradeHistory,
+            "mint={} wallet={} history_mint={} history_wallet={}",
+            mint,
+            wallet,
+            self.mint,
+            self.wallet
+        );
+        
+        if side == TRADE_SIDE_BUY {
+            self.total_bought = self.total_bought.saturating_add(amount);
+            self.total_cost = self.total_cost.saturating_add(quote_amount);
+        } else {
+            self.total_sold = self.total_sold.saturating_add(amount);
+            self.total_proceeds = self.total_proceeds.saturating_add(quote_amount);
+        }
+        self.entries[self.next_index as usize] = TradeEntry {
+            timestamp,
+            amount,
+            quote_amount,
+            side,
+            _padding: [0; 7],
+        };
+        self.next_index = (self.next_index + 1) % TRADE_HISTORY_LEN as u32;
+        self.len = (self.len + 1).min(TRADE_HISTORY_LEN as u32);
+        
+        Ok(())
+    }
+}
+
+#[zero_copy]
+pub struct TradeEntry {
+    pub timestamp: i64,
+    pub amount: u64,       // Token base units
+    pub quote_amount: u64, // Cost of a buy, or proceeds of a sell after tax
+    pub side: u8,          // TRADE_SIDE_*
+    pub _padding: [u8; 7],
+}
+
+#[event]
+pub struct TradeHistoryOpenedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TradeHistoryClosedEvent {
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,1181 @@
+// Trading module for Crossify Token Factory
+// This file contains buys and sells against the bonding curve, backed by a quote-token reserve
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::sysvar::instructions as ix_sysvar;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use std::mem::size_of;
+
+use crate::{
+    bps_of, emit_digest, pro_rata_share, quote_bonding_curve, record_referral, release_from_supply_vault,
+    require_allowed_quote_mint, require_authority_or_delegate, require_no_round_trip, require_feature_enabled,
+    require_no_trade_bridge_mix, secondary_supply, trader_burn, trader_transfer, Delegation, ReferralStats, TokenData,
+    TokenFactory, TokenFactoryError, TokenStats, TradeHistory, TradingSession, ACCOUNT_RESERVED_LEN, ACCOUNT_VERSION,
+    CAPABILITY_CLAIM_FEES, DIGEST_ACTION_BOUGHT, DIGEST_ACTION_FEES_CLAIMED, DIGEST_ACTION_GRADUATED,
+    DIGEST_ACTION_REFUNDED, DIGEST_ACTION_SOLD, FEATURE_TRADING, TRADE_SIDE_BUY, TRADE_SIDE_SELL,
+};
+
+#[derive(Accounts)]
+pub struct InitializeReserve<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    pub quote_mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA mint authority, only its address is compared
+    #[account(seeds = [b"mint_authority", mint.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: PDA that owns the reserve and creator fee vaults; it holds no data
+    #[account(seeds = [b"reserve_authority", mint.key().as_ref()], bump)]
+    pub reserve_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"reserve", mint.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = reserve_authority,
+    )]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"creator_fee_vault", mint.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = reserve_authority,
+    )]
+    pub creator_fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    
+    /// CHECK: Factory allowlist entry for the quote mint, checked in process
+    #[account(seeds = [b"quote_mint", token_data.factory.as_ref(), quote_mint.key().as_ref()], bump)]
+    pub allowed_quote_mint: UncheckedAccount<'info>,
+    
+    /// CHECK: TokenStats PDA of the quote mint, which must not exist
+    #[account(seeds = [b"token_stats", quote_mint.key().as_ref()], bump)]
+    pub quote_token_stats: UncheckedAccount<'info>,
+}
+
+impl<'info> InitializeReserve<'info> {
+    pub fn process(&mut self, reserve_authority_bump: u8) -> Result<()> {
+        let token_data = &mut self.token_data;
+        let mint_authority = self.mint_authority.key();
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=initialize_reserve token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            self.authority.key(),
+            token_data.authority
+        );
+        require_with_context!(
+            token_data.bonding_curve.configured,
+            TokenFactoryError::CurveNotConfigured,
+            "instruction=initialize_reserve token_id={}",
+            token_data.token_id
+        );
+        
+        // Buys mint from the curve, so the program must hold the mint authority, unless they are
+        // served from the supply vault
+        require_with_context!(
+            token_data.supply_from_vault || self.mint.mint_authority == COption::Some(mint_authority),
+            TokenFactoryError::MintAuthorityNotDelegated,
+            "instruction=initialize_reserve token_id={} expected_mint_authority={}",
+            token_data.token_id,
+            mint_authority
+        );
+        
+        require_allowed_quote_mint(
+            &self.quote_mint,
+            &self.allowed_quote_mint,
+            &self.quote_token_stats,
+            "initialize_reserve",
+        )?;
+        
+        token_data.bonding_curve.quote_mint = self.quote_mint.key();
+        token_data.bonding_curve.reserve_authority_bump = reserve_authority_bump;
+        
+        emit!(ReserveInitializedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            quote_mint: self.quote_mint.key(),
+            reserve_vault: self.reserve_vault.key(),
+            creator_fee_vault: self.creator_fee_vault.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct BuyTokens<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_stats", token_data.mint.as_ref()],
+        bump = token_stats.bump,
+    )]
+    pub token_stats: Account<'info, TokenStats>,
+    
+    #[account(mut, address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA mint authority, signs the mint CPI
+    #[account(
+        seeds = [b"mint_authority", mint.key().as_ref()],
+        bump = token_data.mint_authority_bump,
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"reserve", mint.key().as_ref()],
+        bump,
+    )]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    
+    // Owned by the buyer, or by the session owner when trading through a session
+    #[account(mut, token::mint = token_data.bonding_curve.quote_mint)]
+    pub buyer_quote_account: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = mint)]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + size_of::<BuyerRecord>(),
+        seeds = [b"buyer_record", mint.key().as_ref(), buyer_quote_account.owner.as_ref()],
+        bump,
+    )]
+    pub buyer_record: Account<'info, BuyerRecord>,
+    
+    // The trading wallet, or a session key of it
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar, used to reject trades bundled with a bridge_out of this mint
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    
+    #[account(mut)]
+    pub session: Option<Account<'info, TradingSession>>,
+    
+    // The trader's opt-in trade history for this mint, if they opened one
+    #[account(mut)]
+    pub trade_history: Option<AccountLoader<'info, TradeHistory>>,
+    
+    // Read for the factory's feature switches
+    #[account(address = token_data.factory, seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    // Required for tokens whose curve supply comes from the supply vault
+    #[account(
+        mut,
+        seeds = [b"supply_vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub supply_vault: Option<Account<'info, TokenAccount>>,
+    
+    // Referral tally, for this trade's quote mint, of the frontend that routed the trade, if any
+    #[account(mut)]
+    pub referral_stats: Option<Account<'info, ReferralStats>>,
+}
+
+impl<'info> BuyTokens<'info> {
+    pub fn process(&mut self, amount: u64, max_cost: u64) -> Result<()> {
+        require_feature_enabled(&self.token_factory, FEATURE_TRADING, "buy_tokens")?;
+        let token_data = &self.token_data;
+        let curve_supply = self.token_stats.curve_supply;
+        
+        require_trading_enabled(token_data, "buy_tokens", amount)?;
+        require_no_trade_bridge_mix(&self.instructions, &token_data.mint, false, "buy_tokens")?;
+        require_no_round_trip(&self.instructions, token_data, true, "buy_tokens")?;
+        
+        // Tokens can be announced ahead of launch; buys open at the scheduled time
+        let now = Clock::get()?.unix_timestamp;
+        require_with_context!(
+            now >= token_data.bonding_curve.trading_start_ts,
+            TokenFactoryError::TradingNotStarted,
+            "instruction=buy_tokens token_id={} now={} trading_start_ts={}",
+            token_data.token_id,
+            now,
+            token_data.bonding_curve.trading_start_ts
+        );
+        
+        let cost = quote_bonding_curve(&token_data.bonding_curve, token_data.decimals, curve_supply, amount)?;
+        require_with_context!(
+            cost > 0,
+            TokenFactoryError::InvalidAmount,
+            "instruction=buy_tokens token_id={} amount={} rounds to zero cost",
+            token_data.token_id,
+            amount
+        );
+        require_with_context!(
+            cost <= max_cost,
+            TokenFactoryError::SlippageExceeded,
+            "instruction=buy_tokens token_id={} amount={} cost={} max_cost={}",
+            token_data.token_id,
+            amount,
+            cost,
+            max_cost
+        );
+        
+        // A session key trades for the session owner, within the session's limits
+        let trader = match self.session.as_mut() {
+            Some(session) => {
+                session.charge(&self.buyer.key(), &token_data.mint, cost, 0, "buy_tokens")?;
+                session.owner
+            }
+            None => self.buyer.key(),
+        };
+        require_with_context!(
+            self.buyer_quote_account.owner == trader && self.buyer_token_account.owner == trader,
+            TokenFactoryError::InvalidAuthority,
+            "instruction=buy_tokens token_id={} trader={} quote_owner={} token_owner={}",
+            token_data.token_id,
+            trader,
+            self.buyer_quote_account.owner,
+            self.buyer_token_account.owner
+        );
+        
+        // Buyer pays the reserve
+        trader_transfer(
+            &self.token_program,
+            self.buyer_quote_account.to_account_info(),
+            self.reserve_vault.to_account_info(),
+            self.buyer.to_account_info(),
+            self.session.as_ref(),
+            cost,
+        )?;
+        
+        // Curve mints the purchased tokens, or hands them out of the supply vault
+        let mint_key = token_data.mint;
+        if token_data.supply_from_vault {
+            release_from_supply_vault(
+                token_data,
+                &self.token_program,
+                self.supply_vault.as_ref(),
+                self.mint_authority.to_account_info(),
+                self.buyer_token_account.to_account_info(),
+                amount,
+                "buy_tokens",
+            )?;
+        } else {
+            let bump = [token_data.mint_authority_bump];
+            let signer_seeds: &[&[&[u8]]] = &[&[b"mint_authority", mint_key.as_ref(), &bump]];
+            token::mint_to(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    token::MintTo {
+                        mint: self.mint.to_account_info(),
+                        to: self.buyer_token_account.to_account_info(),
+                        authority: self.mint_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+            )?;
+        }
+        
+        let token_stats = &mut self.token_stats;
+        token_stats.curve_supply = curve_supply
+            .checked_add(amount)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        token_stats.total_volume = token_stats
+            .total_volume
+            .checked_add(cost)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        
+        let buyer_record = &mut self.buyer_record;
+        if buyer_record.wallet == Pubkey::default() {
+            buyer_record.wallet = trader;
+            buyer_record.mint = mint_key;
+            buyer_record.account_version = ACCOUNT_VERSION;
+        }
+        if buyer_record.net_bought == 0 {
+            token_stats.curve_holders = token_stats.curve_holders.saturating_add(1);
+        }
+        buyer_record.net_bought = buyer_record
+            .net_bought
+            .checked_add(amount)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        
+        // Reaching the graduation threshold permanently rules out refunds
+        self.reserve_vault.reload()?;
+        record_buy_milestones(&mut self.token_data, self.reserve_vault.amount, trader, now);
+        
+        if let Some(trade_history) = self.trade_history.as_ref() {
+            trade_history
+                .load_mut()?
+                .record(&mint_key, &trader, TRADE_SIDE_BUY, amount, cost, now)?;
+        }
+
+        record_referral(
+            self.referral_stats.as_mut(),
+            &self.token_data,
+            self.token_data.bonding_curve.quote_mint,
+            cost,
+            0,
+            now,
+            "buy_tokens",
+        )?;
+        
+        emit!(TokensBoughtEvent {
+            token_id: self.token_data.token_id,
+            mint: mint_key,
+            buyer: trader,
+            amount,
+            cost,
+            curve_supply: token_stats.curve_supply,
+            timestamp: now,
+        });
+        emit_digest(DIGEST_ACTION_BOUGHT, mint_key, trader, amount, now);
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SellTokens<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_stats", token_data.mint.as_ref()],
+        bump = token_stats.bump,
+    )]
+    pub token_stats: Account<'info, TokenStats>,
+    
+    #[account(mut, address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA that owns the reserve and creator fee vaults; it holds no data
+    #[account(
+        seeds = [b"reserve_authority", mint.key().as_ref()],
+        bump = token_data.bonding_curve.reserve_authority_bump,
+    )]
+    pub reserve_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"reserve", mint.key().as_ref()],
+        bump,
+    )]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"creator_fee_vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub creator_fee_vault: Account<'info, TokenAccount>,
+    
+    // Owned by the seller, or by the session owner when trading through a session
+    #[account(mut, token::mint = mint)]
+    pub seller_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = token_data.bonding_curve.quote_mint)]
+    pub seller_quote_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = 8 + size_of::<BuyerRecord>(),
+        seeds = [b"buyer_record", mint.key().as_ref(), seller_token_account.owner.as_ref()],
+        bump,
+    )]
+    pub buyer_record: Account<'info, BuyerRecord>,
+    
+    // The trading wallet, or a session key of it
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar, used to reject trades bundled with a bridge_out of this mint
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    
+    #[account(mut)]
+    pub session: Option<Account<'info, TradingSession>>,
+    
+    // The trader's opt-in trade history for this mint, if they opened one
+    #[account(mut)]
+    pub trade_history: Option<AccountLoader<'info, TradeHistory>>,
+    
+    /// CHECK: SecondaryCurve PDA of the mint, possibly uninitialized; checked in `process`
+    #[account(seeds = [b"secondary_curve", mint.key().as_ref()], bump)]
+    pub secondary_curve: UncheckedAccount<'info>,
+    
+    // Read for the factory's feature switches
+    #[account(address = token_data.factory, seeds = [b"token_factory"], bump)]
+    pub token_factory: Account<'info, TokenFactory>,
+    
+    // Required for tokens whose curve supply comes from the supply vault
+    #[account(
+        mut,
+        seeds = [b"supply_vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub supply_vault: Option<Account<'info, TokenAccount>>,
+    
+    // Referral tally, for this trade's quote mint, of the frontend that routed the trade, if any
+    #[account(mut)]
+    pub referral_stats: Option<Account<'info, ReferralStats>>,
+}
+
+impl<'info> SellTokens<'info> {
+    pub fn process(&mut self, amount: u64, min_proceeds: u64) -> Result<()> {
+        require_feature_enabled(&self.token_factory, FEATURE_TRADING, "sell_tokens")?;
+        let token_data = &self.token_data;
+        let curve_supply = self.token_stats.curve_supply;
+        
+        require_trading_enabled(token_data, "sell_tokens", amount)?;
+        require_no_trade_bridge_mix(&self.instructions, &token_data.mint, false, "sell_tokens")?;
+        require_no_round_trip(&self.instructions, token_data, false, "sell_tokens")?;
+        // Supply bought with a secondary quote asset is only sold back into that asset's reserve
+        let secondary_supply = secondary_supply(&self.secondary_curve)?;
+        require_with_context!(
+            amount <= curve_supply.saturating_sub(secondary_supply),
+            TokenFactoryError::InsufficientCurveSupply,
+            "instruction=sell_tokens token_id={} amount={} curve_supply={} secondary_supply={}",
+            token_data.token_id,
+            amount,
+            curve_supply,
+            secondary_supply
+        );
+        
+        // Sells unwind the curve from the supply left after the sale
+        let remaining_supply = curve_supply - amount;
+        let gross_proceeds = quote_bonding_curve(
+            &token_data.bonding_curve,
+            token_data.decimals,
+            remaining_supply,
+            amount,
+        )?;
+        let sell_tax = bps_of(gross_proceeds, token_data.bonding_curve.sell_tax_bps)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        let proceeds = gross_proceeds - sell_tax;
+        require_with_context!(
+            proceeds >= min_proceeds,
+            TokenFactoryError::SlippageExceeded,
+            "instruction=sell_tokens token_id={} amount={} proceeds={} min_proceeds={}",
+            token_data.token_id,
+            amount,
+            proceeds,
+            min_proceeds
+        );
+        
+        // A session key trades for the session owner, within the session's limits
+        let trader = match self.session.as_mut() {
+            Some(session) => {
+                session.charge(&self.seller.key(), &token_data.mint, 0, amount, "sell_tokens")?;
+                session.owner
+            }
+            None => self.seller.key(),
+        };
+        require_with_context!(
+            self.seller_token_account.owner == trader && self.seller_quote_account.owner == trader,
+            TokenFactoryError::InvalidAuthority,
+            "instruction=sell_tokens token_id={} trader={} token_owner={} quote_owner={}",
+            token_data.token_id,
+            trader,
+            self.seller_token_account.owner,
+            self.seller_quote_account.owner
+        );
+        
+        // Per-wallet cooldown and windowed sell cap, if the creator set them
+        let now = Clock::get()?.unix_timestamp;
+        let closes_position =
+            record_curve_sell(token_data, &mut self.buyer_record, trader, amount, now, "sell_tokens")?;
+        
+        // A fixed supply cannot be burned away, so sold tokens go back into the supply vault
+        if token_data.supply_from_vault {
+            let supply_vault = self
+                .supply_vault
+                .as_ref()
+                .ok_or(TokenFactoryError::SupplyVaultExhausted)?;
+            trader_transfer(
+                &self.token_program,
+                self.seller_token_account.to_account_info(),
+                supply_vault.to_account_info(),
+                self.seller.to_account_info(),
+                self.session.as_ref(),
+                amount,
+            )?;
+        } else {
+            trader_burn(
+                &self.token_program,
+                self.mint.to_account_info(),
+                self.seller_token_account.to_account_info(),
+                self.seller.to_account_info(),
+                self.session.as_ref(),
+                amount,
+            )?;
+        }
+        
+        // Reserve pays the seller, and the creator's tax goes to the fee vault
+        let mint_key = token_data.mint;
+        let bump = [token_data.bonding_curve.reserve_authority_bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"reserve_authority", mint_key.as_ref(), &bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.reserve_vault.to_account_info(),
+                    to: self.seller_quote_account.to_account_info(),
+                    authority: self.reserve_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            proceeds,
+        )?;
+        if sell_tax > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    token::Transfer {
+                        from: self.reserve_vault.to_account_info(),
+                        to: self.creator_fee_vault.to_account_info(),
+                        authority: self.reserve_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                sell_tax,
+            )?;
+        }
+
+        let token_stats = &mut self.token_stats;
+        token_stats.curve_supply = remaining_supply;
+        if closes_position {
+            token_stats.curve_holders = token_stats.curve_holders.saturating_sub(1);
+        }
+        token_stats.total_volume = token_stats
+            .total_volume
+            .checked_add(gross_proceeds)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        token_stats.total_creator_fees = token_stats
+            .total_creator_fees
+            .checked_add(sell_tax)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        self.token_data.bonding_curve.trading_started = true;
+        if let Some(trade_history) = self.trade_history.as_ref() {
+            trade_history
+                .load_mut()?
+                .record(&mint_key, &trader, TRADE_SIDE_SELL, amount, proceeds, now)?;
+        }
+        
+        record_referral(
+            self.referral_stats.as_mut(),
+            &self.token_data,
+            self.token_data.bonding_curve.quote_mint,
+            gross_proceeds,
+            sell_tax,
+            now,
+            "sell_tokens",
+        )?;
+        
+        emit!(TokensSoldEvent {
+            token_id: self.token_data.token_id,
+            mint: mint_key,
+            seller: trader,
+            amount,
+            proceeds,
+            sell_tax,
+            curve_supply: remaining_supply,
+            timestamp: now,
+        });
+        emit_digest(DIGEST_ACTION_SOLD, mint_key, trader, amount, now);
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct OpenBuyerRecord<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        init,
+        payer = sponsor,
+        space = 8 + size_of::<BuyerRecord>(),
+        seeds = [b"buyer_record", token_data.mint.as_ref(), wallet.as_ref()],
+        bump,
+    )]
+    pub buyer_record: Account<'info, BuyerRecord>,
+    
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> OpenBuyerRecord<'info> {
+    // Lets a sponsor pay the record's rent up front, so a wallet without SOL can trade in a
+    // transaction whose fee payer is the sponsor: buys and sells then only need its signature
+    pub fn process(&mut self, wallet: Pubkey) -> Result<()> {
+        let buyer_record = &mut self.buyer_record;
+        buyer_record.wallet = wallet;
+        buyer_record.mint = self.token_data.mint;
+        buyer_record.account_version = ACCOUNT_VERSION;
+        
+        emit!(BuyerRecordOpenedEvent {
+            token_id: self.token_data.token_id,
+            mint: self.token_data.mint,
+            wallet,
+            sponsor: self.sponsor.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ClaimCreatorFees<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    /// CHECK: PDA that owns the reserve and creator fee vaults; it holds no data
+    #[account(
+        seeds = [b"reserve_authority", token_data.mint.as_ref()],
+        bump = token_data.bonding_curve.reserve_authority_bump,
+    )]
+    pub reserve_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"creator_fee_vault", token_data.mint.as_ref()],
+        bump,
+    )]
+    pub creator_fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = token_data.bonding_curve.quote_mint)]
+    pub destination: Account<'info, TokenAccount>,
+    
+    // Token authority, or a delegate claiming under `delegation`
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    pub delegation: Option<Account<'info, Delegation>>,
+    
+    /// CHECK: PayoutSplitter PDA of the creator fee vault, possibly uninitialized; checked in `process`
+    #[account(seeds = [b"payout_splitter", creator_fee_vault.key().as_ref()], bump)]
+    pub payout_splitter: UncheckedAccount<'info>,
+}
+
+impl<'info> ClaimCreatorFees<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let token_data = &self.token_data;
+        
+        // Verify authority
+        require_authority_or_delegate(
+            token_data,
+            &self.authority.key(),
+            self.delegation.as_ref(),
+            CAPABILITY_CLAIM_FEES,
+            "claim_creator_fees",
+        )?;
+        // Delegates collect for the authority, never for themselves
+        require_with_context!(
+            token_data.authority == self.authority.key() || self.destination.owner == token_data.authority,
+            TokenFactoryError::InvalidRecipient,
+            "instruction=claim_creator_fees token_id={} destination_owner={} expected_owner={}",
+            token_data.token_id,
+            self.destination.owner,
+            token_data.authority
+        );
+        // An attached splitter is the only way fees leave the vault
+        require_with_context!(
+            self.payout_splitter.data_is_empty(),
+            TokenFactoryError::PayoutSplitterAttached,
+            "instruction=claim_creator_fees token_id={} payout_splitter={}",
+            token_data.token_id,
+            self.payout_splitter.key()
+        );
+        
+        let amount = self.creator_fee_vault.amount;
+        require_with_context!(
+            amount > 0,
+            TokenFactoryError::NothingToClaim,
+            "instruction=claim_creator_fees token_id={}",
+            token_data.token_id
+        );
+        
+        let mint_key = token_data.mint;
+        let bump = [token_data.bonding_curve.reserve_authority_bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"reserve_authority", mint_key.as_ref(), &bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.creator_fee_vault.to_account_info(),
+                    to: self.destination.to_account_info(),
+                    authority: self.reserve_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+        
+        let now = Clock::get()?.unix_timestamp;
+        emit!(CreatorFeesClaimedEvent {
+            token_id: token_data.token_id,
+            mint: mint_key,
+            destination: self.destination.key(),
+            amount,
+            timestamp: now,
+        });
+        emit_digest(DIGEST_ACTION_FEES_CLAIMED, mint_key, self.destination.owner, amount, now);
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Refund<'info> {
+    #[account(mut)]
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        mut,
+        seeds = [b"token_stats", token_data.mint.as_ref()],
+        bump = token_stats.bump,
+    )]
+    pub token_stats: Account<'info, TokenStats>,
+    
+    #[account(mut, address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA that owns the reserve and creator fee vaults; it holds no data
+    #[account(
+        seeds = [b"reserve_authority", mint.key().as_ref()],
+        bump = token_data.bonding_curve.reserve_authority_bump,
+    )]
+    pub reserve_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"reserve", mint.key().as_ref()],
+        bump,
+    )]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"buyer_record", mint.key().as_ref(), holder.key().as_ref()],
+        bump,
+    )]
+    pub buyer_record: Account<'info, BuyerRecord>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = holder,
+    )]
+    pub holder_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = token_data.bonding_curve.quote_mint,
+        token::authority = holder,
+    )]
+    pub holder_quote_account: Account<'info, TokenAccount>,
+    
+    pub holder: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> Refund<'info> {
+    pub fn process(&mut self, amount: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let token_data = &mut self.token_data;
+        
+        require_with_context!(
+            token_data.refund_policy.enabled,
+            TokenFactoryError::RefundsNotEnabled,
+            "instruction=refund token_id={}",
+            token_data.token_id
+        );
+        require_with_context!(
+            now > token_data.refund_policy.deadline && !token_data.bonding_curve.threshold_reached,
+            TokenFactoryError::RefundsNotAvailable,
+            "instruction=refund token_id={} now={} deadline={} threshold_reached={}",
+            token_data.token_id,
+            now,
+            token_data.refund_policy.deadline,
+            token_data.bonding_curve.threshold_reached
+        );
+        
+        // The first refund locks the curve and fixes the pro-rata rate for everyone
+        if !token_data.refund_policy.failed {
+            token_data.refund_policy.failed = true;
+            token_data.refund_policy.refund_reserve = self.reserve_vault.amount;
+            token_data.refund_policy.refund_supply = self.token_stats.curve_supply;
+            token_data.bonding_curve.enabled = false;
+            
+            emit!(LaunchFailedEvent {
+                token_id: token_data.token_id,
+                mint: token_data.mint,
+                refund_reserve: token_data.refund_policy.refund_reserve,
+                refund_supply: token_data.refund_policy.refund_supply,
+                timestamp: now,
+            });
+        }
+
+        // Only tokens bought from the curve are refundable
+        require_with_context!(
+            amount > 0 && amount <= self.buyer_record.net_bought,
+            TokenFactoryError::InvalidAmount,
+            "instruction=refund token_id={} amount={} net_bought={}",
+            token_data.token_id,
+            amount,
+            self.buyer_record.net_bought
+        );
+        let refund_amount = pro_rata_share(
+            amount,
+            token_data.refund_policy.refund_reserve,
+            token_data.refund_policy.refund_supply,
+        )
+        .ok_or(TokenFactoryError::MathOverflow)?;
+        
+        token::burn(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Burn {
+                    mint: self.mint.to_account_info(),
+                    from: self.holder_token_account.to_account_info(),
+                    authority: self.holder.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        let mint_key = token_data.mint;
+        let bump = [token_data.bonding_curve.reserve_authority_bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"reserve_authority", mint_key.as_ref(), &bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.reserve_vault.to_account_info(),
+                    to: self.holder_quote_account.to_account_info(),
+                    authority: self.reserve_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            refund_amount,
+        )?;
+        
+        self.buyer_record.net_bought -= amount;
+        self.token_stats.curve_supply = self.token_stats.curve_supply.saturating_sub(amount);
+        if self.buyer_record.net_bought == 0 {
+            self.token_stats.curve_holders = self.token_stats.curve_holders.saturating_sub(1);
+        }
+        
+        emit!(RefundedEvent {
+            token_id: self.token_data.token_id,
+            mint: mint_key,
+            holder: self.holder.key(),
+            amount,
+            refund_amount,
+            timestamp: now,
+        });
+        emit_digest(DIGEST_ACTION_REFUNDED, mint_key, self.holder.key(), refund_amount, now);
+        
+        Ok(())
+    }
+}
+
+// Flags graduation once the reserve reaches the threshold, and opens trading on the first buy
+pub fn record_buy_milestones(token_data: &mut TokenData, reserve_balance: u64, buyer: Pubkey, now: i64) {
+    let mint_key = token_data.mint;
+    let bonding_curve = &mut token_data.bonding_curve;
+    if !bonding_curve.threshold_reached
+        && bonding_curve.graduation_threshold > 0
+        && reserve_balance >= bonding_curve.graduation_threshold
+    {
+        bonding_curve.threshold_reached = true;
+        emit!(GraduationThresholdReachedEvent {
+            token_id: token_data.token_id,
+            mint: mint_key,
+            reserve_balance,
+            graduation_threshold: token_data.bonding_curve.graduation_threshold,
+            timestamp: now,
+        });
+        emit_digest(DIGEST_ACTION_GRADUATED, mint_key, token_data.authority, reserve_balance, now);
+        
+        // Graduated tokens keep their name and URI unless the creator opted out up front
+        if !token_data.keep_metadata_mutable {
+            token_data.metadata_frozen = true;
+            emit!(MetadataFrozenEvent {
+                token_id: token_data.token_id,
+                mint: mint_key,
+                frozen_at: now,
+                timestamp: now,
+            });
+        }
+    }
+    if !token_data.bonding_curve.trading_started {
+        token_data.bonding_curve.trading_started = true;
+        emit!(TradingOpenedEvent {
+            token_id: token_data.token_id,
+            mint: mint_key,
+            opened_at: now,
+            first_buyer: buyer,
+            timestamp: now,
+        });
+    }
+}
+
+// Applies the token's sell limits to a curve sell by `trader` and books it; returns whether the
+// sale closes the wallet's curve position
+pub fn record_curve_sell(
+    token_data: &TokenData,
+    buyer_record: &mut BuyerRecord,
+    trader: Pubkey,
+    amount: u64,
+    now: i64,
+    instruction: &str,
+) -> Result<bool> {
+    let limits = &token_data.sell_limits;
+    if buyer_record.wallet == Pubkey::default() {
+        buyer_record.wallet = trader;
+        buyer_record.mint = token_data.mint;
+        buyer_record.account_version = ACCOUNT_VERSION;
+    }
+    if limits.cooldown_seconds > 0 && buyer_record.last_sell_ts > 0 {
+        let cooldown_ends_at = buyer_record.last_sell_ts.saturating_add(limits.cooldown_seconds);
+        require_with_context!(
+            now >= cooldown_ends_at,
+            TokenFactoryError::SellCooldownActive,
+            "instruction={} token_id={} wallet={} now={} cooldown_ends_at={}",
+            instruction,
+            token_data.token_id,
+            buyer_record.wallet,
+            now,
+            cooldown_ends_at
+        );
+    }
+    if limits.max_sell_per_window > 0 {
+        if now >= buyer_record.window_start_ts.saturating_add(limits.window_seconds) {
+            buyer_record.window_start_ts = now;
+            buyer_record.sold_in_window = 0;
+        }
+        let sold_in_window = buyer_record
+            .sold_in_window
+            .checked_add(amount)
+            .ok_or(TokenFactoryError::MathOverflow)?;
+        require_with_context!(
+            sold_in_window <= limits.max_sell_per_window,
+            TokenFactoryError::SellLimitExceeded,
+            "instruction={} token_id={} wallet={} amount={} sold_in_window={} max_sell_per_window={}",
+            instruction,
+            token_data.token_id,
+            buyer_record.wallet,
+            amount,
+            buyer_record.sold_in_window,
+            limits.max_sell_per_window
+        );
+        buyer_record.sold_in_window = sold_in_window;
+    }
+    buyer_record.last_sell_ts = now;
+    let closes_position = buyer_record.net_bought > 0 && buyer_record.net_bought <= amount;
+    buyer_record.net_bought = buyer_record.net_bought.saturating_sub(amount);
+    Ok(closes_position)
+}
+
+pub fn require_trading_enabled(token_data: &TokenData, instruction: &str, amount: u64) -> Result<()> {
+    require_with_context!(
+        amount > 0,
+        TokenFactoryError::InvalidAmount,
+        "instruction={} token_id={} amount={}",
+        instruction,
+        token_data.token_id,
+        amount
+    );
+    require_with_context!(
+        token_data.bonding_curve.configured,
+        TokenFactoryError::CurveNotConfigured,
+        "instruction={} token_id={} amount={}",
+        instruction,
+        token_data.token_id,
+        amount
+    );
+    require_with_context!(
+        token_data.bonding_curve.enabled,
+        TokenFactoryError::CurveDisabled,
+        "instruction={} token_id={} amount={}",
+        instruction,
+        token_data.token_id,
+        amount
+    );
+    require_with_context!(
+        token_data.bonding_curve.quote_mint != Pubkey::default(),
+        TokenFactoryError::ReserveNotInitialized,
+        "instruction={} token_id={} amount={}",
+        instruction,
+        token_data.token_id,
+        amount
+    );
+    require_with_context!(
+        !token_data.bonding_curve.auction_pending,
+        TokenFactoryError::AuctionInProgress,
+        "instruction={} token_id={} amount={}",
+        instruction,
+        token_data.token_id,
+        amount
+    );
+    
+    // A refundable launch that missed its deadline only allows refunds
+    let refund_policy = &token_data.refund_policy;
+    if refund_policy.enabled && !token_data.bonding_curve.threshold_reached {
+        let now = Clock::get()?.unix_timestamp;
+        require_with_context!(
+            now <= refund_policy.deadline,
+            TokenFactoryError::LaunchFailed,
+            "instruction={} token_id={} now={} deadline={}",
+            instruction,
+            token_data.token_id,
+            now,
+            refund_policy.deadline
+        );
+    }
+    Ok(())
+}
+
+/// Per-(mint, wallet) trading state used to enforce sell limits and refunds
+#[account]
+pub struct BuyerRecord {
+    pub wallet: Pubkey,
+    pub mint: Pubkey,
+    pub net_bought: u64, // Tokens bought from the curve minus tokens sold back
+    pub last_sell_ts: i64,
+    pub window_start_ts: i64,
+    pub sold_in_window: u64,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+#[event]
+pub struct BuyerRecordOpenedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub sponsor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReserveInitializedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub reserve_vault: Pubkey,
+    pub creator_fee_vault: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TradingOpenedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub opened_at: i64,
+    pub first_buyer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokensBoughtEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub buyer: Pubkey,
+    pub amount: u64,
+    pub cost: u64,
+    pub curve_supply: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokensSoldEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub seller: Pubkey,
+    pub amount: u64,
+    pub proceeds: u64,
+    pub sell_tax: u64,
+    pub curve_supply: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GraduationThresholdReachedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub reserve_balance: u64,
+    pub graduation_threshold: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MetadataFrozenEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub frozen_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LaunchFailedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub refund_reserve: u64,
+    pub refund_supply: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RefundedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+    pub refund_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreatorFeesClaimedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
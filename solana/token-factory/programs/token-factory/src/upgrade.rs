@@ -0,0 +1,137 @@
+// Upgrade module for Crossify Token Factory
+// This file contains the on-chain record of who can upgrade the program and the path to renounce it
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::solana_program::program::invoke;
+use std::mem::size_of;
+
+use crate::{TokenFactoryError, ACCOUNT_RESERVED_LEN, ACCOUNT_VERSION};
+
+#[derive(Accounts)]
+pub struct RecordUpgradeAuthority<'info> {
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + size_of::<UpgradeAuthorityRecord>(),
+        seeds = [b"upgrade_authority"],
+        bump,
+    )]
+    pub upgrade_authority_record: Account<'info, UpgradeAuthorityRecord>,
+    
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::TokenFactory>,
+    
+    pub program_data: Account<'info, ProgramData>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RecordUpgradeAuthority<'info> {
+    // Permissionless, so anyone can bring the record up to date after an authority change
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        let upgrade_authority = self.program_data.upgrade_authority_address;
+        self.upgrade_authority_record.bump = bump;
+        self.upgrade_authority_record.account_version = ACCOUNT_VERSION;
+        self.upgrade_authority_record.update(upgrade_authority, self.program_data.slot)
+    }
+}
+
+#[derive(Accounts)]
+pub struct RenounceUpgradeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"upgrade_authority"],
+        bump = upgrade_authority_record.bump,
+    )]
+    pub upgrade_authority_record: Account<'info, UpgradeAuthorityRecord>,
+    
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::TokenFactory>,
+    
+    #[account(
+        mut,
+        constraint = program_data.upgrade_authority_address == Some(authority.key())
+            @ TokenFactoryError::InvalidAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    pub authority: Signer<'info>,
+    
+    /// CHECK: BPF upgradeable loader, which owns the program data account
+    #[account(address = bpf_loader_upgradeable::ID)]
+    pub bpf_loader_upgradeable_program: UncheckedAccount<'info>,
+}
+
+impl<'info> RenounceUpgradeAuthority<'info> {
+    // Irreversible: with no upgrade authority the loader never accepts another deploy
+    pub fn process(&mut self) -> Result<()> {
+        let renounce_ix = bpf_loader_upgradeable::set_upgrade_authority(
+            &self.program.key(),
+            &self.authority.key(),
+            None,
+        );
+        invoke(
+            &renounce_ix,
+            &[
+                self.program_data.to_account_info(),
+                self.authority.to_account_info(),
+                self.bpf_loader_upgradeable_program.to_account_info(),
+            ],
+        )?;
+        
+        self.upgrade_authority_record.update(None, self.program_data.slot)
+    }
+}
+
+/// Last upgrade authority seen in the program's ProgramData account
+#[account]
+pub struct UpgradeAuthorityRecord {
+    pub upgrade_authority: Pubkey, // default once renounced
+    pub immutable: bool,
+    pub last_deploy_slot: u64,
+    pub recorded_at: i64,
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+impl UpgradeAuthorityRecord {
+    fn update(&mut self, upgrade_authority: Option<Pubkey>, last_deploy_slot: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let previous_authority = self.upgrade_authority;
+        let new_authority = upgrade_authority.unwrap_or_default();
+        let changed = self.recorded_at == 0
+            || previous_authority != new_authority
+            || self.immutable != upgrade_authority.is_none();
+        
+        self.upgrade_authority = new_authority;
+        self.immutable = upgrade_authority.is_none();
+        self.last_deploy_slot = last_deploy_slot;
+        self.recorded_at = now;
+        
+        if changed {
+            emit!(UpgradeAuthorityChangedEvent {
+                previous_authority,
+                new_authority,
+                immutable: self.immutable,
+                last_deploy_slot,
+                timestamp: now,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct UpgradeAuthorityChangedEvent {
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey, // default when the program became immutable
+    pub immutable: bool,
+    pub last_deploy_slot: u64,
+    pub timestamp: i64,
+}
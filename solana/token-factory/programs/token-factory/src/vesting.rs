@@ -0,0 +1,371 @@
+// Vesting module for Crossify Token Factory
+// This file contains vesting vaults that release deposited tokens to a beneficiary over time
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::{TokenData, TokenFactoryError, ACCOUNT_RESERVED_LEN, ACCOUNT_VERSION};
+
+// How a vault releases its tokens
+pub const VESTING_MODE_CLIFF: u8 = 0; // Equal unlocks at evenly spaced cliffs
+pub const VESTING_MODE_STREAM: u8 = 1; // A fixed amount accrues every second
+
+#[derive(Accounts)]
+#[instruction(beneficiary: Pubkey)]
+pub struct CreateVestingVault<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + VestingVault::LEN,
+        seeds = [b"vesting_vault", mint.key().as_ref(), beneficiary.as_ref()],
+        bump,
+    )]
+    pub vesting_vault: Account<'info, VestingVault>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"vesting_tokens", vesting_vault.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vesting_vault,
+    )]
+    pub vesting_tokens: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = authority,
+    )]
+    pub funding_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> CreateVestingVault<'info> {
+    // The whole amount is deposited up front, so the schedule can never be underfunded
+    pub fn process(&mut self, bump: u8, beneficiary: Pubkey, amount: u64, schedule: VestingSchedule) -> Result<()> {
+        let token_data = &self.token_data;
+        
+        // Verify authority
+        require_with_context!(
+            token_data.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=create_vesting_vault token_id={} signer={} expected_authority={}",
+            token_data.token_id,
+            self.authority.key(),
+            token_data.authority
+        );
+        require_with_context!(
+            amount > 0,
+            TokenFactoryError::InvalidAmount,
+            "instruction=create_vesting_vault token_id={} amount={}",
+            token_data.token_id,
+            amount
+        );
+        let valid_mode = match schedule.mode {
+            VESTING_MODE_CLIFF => schedule.cliff_count > 0,
+            VESTING_MODE_STREAM => schedule.cliff_count == 0,
+            _ => false,
+        };
+        require_with_context!(
+            valid_mode && schedule.end_ts > schedule.start_ts,
+            TokenFactoryError::InvalidVestingSchedule,
+            "instruction=create_vesting_vault token_id={} mode={} start_ts={} end_ts={} cliff_count={}",
+            token_data.token_id,
+            schedule.mode,
+            schedule.start_ts,
+            schedule.end_ts,
+            schedule.cliff_count
+        );
+        
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.funding_account.to_account_info(),
+                    to: self.vesting_tokens.to_account_info(),
+                    authority: self.authority.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        let vesting_vault = &mut self.vesting_vault;
+        vesting_vault.mint = token_data.mint;
+        vesting_vault.beneficiary = beneficiary;
+        vesting_vault.funder = self.authority.key();
+        vesting_vault.schedule = schedule.clone();
+        vesting_vault.total_amount = amount;
+        vesting_vault.claimed_amount = 0;
+        vesting_vault.rate_per_second = if schedule.mode == VESTING_MODE_STREAM {
+            amount / (schedule.end_ts - schedule.start_ts) as u64
+        } else {
+            0
+        };
+        vesting_vault.last_claim_ts = schedule.start_ts;
+        vesting_vault.bump = bump;
+        vesting_vault.account_version = ACCOUNT_VERSION;
+        
+        emit!(VestingVaultCreatedEvent {
+            token_id: token_data.token_id,
+            mint: token_data.mint,
+            beneficiary,
+            amount,
+            mode: schedule.mode,
+            start_ts: schedule.start_ts,
+            end_ts: schedule.end_ts,
+            cliff_count: schedule.cliff_count,
+            rate_per_second: vesting_vault.rate_per_second,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(
+        mut,
+        seeds = [b"vesting_vault", token_data.mint.as_ref(), beneficiary.key().as_ref()],
+        bump = vesting_vault.bump,
+    )]
+    pub vesting_vault: Account<'info, VestingVault>,
+    
+    #[account(
+        mut,
+        seeds = [b"vesting_tokens", vesting_vault.key().as_ref()],
+        bump,
+    )]
+    pub vesting_tokens: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = token_data.mint,
+        token::authority = beneficiary,
+    )]
+    pub beneficiary_token_account: Account<'info, TokenAccount>,
+    
+    pub beneficiary: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClaimVested<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let vesting_vault = &self.vesting_vault;
+        let amount = vesting_vault.claimable(now).ok_or(TokenFactoryError::MathOverflow)?;
+        require_with_context!(
+            amount > 0,
+            TokenFactoryError::NothingToClaim,
+            "instruction=claim_vested token_id={} beneficiary={} mode={} now={} claimed={} total={}",
+            self.token_data.token_id,
+            vesting_vault.beneficiary,
+            vesting_vault.schedule.mode,
+            now,
+            vesting_vault.claimed_amount,
+            vesting_vault.total_amount
+        );
+        
+        let (mint_key, beneficiary) = (vesting_vault.mint, vesting_vault.beneficiary);
+        let bump = [vesting_vault.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"vesting_vault", mint_key.as_ref(), beneficiary.as_ref(), &bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.vesting_tokens.to_account_info(),
+                    to: self.beneficiary_token_account.to_account_info(),
+                    authority: self.vesting_vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+        
+        let vesting_vault = &mut self.vesting_vault;
+        vesting_vault.claimed_amount += amount;
+        vesting_vault.last_claim_ts = now;
+        
+        emit!(VestingClaimedEvent {
+            token_id: self.token_data.token_id,
+            mint: mint_key,
+            beneficiary,
+            mode: vesting_vault.schedule.mode,
+            amount,
+            claimed_amount: vesting_vault.claimed_amount,
+            total_amount: vesting_vault.total_amount,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct VestingSchedule {
+    pub mode: u8, // VESTING_MODE_*
+    pub start_ts: i64,
+    pub end_ts: i64, // Everything is claimable from here on
+    pub cliff_count: u32, // Cliff mode: unlocks spread evenly over start..end, the last at end_ts. Zero when streaming
+}
+
+impl VestingSchedule {
+    pub const LEN: usize = 1 + 8 + 8 + 4;
+}
+
+/// Tokens vesting to one beneficiary, held in the `vesting_tokens` account this PDA owns
+#[account]
+pub struct VestingVault {
+    pub mint: Pubkey,
+    pub beneficiary: Pubkey,
+    pub funder: Pubkey, // Token authority that deposited the tokens
+    pub schedule: VestingSchedule,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub rate_per_second: u64, // Stream mode: total over the schedule's duration, rounded down
+    pub last_claim_ts: i64, // Stream mode: accrual restarts here; the schedule start until the first claim
+    pub bump: u8,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+impl VestingVault {
+    pub const LEN: usize = 32 + 32 + 32 + VestingSchedule::LEN + 8 + 8 + 8 + 8 + 1 + 1 + ACCOUNT_RESERVED_LEN;
+    
+    // Amount the beneficiary can claim at `now`. Once the schedule ends everything left is
+    // claimable, which also releases the dust the per-second rate rounded away
+    pub fn claimable(&self, now: i64) -> Option<u64> {
+        let schedule = &self.schedule;
+        let remaining = self.total_amount - self.claimed_amount;
+        if now >= schedule.end_ts {
+            return Some(remaining);
+        }
+        if now <= schedule.start_ts {
+            return Some(0);
+        }
+        match schedule.mode {
+            VESTING_MODE_STREAM => {
+                let accrued = (self.rate_per_second as u128).checked_mul((now - self.last_claim_ts) as u128)?;
+                Some(accrued.min(remaining as u128) as u64)
+            }
+            _ => {
+                let cliffs = (now - schedule.start_ts) as u128 * schedule.cliff_count as u128
+                    / (schedule.end_ts - schedule.start_ts) as u128;
+                let unlocked = self.total_amount as u128 * cliffs / schedule.cliff_count as u128;
+                Some((unlocked as u64).saturating_sub(self.claimed_amount))
+            }
+        }
+    }
+}
+
+#[event]
+pub struct VestingVaultCreatedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub mode: u8,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub cliff_count: u32,
+    pub rate_per_second: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestingClaimedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mode: u8,
+    pub amount: u64,
+    pub claimed_amount: u64,
+    pub total_amount: u64,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vault(mode: u8, total_amount: u64, cliff_count: u32) -> VestingVault {
+        let duration = 1_000;
+        VestingVault {
+            mint: Pubkey::new_unique(),
+            beneficiary: Pubkey::new_unique(),
+            funder: Pubkey::new_unique(),
+            schedule: VestingSchedule {
+                mode,
+                start_ts: 100,
+                end_ts: 100 + duration,
+                cliff_count,
+            },
+            total_amount,
+            claimed_amount: 0,
+            rate_per_second: if mode == VESTING_MODE_STREAM {
+                total_amount / duration as u64
+            } else {
+                0
+            },
+            last_claim_ts: 100,
+            bump: 255,
+            account_version: ACCOUNT_VERSION,
+            _reserved: [0; ACCOUNT_RESERVED_LEN],
+        }
+    }
+
+    #[test]
+    fn cliffs_unlock_in_equal_steps() {
+        let vault = vault(VESTING_MODE_CLIFF, 4_000, 4);
+        assert_eq!(vault.claimable(100), Some(0));
+        assert_eq!(vault.claimable(349), Some(0));
+        assert_eq!(vault.claimable(350), Some(1_000));
+        assert_eq!(vault.claimable(849), Some(2_000));
+        assert_eq!(vault.claimable(850), Some(3_000));
+        assert_eq!(vault.claimable(1_100), Some(4_000));
+    }
+
+    #[test]
+    fn streams_accrue_per_second_since_the_last_claim() {
+        let mut vault = vault(VESTING_MODE_STREAM, 10_500, 0);
+        assert_eq!(vault.rate_per_second, 10);
+        assert_eq!(vault.claimable(90), Some(0));
+        assert_eq!(vault.claimable(101), Some(10));
+        assert_eq!(vault.claimable(400), Some(3_000));
+        
+        vault.claimed_amount = 3_000;
+        vault.last_claim_ts = 400;
+        assert_eq!(vault.claimable(400), Some(0));
+        assert_eq!(vault.claimable(450), Some(500));
+        // The end releases the rounding dust along with the rest
+        assert_eq!(vault.claimable(1_100), Some(7_500));
+    }
+
+    #[test]
+    fn claims_never_exceed_the_deposit() {
+        for mode in [VESTING_MODE_CLIFF, VESTING_MODE_STREAM] {
+            let mut vault = vault(mode, 999, if mode == VESTING_MODE_CLIFF { 7 } else { 0 });
+            for now in (0..1_200).step_by(37) {
+                let amount = vault.claimable(now).unwrap();
+                vault.claimed_amount += amount;
+                vault.last_claim_ts = now.max(vault.last_claim_ts);
+            }
+            assert_eq!(vault.claimed_amount, 999);
+        }
+    }
+}
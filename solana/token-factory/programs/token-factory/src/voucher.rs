@@ -0,0 +1,291 @@
+// Voucher module for Crossify Token Factory
+// This file contains creator-signed claim vouchers verified through the ed25519 program
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{ed25519_program, sysvar::instructions as ix_sysvar};
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use std::mem::size_of;
+
+use crate::{TokenData, TokenFactoryError, ACCOUNT_RESERVED_LEN, ACCOUNT_VERSION};
+
+// Layout of a single-signature ed25519 program instruction
+const ED25519_HEADER_LEN: usize = 2;
+const ED25519_OFFSETS_LEN: usize = 14;
+const ED25519_SIGNATURE_LEN: usize = 64;
+const ED25519_PUBKEY_LEN: usize = 32;
+
+#[derive(Accounts)]
+pub struct FundVoucherVault<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA that owns the voucher vault; it holds no data
+    #[account(seeds = [b"voucher_authority", mint.key().as_ref()], bump)]
+    pub voucher_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"voucher_vault", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = voucher_authority,
+    )]
+    pub voucher_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = authority,
+    )]
+    pub funding_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> FundVoucherVault<'info> {
+    pub fn process(&mut self, amount: u64) -> Result<()> {
+        // Verify authority
+        require_with_context!(
+            self.token_data.authority == self.authority.key(),
+            TokenFactoryError::InvalidAuthority,
+            "instruction=fund_voucher_vault token_id={} signer={} expected_authority={}",
+            self.token_data.token_id,
+            self.authority.key(),
+            self.token_data.authority
+        );
+        require_with_context!(
+            amount > 0,
+            TokenFactoryError::InvalidAmount,
+            "instruction=fund_voucher_vault token_id={} amount={}",
+            self.token_data.token_id,
+            amount
+        );
+        
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.funding_account.to_account_info(),
+                    to: self.voucher_vault.to_account_info(),
+                    authority: self.authority.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        emit!(VoucherVaultFundedEvent {
+            token_id: self.token_data.token_id,
+            mint: self.mint.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(voucher_id: u64)]
+pub struct ClaimVoucher<'info> {
+    pub token_data: Account<'info, TokenData>,
+    
+    #[account(address = token_data.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA that owns the voucher vault; it holds no data
+    #[account(seeds = [b"voucher_authority", mint.key().as_ref()], bump)]
+    pub voucher_authority: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"voucher_vault", mint.key().as_ref()],
+        bump,
+    )]
+    pub voucher_vault: Account<'info, TokenAccount>,
+    
+    // One claim record per voucher id makes every voucher single-use
+    #[account(
+        init,
+        payer = claimant,
+        space = 8 + size_of::<VoucherClaim>(),
+        seeds = [b"voucher_claim", mint.key().as_ref(), &voucher_id.to_le_bytes()],
+        bump,
+    )]
+    pub voucher_claim: Account<'info, VoucherClaim>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = claimant,
+    )]
+    pub claimant_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar, used to read the preceding ed25519 verification
+    #[account(address = ix_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimVoucher<'info> {
+    pub fn process(&mut self, voucher_bump: u8, voucher_id: u64, amount: u64, expires_at: i64) -> Result<()> {
+        let token_data = &self.token_data;
+        let claimant = self.claimant.key();
+        let now = Clock::get()?.unix_timestamp;
+        
+        require_with_context!(
+            expires_at == 0 || now <= expires_at,
+            TokenFactoryError::VoucherExpired,
+            "instruction=claim_voucher token_id={} voucher_id={} now={} expires_at={}",
+            token_data.token_id,
+            voucher_id,
+            now,
+            expires_at
+        );
+        require_with_context!(
+            amount > 0,
+            TokenFactoryError::InvalidAmount,
+            "instruction=claim_voucher token_id={} voucher_id={} amount={}",
+            token_data.token_id,
+            voucher_id,
+            amount
+        );
+        
+        // The creator must have signed exactly this voucher in the preceding instruction
+        let message = voucher_message(&token_data.mint, &claimant, voucher_id, amount, expires_at);
+        require_with_context!(
+            verify_ed25519_instruction(&self.instructions.to_account_info(), &token_data.authority, &message)?,
+            TokenFactoryError::InvalidVoucherSignature,
+            "instruction=claim_voucher token_id={} voucher_id={} claimant={} expected_signer={}",
+            token_data.token_id,
+            voucher_id,
+            claimant,
+            token_data.authority
+        );
+        
+        let mint_key = token_data.mint;
+        let bump = [voucher_bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"voucher_authority", mint_key.as_ref(), &bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.voucher_vault.to_account_info(),
+                    to: self.claimant_token_account.to_account_info(),
+                    authority: self.voucher_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+        
+        let voucher_claim = &mut self.voucher_claim;
+        voucher_claim.mint = mint_key;
+        voucher_claim.voucher_id = voucher_id;
+        voucher_claim.claimant = claimant;
+        voucher_claim.account_version = ACCOUNT_VERSION;
+        voucher_claim.amount = amount;
+        
+        emit!(VoucherClaimedEvent {
+            token_id: self.token_data.token_id,
+            mint: mint_key,
+            voucher_id,
+            claimant,
+            amount,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+// Signed bytes: mint || claimant || voucher_id || amount || expires_at, integers little-endian
+pub fn voucher_message(mint: &Pubkey, claimant: &Pubkey, voucher_id: u64, amount: u64, expires_at: i64) -> Vec<u8> {
+    let mut message = Vec::with_capacity(32 + 32 + 8 + 8 + 8);
+    message.extend_from_slice(mint.as_ref());
+    message.extend_from_slice(claimant.as_ref());
+    message.extend_from_slice(&voucher_id.to_le_bytes());
+    message.extend_from_slice(&amount.to_le_bytes());
+    message.extend_from_slice(&expires_at.to_le_bytes());
+    message
+}
+
+// Checks that the instruction before this one is an ed25519 verification of `message` by `signer`
+pub fn verify_ed25519_instruction(instructions: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<bool> {
+    let current_index = ix_sysvar::load_current_index_checked(instructions)?;
+    if current_index == 0 {
+        return Ok(false);
+    }
+    let ix = ix_sysvar::load_instruction_at_checked((current_index - 1) as usize, instructions)?;
+    if ix.program_id != ed25519_program::ID || !ix.accounts.is_empty() {
+        return Ok(false);
+    }
+
+    let data = &ix.data;
+    if data.len() < ED25519_HEADER_LEN + ED25519_OFFSETS_LEN || data[0] != 1 {
+        return Ok(false);
+    }
+    let read_u16 = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+    let offsets = ED25519_HEADER_LEN;
+    let signature_offset = read_u16(offsets) as usize;
+    let signature_ix = read_u16(offsets + 2);
+    let pubkey_offset = read_u16(offsets + 4) as usize;
+    let pubkey_ix = read_u16(offsets + 6);
+    let message_offset = read_u16(offsets + 8) as usize;
+    let message_len = read_u16(offsets + 10) as usize;
+    let message_ix = read_u16(offsets + 12);
+    
+    // All parts must live in the ed25519 instruction itself
+    if signature_ix != u16::MAX || pubkey_ix != u16::MAX || message_ix != u16::MAX {
+        return Ok(false);
+    }
+    if signature_offset + ED25519_SIGNATURE_LEN > data.len()
+        || pubkey_offset + ED25519_PUBKEY_LEN > data.len()
+        || message_offset + message_len > data.len()
+    {
+        return Ok(false);
+    }
+
+    Ok(&data[pubkey_offset..pubkey_offset + ED25519_PUBKEY_LEN] == signer.as_ref()
+        && &data[message_offset..message_offset + message_len] == message)
+}
+
+#[account]
+pub struct VoucherClaim {
+    pub mint: Pubkey,
+    pub voucher_id: u64,
+    pub claimant: Pubkey,
+    pub amount: u64,
+    pub account_version: u8,
+    pub _reserved: [u8; ACCOUNT_RESERVED_LEN],
+}
+
+#[event]
+pub struct VoucherVaultFundedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoucherClaimedEvent {
+    pub token_id: u64,
+    pub mint: Pubkey,
+    pub voucher_id: u64,
+    pub claimant: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
@@ -1,34 +1,114 @@
 // Wormhole integration module for Crossify Token Factory
-// This file contains the integration with Wormhole for cross-chain messaging
+// This file contains the integration with Wormhole for cross-chain messaging. The wire codec
+// itself lives in crossify-common, shared with off-chain tools; the payload types stay here so
+// the IDL clients generate from carries them
 
 use anchor_lang::prelude::*;
-use std::mem::size_of;
+use crossify_common::CodecError;
+
+use crate::{TokenFactoryError, MAX_METADATA_URI_LEN, MAX_NAME_LEN, MAX_REGISTERED_SYMBOL_LEN};
+
+pub use crossify_common::{is_evm_chain, ABI_WIRE_VERSION, MAX_BATCH_SYNC_MESSAGES};
 
 // Wormhole program IDs
+#[allow(clippy::module_inception)]
 pub mod wormhole {
     use anchor_lang::prelude::*;
     
-    // Wormhole Core Bridge program ID on Solana Devnet
+    // Wormhole Core Bridge program ID, matching `crate::wormhole_core_bridge::ID` for this build
+    #[cfg(feature = "mainnet-beta")]
+    #[constant]
+    pub const CORE_BRIDGE_PROGRAM_ID: &str = "worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth";
+    #[cfg(not(feature = "mainnet-beta"))]
+    #[constant]
     pub const CORE_BRIDGE_PROGRAM_ID: &str = "3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5";
     
-    // Wormhole Token Bridge program ID on Solana Devnet
+    // Wormhole Token Bridge program ID, matching `crate::wormhole_token_bridge::ID` for this build
+    #[cfg(feature = "mainnet-beta")]
+    #[constant]
+    pub const TOKEN_BRIDGE_PROGRAM_ID: &str = "wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb";
+    #[cfg(not(feature = "mainnet-beta"))]
+    #[constant]
     pub const TOKEN_BRIDGE_PROGRAM_ID: &str = "DZnkkTmCiFWfYTfT41X3Rd1kDgozqzxWaHqsw6W4x2oe";
     
-    // Chain IDs in Wormhole ecosystem
+    // Chain IDs and message types from `crossify_common::chain`, written out as literals because
+    // the IDL records a constant's expression, not its value; the tests keep the two in step
+    #[constant]
     pub const CHAIN_ID_SOLANA: u16 = 1;
+    #[constant]
     pub const CHAIN_ID_ETHEREUM: u16 = 2;
+    #[constant]
     pub const CHAIN_ID_BSC: u16 = 4;
-    pub const CHAIN_ID_BASE: u16 = 30; // Example value, may need to be updated
+    #[constant]
+    pub const CHAIN_ID_BASE: u16 = 30;
     
-    // Message types
+    #[constant]
     pub const MSG_TYPE_TOKEN_CREATION: u8 = 1;
+    #[constant]
     pub const MSG_TYPE_PRICE_UPDATE: u8 = 2;
+    #[constant]
     pub const MSG_TYPE_LIQUIDITY_UPDATE: u8 = 3;
+    #[constant]
+    pub const MSG_TYPE_SUPPLY_UPDATE: u8 = 4;
+    #[constant]
+    pub const MSG_TYPE_TOKEN_TRANSFER: u8 = 5;
+    #[constant]
+    pub const MSG_TYPE_BATCH_SYNC: u8 = 6;
+    #[constant]
+    pub const MSG_TYPE_REMOTE_BUY: u8 = 7;
+    #[constant]
+    pub const MSG_TYPE_REMOTE_SELL: u8 = 8;
+    #[constant]
+    pub const MSG_TYPE_QUOTE_TRANSFER: u8 = 9;
+    #[constant]
+    pub const MSG_TYPE_REFUND: u8 = 10;
+}
+
+/// 32-byte Wormhole address: Solana keys as-is, 20-byte EVM addresses left-padded with zeros
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct UniversalAddress {
+    pub bytes: [u8; 32],
+}
+
+impl UniversalAddress {
+    pub fn from_pubkey(pubkey: &Pubkey) -> Self {
+        Self { bytes: pubkey.to_bytes() }
+    }
+
+    pub fn from_evm_address(address: &[u8; 20]) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes[12..].copy_from_slice(address);
+        Self { bytes }
+    }
+
+    pub fn to_pubkey(&self) -> Pubkey {
+        Pubkey::new_from_array(self.bytes)
+    }
+
+    // The 20-byte address, if the top 12 bytes are zero padding
+    pub fn to_evm_address(&self) -> Option<[u8; 20]> {
+        if self.bytes[..12].iter().any(|byte| *byte != 0) {
+            return None;
+        }
+        let mut address = [0u8; 20];
+        address.copy_from_slice(&self.bytes[12..]);
+        Some(address)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.bytes.iter().all(|byte| *byte == 0)
+    }
+
+    // Non-zero, and correctly padded when `chain_id` is an EVM chain
+    pub fn is_valid_for_chain(&self, chain_id: u16) -> bool {
+        !self.is_zero() && (!is_evm_chain(chain_id) || self.to_evm_address().is_some())
+    }
 }
 
-// Wormhole message payload structure for token creation
+/// Wormhole message payload structure for token creation
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct TokenCreationPayload {
+    pub factory: Pubkey, // Token ids are only unique within one factory
     pub token_id: u64,
     pub name: String,
     pub symbol: String,
@@ -41,18 +121,21 @@ pub struct TokenCreationPayload {
     pub reserve_ratio: u16,
 }
 
-// Wormhole message payload structure for price updates
+/// Wormhole message payload structure for price updates
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct PriceUpdatePayload {
+    pub factory: Pubkey,
     pub token_id: u64,
+    pub nonce: u64, // Grows with every price update the token's chain sends, starting at 1
     pub current_price: u64,
     pub current_supply: u64,
     pub timestamp: i64,
 }
 
-// Wormhole message payload structure for liquidity updates
+/// Wormhole message payload structure for liquidity updates
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct LiquidityUpdatePayload {
+    pub factory: Pubkey,
     pub token_id: u64,
     pub liquidity_added: u64,
     pub liquidity_removed: u64,
@@ -60,59 +143,490 @@ pub struct LiquidityUpdatePayload {
     pub timestamp: i64,
 }
 
+/// Wormhole message payload structure for supply updates (e.g. after burns)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SupplyUpdatePayload {
+    pub factory: Pubkey,
+    pub token_id: u64,
+    pub total_supply: u64,
+    pub total_burned: u64,
+    pub timestamp: i64,
+}
+
+/// Wormhole message payload structure for tokens burned here and released on the target chain
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TokenTransferPayload {
+    pub factory: Pubkey,
+    pub token_id: u64,
+    pub amount: u64,
+    pub sender: Pubkey,
+    pub recipient_chain: u16,
+    pub recipient: UniversalAddress,
+    pub timestamp: i64,
+}
+
+/// Wormhole message payload structure for a curve buy paid for on another chain
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RemoteBuyPayload {
+    pub factory: Pubkey,
+    pub token_id: u64,
+    pub quote_amount: u64, // Bridged through the Token Bridge into the token's remote quote vault
+    pub min_tokens: u64,
+    pub buyer: UniversalAddress, // Paying address on the source chain
+    pub recipient_chain: u16,    // CHAIN_ID_SOLANA to hold the tokens here, otherwise bridged on
+    pub recipient: UniversalAddress,
+    pub timestamp: i64,
+}
+
+/// Wormhole message payload structure for a curve sell of tokens already taken out of supply on
+/// another chain
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RemoteSellPayload {
+    pub factory: Pubkey,
+    pub token_id: u64,
+    pub amount: u64,
+    pub min_proceeds: u64,
+    pub seller: UniversalAddress, // Receives the quote proceeds on the source chain
+    pub timestamp: i64,
+}
+
+/// Wormhole message payload structure for quote proceeds owed to an address on another chain
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct QuoteTransferPayload {
+    pub factory: Pubkey,
+    pub token_id: u64,
+    pub quote_mint: Pubkey,
+    pub amount: u64, // Held in the token's remote quote vault for the Token Bridge transfer out
+    pub recipient_chain: u16,
+    pub recipient: UniversalAddress,
+    pub timestamp: i64,
+}
+
+/// Wormhole message payload structure for a remote trade rejected here, releasing what the
+/// source chain escrowed for it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RefundPayload {
+    pub factory: Pubkey,
+    pub token_id: u64,
+    pub sequence: u64,    // Sequence of the rejected message on the source chain
+    pub message_type: u8, // Type of the rejected message
+    pub reason: u8,
+    pub amount: u64,
+    pub recipient: UniversalAddress,
+    pub timestamp: i64,
+}
+
+fn frame_message(message_type: u8, body: &[u8]) -> Vec<u8> {
+    crossify_common::frame_message(message_type, body)
+}
+
 // Function to serialize a token creation message
 pub fn serialize_token_creation_message(payload: &TokenCreationPayload) -> Vec<u8> {
-    let mut message = Vec::new();
-    message.push(wormhole::MSG_TYPE_TOKEN_CREATION);
-    message.extend_from_slice(&payload.try_to_vec().unwrap());
-    message
+    frame_message(wormhole::MSG_TYPE_TOKEN_CREATION, &payload.try_to_vec().unwrap())
 }
 
 // Function to serialize a price update message
 pub fn serialize_price_update_message(payload: &PriceUpdatePayload) -> Vec<u8> {
-    let mut message = Vec::new();
-    message.push(wormhole::MSG_TYPE_PRICE_UPDATE);
-    message.extend_from_slice(&payload.try_to_vec().unwrap());
-    message
+    frame_message(wormhole::MSG_TYPE_PRICE_UPDATE, &payload.try_to_vec().unwrap())
 }
 
 // Function to serialize a liquidity update message
 pub fn serialize_liquidity_update_message(payload: &LiquidityUpdatePayload) -> Vec<u8> {
-    let mut message = Vec::new();
-    message.push(wormhole::MSG_TYPE_LIQUIDITY_UPDATE);
-    message.extend_from_slice(&payload.try_to_vec().unwrap());
-    message
+    frame_message(wormhole::MSG_TYPE_LIQUIDITY_UPDATE, &payload.try_to_vec().unwrap())
+}
+
+// Function to serialize a supply update message
+pub fn serialize_supply_update_message(payload: &SupplyUpdatePayload) -> Vec<u8> {
+    frame_message(wormhole::MSG_TYPE_SUPPLY_UPDATE, &payload.try_to_vec().unwrap())
+}
+
+// Function to serialize a token transfer message
+pub fn serialize_token_transfer_message(payload: &TokenTransferPayload) -> Vec<u8> {
+    frame_message(wormhole::MSG_TYPE_TOKEN_TRANSFER, &payload.try_to_vec().unwrap())
+}
+
+// Function to serialize a remote buy message
+pub fn serialize_remote_buy_message(payload: &RemoteBuyPayload) -> Vec<u8> {
+    frame_message(wormhole::MSG_TYPE_REMOTE_BUY, &payload.try_to_vec().unwrap())
+}
+
+// Function to serialize a remote sell message
+pub fn serialize_remote_sell_message(payload: &RemoteSellPayload) -> Vec<u8> {
+    frame_message(wormhole::MSG_TYPE_REMOTE_SELL, &payload.try_to_vec().unwrap())
+}
+
+// Function to serialize a quote transfer message
+pub fn serialize_quote_transfer_message(payload: &QuoteTransferPayload) -> Vec<u8> {
+    frame_message(wormhole::MSG_TYPE_QUOTE_TRANSFER, &payload.try_to_vec().unwrap())
+}
+
+// Function to serialize a refund message
+pub fn serialize_refund_message(payload: &RefundPayload) -> Vec<u8> {
+    frame_message(wormhole::MSG_TYPE_REFUND, &payload.try_to_vec().unwrap())
+}
+
+/// Message fields of a Core Bridge PostedVAA account
+#[derive(Clone)]
+pub struct PostedVaa {
+    pub consistency_level: u8,
+    pub timestamp: u32,
+    pub nonce: u32,
+    pub sequence: u64,
+    pub emitter_chain: u16,
+    pub emitter_address: UniversalAddress,
+    pub payload: Vec<u8>,
 }
 
-// Function to deserialize a Wormhole message
+// Function to parse a Core Bridge PostedVAA account
+pub fn parse_posted_vaa(data: &[u8]) -> Result<PostedVaa> {
+    let vaa = crossify_common::parse_posted_vaa(data).map_err(codec_error)?;
+    Ok(PostedVaa {
+        consistency_level: vaa.consistency_level,
+        timestamp: vaa.timestamp,
+        nonce: vaa.nonce,
+        sequence: vaa.sequence,
+        emitter_chain: vaa.emitter_chain,
+        emitter_address: UniversalAddress { bytes: vaa.emitter_address },
+        payload: vaa.payload,
+    })
+}
+
+// Function to serialize a batch of complete messages as u16 count, then u32-length-prefixed entries
+pub fn serialize_batch_sync_message(messages: &[Vec<u8>]) -> Vec<u8> {
+    crossify_common::serialize_batch_sync_message(messages)
+}
+
+// Function to deserialize a Wormhole message into its type and body; the body must be exactly
+// as long as the header declares. ABI-encoded messages are accepted too and returned with their
+// body re-encoded as Borsh, so parsers handle both formats while remote chains migrate
 pub fn deserialize_wormhole_message(data: &[u8]) -> Result<(u8, Vec<u8>)> {
-    if data.is_empty() {
-        return Err(ProgramError::InvalidInstructionData.into());
-    }
-    
-    let message_type = data[0];
-    let payload = data[1..].to_vec();
-    
-    Ok((message_type, payload))
+    crossify_common::deserialize_message(data).map_err(codec_error)
 }
 
-// Function to parse a token creation message
+// Logs why a message failed to decode and maps it to the program's error
+fn codec_error(err: CodecError) -> Error {
+    msg!("{}", err);
+    let code = match err {
+        CodecError::EmptyPayload => TokenFactoryError::EmptyPayload,
+        CodecError::TruncatedPayload { .. } => TokenFactoryError::TruncatedPayload,
+        CodecError::TrailingBytes { .. } => TokenFactoryError::TrailingBytes,
+        CodecError::UnknownMessageType { .. } => TokenFactoryError::UnknownMessageType,
+        CodecError::UnknownVersion { .. } => TokenFactoryError::UnknownVersion,
+        CodecError::TooManyMessages { .. } => TokenFactoryError::PayloadFieldTooLong,
+        CodecError::Malformed { .. } => TokenFactoryError::DeserializeFailed,
+    };
+    error!(code)
+}
+
+// Logs which message type failed so the error is traceable without the raw payload
+fn deserialize_failed(message_type: u8) -> Error {
+    msg!("message_type={} payload failed to deserialize", message_type);
+    error!(TokenFactoryError::DeserializeFailed)
+}
+
+// Function to parse a token creation message; strings are held to the limits create_token applies
 pub fn parse_token_creation_message(payload: &[u8]) -> Result<TokenCreationPayload> {
-    TokenCreationPayload::try_from_slice(payload)
-        .map_err(|_| ProgramError::InvalidInstructionData.into())
+    let creation = TokenCreationPayload::try_from_slice(payload)
+        .map_err(|_| deserialize_failed(wormhole::MSG_TYPE_TOKEN_CREATION))?;
+    require_with_context!(
+        creation.name.len() <= MAX_NAME_LEN
+            && creation.symbol.len() <= MAX_REGISTERED_SYMBOL_LEN
+            && creation.metadata_uri.len() <= MAX_METADATA_URI_LEN,
+        TokenFactoryError::PayloadFieldTooLong,
+        "message_type={} name_len={} symbol_len={} metadata_uri_len={}",
+        wormhole::MSG_TYPE_TOKEN_CREATION,
+        creation.name.len(),
+        creation.symbol.len(),
+        creation.metadata_uri.len()
+    );
+    Ok(creation)
 }
 
 // Function to parse a price update message
 pub fn parse_price_update_message(payload: &[u8]) -> Result<PriceUpdatePayload> {
     PriceUpdatePayload::try_from_slice(payload)
-        .map_err(|_| ProgramError::InvalidInstructionData.into())
+        .map_err(|_| deserialize_failed(wormhole::MSG_TYPE_PRICE_UPDATE))
 }
 
 // Function to parse a liquidity update message
 pub fn parse_liquidity_update_message(payload: &[u8]) -> Result<LiquidityUpdatePayload> {
     LiquidityUpdatePayload::try_from_slice(payload)
-        .map_err(|_| ProgramError::InvalidInstructionData.into())
+        .map_err(|_| deserialize_failed(wormhole::MSG_TYPE_LIQUIDITY_UPDATE))
+}
+
+// Function to parse a supply update message
+pub fn parse_supply_update_message(payload: &[u8]) -> Result<SupplyUpdatePayload> {
+    SupplyUpdatePayload::try_from_slice(payload)
+        .map_err(|_| deserialize_failed(wormhole::MSG_TYPE_SUPPLY_UPDATE))
+}
+
+// Function to parse a token transfer message
+pub fn parse_token_transfer_message(payload: &[u8]) -> Result<TokenTransferPayload> {
+    TokenTransferPayload::try_from_slice(payload)
+        .map_err(|_| deserialize_failed(wormhole::MSG_TYPE_TOKEN_TRANSFER))
+}
+
+// Function to parse a remote buy message
+pub fn parse_remote_buy_message(payload: &[u8]) -> Result<RemoteBuyPayload> {
+    RemoteBuyPayload::try_from_slice(payload)
+        .map_err(|_| deserialize_failed(wormhole::MSG_TYPE_REMOTE_BUY))
+}
+
+// Function to parse a remote sell message
+pub fn parse_remote_sell_message(payload: &[u8]) -> Result<RemoteSellPayload> {
+    RemoteSellPayload::try_from_slice(payload)
+        .map_err(|_| deserialize_failed(wormhole::MSG_TYPE_REMOTE_SELL))
+}
+
+// Function to parse a quote transfer message
+pub fn parse_quote_transfer_message(payload: &[u8]) -> Result<QuoteTransferPayload> {
+    QuoteTransferPayload::try_from_slice(payload)
+        .map_err(|_| deserialize_failed(wormhole::MSG_TYPE_QUOTE_TRANSFER))
+}
+
+// Function to parse a refund message
+pub fn parse_refund_message(payload: &[u8]) -> Result<RefundPayload> {
+    RefundPayload::try_from_slice(payload)
+        .map_err(|_| deserialize_failed(wormhole::MSG_TYPE_REFUND))
+}
+
+// Function to split a batch sync body (after the message header) into its messages
+pub fn parse_batch_sync_message(payload: &[u8]) -> Result<Vec<Vec<u8>>> {
+    crossify_common::parse_batch_sync_message(payload).map_err(codec_error)
 }
 
 // In a real implementation, this would include the actual Wormhole integration
 // For now, this is a placeholder for the future integration
+
+// Checks the serializers and parsers against the wire-format fixtures shared with the EVM repo
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::collections::HashMap;
+    
+    const FIXTURES_PATH: &str = "../../../../integration/fixtures/wormhole_payloads.json";
+    
+    // Field values of one fixture, by field name
+    struct Fields(HashMap<String, String>);
+    
+    impl Fields {
+        fn str(&self, name: &str) -> String {
+            self.0
+                .get(name)
+                .unwrap_or_else(|| panic!("fixture has no field {}", name))
+                .clone()
+        }
+
+        fn num<T: std::str::FromStr>(&self, name: &str) -> T {
+            self.str(name)
+                .parse()
+                .unwrap_or_else(|_| panic!("field {} is not a number", name))
+        }
+
+        fn bytes32(&self, name: &str) -> [u8; 32] {
+            decode_hex(&self.str(name)).try_into().expect("32-byte field")
+        }
+
+        fn pubkey(&self, name: &str) -> Pubkey {
+            Pubkey::new_from_array(self.bytes32(name))
+        }
+
+        fn address(&self, name: &str) -> UniversalAddress {
+            UniversalAddress {
+                bytes: self.bytes32(name),
+            }
+        }
+    }
+
+    fn decode_hex(hex: &str) -> Vec<u8> {
+        let hex = hex.strip_prefix("0x").unwrap_or(hex);
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).expect("hex byte"))
+            .collect()
+    }
+
+    fn load_fixtures() -> Vec<(String, u8, Fields, Vec<u8>)> {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(FIXTURES_PATH);
+        let json: Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(json["version"], 1);
+        json["fixtures"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|fixture| {
+                let fields = fixture["fields"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|field| {
+                        (
+                            field["name"].as_str().unwrap().to_string(),
+                            field["value"].as_str().unwrap().to_string(),
+                        )
+                    })
+                    .collect();
+                (
+                    fixture["name"].as_str().unwrap().to_string(),
+                    fixture["message_type"].as_u64().unwrap() as u8,
+                    Fields(fields),
+                    decode_hex(fixture["hex"].as_str().unwrap()),
+                )
+            })
+            .collect()
+    }
+
+    // Encodes a fixture with the program's serializer for its message type
+    fn serialize_fixture(message_type: u8, f: &Fields) -> Vec<u8> {
+        match message_type {
+            wormhole::MSG_TYPE_TOKEN_CREATION => serialize_token_creation_message(&TokenCreationPayload {
+                factory: f.pubkey("factory"),
+                token_id: f.num("token_id"),
+                name: f.str("name"),
+                symbol: f.str("symbol"),
+                decimals: f.num("decimals"),
+                metadata_uri: f.str("metadata_uri"),
+                initial_supply: f.num("initial_supply"),
+                curve_type: f.num("curve_type"),
+                base_price: f.num("base_price"),
+                slope: f.num("slope"),
+                reserve_ratio: f.num("reserve_ratio"),
+            }),
+            wormhole::MSG_TYPE_PRICE_UPDATE => serialize_price_update_message(&PriceUpdatePayload {
+                factory: f.pubkey("factory"),
+                token_id: f.num("token_id"),
+                nonce: f.num("nonce"),
+                current_price: f.num("current_price"),
+                current_supply: f.num("current_supply"),
+                timestamp: f.num("timestamp"),
+            }),
+            wormhole::MSG_TYPE_LIQUIDITY_UPDATE => serialize_liquidity_update_message(&LiquidityUpdatePayload {
+                factory: f.pubkey("factory"),
+                token_id: f.num("token_id"),
+                liquidity_added: f.num("liquidity_added"),
+                liquidity_removed: f.num("liquidity_removed"),
+                current_liquidity: f.num("current_liquidity"),
+                timestamp: f.num("timestamp"),
+            }),
+            wormhole::MSG_TYPE_SUPPLY_UPDATE => serialize_supply_update_message(&SupplyUpdatePayload {
+                factory: f.pubkey("factory"),
+                token_id: f.num("token_id"),
+                total_supply: f.num("total_supply"),
+                total_burned: f.num("total_burned"),
+                timestamp: f.num("timestamp"),
+            }),
+            wormhole::MSG_TYPE_TOKEN_TRANSFER => serialize_token_transfer_message(&TokenTransferPayload {
+                factory: f.pubkey("factory"),
+                token_id: f.num("token_id"),
+                amount: f.num("amount"),
+                sender: f.pubkey("sender"),
+                recipient_chain: f.num("recipient_chain"),
+                recipient: f.address("recipient"),
+                timestamp: f.num("timestamp"),
+            }),
+            wormhole::MSG_TYPE_REMOTE_BUY => serialize_remote_buy_message(&RemoteBuyPayload {
+                factory: f.pubkey("factory"),
+                token_id: f.num("token_id"),
+                quote_amount: f.num("quote_amount"),
+                min_tokens: f.num("min_tokens"),
+                buyer: f.address("buyer"),
+                recipient_chain: f.num("recipient_chain"),
+                recipient: f.address("recipient"),
+                timestamp: f.num("timestamp"),
+            }),
+            wormhole::MSG_TYPE_REMOTE_SELL => serialize_remote_sell_message(&RemoteSellPayload {
+                factory: f.pubkey("factory"),
+                token_id: f.num("token_id"),
+                amount: f.num("amount"),
+                min_proceeds: f.num("min_proceeds"),
+                seller: f.address("seller"),
+                timestamp: f.num("timestamp"),
+            }),
+            wormhole::MSG_TYPE_QUOTE_TRANSFER => serialize_quote_transfer_message(&QuoteTransferPayload {
+                factory: f.pubkey("factory"),
+                token_id: f.num("token_id"),
+                quote_mint: f.pubkey("quote_mint"),
+                amount: f.num("amount"),
+                recipient_chain: f.num("recipient_chain"),
+                recipient: f.address("recipient"),
+                timestamp: f.num("timestamp"),
+            }),
+            wormhole::MSG_TYPE_REFUND => serialize_refund_message(&RefundPayload {
+                factory: f.pubkey("factory"),
+                token_id: f.num("token_id"),
+                sequence: f.num("sequence"),
+                message_type: f.num("message_type"),
+                reason: f.num("reason"),
+                amount: f.num("amount"),
+                recipient: f.address("recipient"),
+                timestamp: f.num("timestamp"),
+            }),
+            message_type => panic!("no serializer for message type {}", message_type),
+        }
+    }
+
+    // Decodes fixture bytes with the program's parser and encodes the result again
+    fn reserialize(message_type: u8, body: &[u8]) -> Vec<u8> {
+        match message_type {
+            wormhole::MSG_TYPE_TOKEN_CREATION => {
+                serialize_token_creation_message(&parse_token_creation_message(body).unwrap())
+            }
+            wormhole::MSG_TYPE_PRICE_UPDATE => {
+                serialize_price_update_message(&parse_price_update_message(body).unwrap())
+            }
+            wormhole::MSG_TYPE_LIQUIDITY_UPDATE => {
+                serialize_liquidity_update_message(&parse_liquidity_update_message(body).unwrap())
+            }
+            wormhole::MSG_TYPE_SUPPLY_UPDATE => {
+                serialize_supply_update_message(&parse_supply_update_message(body).unwrap())
+            }
+            wormhole::MSG_TYPE_TOKEN_TRANSFER => {
+                serialize_token_transfer_message(&parse_token_transfer_message(body).unwrap())
+            }
+            wormhole::MSG_TYPE_REMOTE_BUY => serialize_remote_buy_message(&parse_remote_buy_message(body).unwrap()),
+            wormhole::MSG_TYPE_REMOTE_SELL => serialize_remote_sell_message(&parse_remote_sell_message(body).unwrap()),
+            wormhole::MSG_TYPE_QUOTE_TRANSFER => {
+                serialize_quote_transfer_message(&parse_quote_transfer_message(body).unwrap())
+            }
+            wormhole::MSG_TYPE_REFUND => serialize_refund_message(&parse_refund_message(body).unwrap()),
+            message_type => panic!("no parser for message type {}", message_type),
+        }
+    }
+
+    #[test]
+    fn idl_constants_match_crossify_common() {
+        assert_eq!(wormhole::CHAIN_ID_SOLANA, crossify_common::CHAIN_ID_SOLANA);
+        assert_eq!(wormhole::CHAIN_ID_ETHEREUM, crossify_common::CHAIN_ID_ETHEREUM);
+        assert_eq!(wormhole::CHAIN_ID_BSC, crossify_common::CHAIN_ID_BSC);
+        assert_eq!(wormhole::CHAIN_ID_BASE, crossify_common::CHAIN_ID_BASE);
+        assert_eq!(wormhole::MSG_TYPE_TOKEN_CREATION, crossify_common::MSG_TYPE_TOKEN_CREATION);
+        assert_eq!(wormhole::MSG_TYPE_PRICE_UPDATE, crossify_common::MSG_TYPE_PRICE_UPDATE);
+        assert_eq!(wormhole::MSG_TYPE_LIQUIDITY_UPDATE, crossify_common::MSG_TYPE_LIQUIDITY_UPDATE);
+        assert_eq!(wormhole::MSG_TYPE_SUPPLY_UPDATE, crossify_common::MSG_TYPE_SUPPLY_UPDATE);
+        assert_eq!(wormhole::MSG_TYPE_TOKEN_TRANSFER, crossify_common::MSG_TYPE_TOKEN_TRANSFER);
+        assert_eq!(wormhole::MSG_TYPE_BATCH_SYNC, crossify_common::MSG_TYPE_BATCH_SYNC);
+        assert_eq!(wormhole::MSG_TYPE_REMOTE_BUY, crossify_common::MSG_TYPE_REMOTE_BUY);
+        assert_eq!(wormhole::MSG_TYPE_REMOTE_SELL, crossify_common::MSG_TYPE_REMOTE_SELL);
+        assert_eq!(wormhole::MSG_TYPE_QUOTE_TRANSFER, crossify_common::MSG_TYPE_QUOTE_TRANSFER);
+        assert_eq!(wormhole::MSG_TYPE_REFUND, crossify_common::MSG_TYPE_REFUND);
+    }
+
+    #[test]
+    fn serializers_match_fixtures() {
+        let fixtures = load_fixtures();
+        assert!(!fixtures.is_empty());
+        for (name, message_type, fields, expected) in &fixtures {
+            assert_eq!(&serialize_fixture(*message_type, fields), expected, "fixture {}", name);
+        }
+    }
+
+    #[test]
+    fn parsers_accept_fixtures() {
+        for (name, message_type, _, bytes) in &load_fixtures() {
+            let (parsed_type, body) = deserialize_wormhole_message(bytes).unwrap();
+            assert_eq!(parsed_type, *message_type, "fixture {}", name);
+            assert_eq!(&reserialize(parsed_type, &body), bytes, "fixture {}", name);
+        }
+    }
+}
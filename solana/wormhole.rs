@@ -1,118 +0,0 @@
-// Wormhole integration module for Crossify Token Factory
-// This file contains the integration with Wormhole for cross-chain messaging
-
-use anchor_lang::prelude::*;
-use std::mem::size_of;
-
-// Wormhole program IDs
-pub mod wormhole {
-    use anchor_lang::prelude::*;
-    
-    // Wormhole Core Bridge program ID on Solana Devnet
-    pub const CORE_BRIDGE_PROGRAM_ID: &str = "3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5";
-    
-    // Wormhole Token Bridge program ID on Solana Devnet
-    pub const TOKEN_BRIDGE_PROGRAM_ID: &str = "DZnkkTmCiFWfYTfT41X3Rd1kDgozqzxWaHqsw6W4x2oe";
-    
-    // Chain IDs in Wormhole ecosystem
-    pub const CHAIN_ID_SOLANA: u16 = 1;
-    pub const CHAIN_ID_ETHEREUM: u16 = 2;
-    pub const CHAIN_ID_BSC: u16 = 4;
-    pub const CHAIN_ID_BASE: u16 = 30; // Example value, may need to be updated
-    
-    // Message types
-    pub const MSG_TYPE_TOKEN_CREATION: u8 = 1;
-    pub const MSG_TYPE_PRICE_UPDATE: u8 = 2;
-    pub const MSG_TYPE_LIQUIDITY_UPDATE: u8 = 3;
-}
-
-// Wormhole message payload structure for token creation
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct TokenCreationPayload {
-    pub token_id: u64,
-    pub name: String,
-    pub symbol: String,
-    pub decimals: u8,
-    pub metadata_uri: String,
-    pub initial_supply: u64,
-    pub curve_type: u8,
-    pub base_price: u64,
-    pub slope: u64,
-    pub reserve_ratio: u16,
-}
-
-// Wormhole message payload structure for price updates
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct PriceUpdatePayload {
-    pub token_id: u64,
-    pub current_price: u64,
-    pub current_supply: u64,
-    pub timestamp: i64,
-}
-
-// Wormhole message payload structure for liquidity updates
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct LiquidityUpdatePayload {
-    pub token_id: u64,
-    pub liquidity_added: u64,
-    pub liquidity_removed: u64,
-    pub current_liquidity: u64,
-    pub timestamp: i64,
-}
-
-// Function to serialize a token creation message
-pub fn serialize_token_creation_message(payload: &TokenCreationPayload) -> Vec<u8> {
-    let mut message = Vec::new();
-    message.push(wormhole::MSG_TYPE_TOKEN_CREATION);
-    message.extend_from_slice(&payload.try_to_vec().unwrap());
-    message
-}
-
-// Function to serialize a price update message
-pub fn serialize_price_update_message(payload: &PriceUpdatePayload) -> Vec<u8> {
-    let mut message = Vec::new();
-    message.push(wormhole::MSG_TYPE_PRICE_UPDATE);
-    message.extend_from_slice(&payload.try_to_vec().unwrap());
-    message
-}
-
-// Function to serialize a liquidity update message
-pub fn serialize_liquidity_update_message(payload: &LiquidityUpdatePayload) -> Vec<u8> {
-    let mut message = Vec::new();
-    message.push(wormhole::MSG_TYPE_LIQUIDITY_UPDATE);
-    message.extend_from_slice(&payload.try_to_vec().unwrap());
-    message
-}
-
-// Function to deserialize a Wormhole message
-pub fn deserialize_wormhole_message(data: &[u8]) -> Result<(u8, Vec<u8>)> {
-    if data.is_empty() {
-        return Err(ProgramError::InvalidInstructionData.into());
-    }
-    
-    let message_type = data[0];
-    let payload = data[1..].to_vec();
-    
-    Ok((message_type, payload))
-}
-
-// Function to parse a token creation message
-pub fn parse_token_creation_message(payload: &[u8]) -> Result<TokenCreationPayload> {
-    TokenCreationPayload::try_from_slice(payload)
-        .map_err(|_| ProgramError::InvalidInstructionData.into())
-}
-
-// Function to parse a price update message
-pub fn parse_price_update_message(payload: &[u8]) -> Result<PriceUpdatePayload> {
-    PriceUpdatePayload::try_from_slice(payload)
-        .map_err(|_| ProgramError::InvalidInstructionData.into())
-}
-
-// Function to parse a liquidity update message
-pub fn parse_liquidity_update_message(payload: &[u8]) -> Result<LiquidityUpdatePayload> {
-    LiquidityUpdatePayload::try_from_slice(payload)
-        .map_err(|_| ProgramError::InvalidInstructionData.into())
-}
-
-// In a real implementation, this would include the actual Wormhole integration
-// For now, this is a placeholder for the future integration